@@ -0,0 +1,63 @@
+// Times folding a generated 1M-element array into a shape, and inferring types from it.
+// Run with `cargo bench --bench fold`.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use jsonc::{infer_schema, GeneratorOptions, Shape};
+use serde_json::{json, Value};
+
+const ELEMENTS: usize = 1_000_000;
+const RUNS: u32 = 5;
+
+// Objects alike but for their values, with an address nested in each, every thousandth
+// one missing a key
+fn alike() -> Value {
+    (0..ELEMENTS)
+        .map(|i| {
+            let mut element = json!({
+                "id": i,
+                "name": format!("user {i}"),
+                "score": i as f64 / 2.0,
+                "tags": ["a", "b"],
+                "address": {"street": format!("{i} Main St"), "zip": "12345", "geo": [1.5, 2.5]},
+            });
+            if i % 1000 == 999 {
+                element.as_object_mut().unwrap().remove("tags");
+            }
+            element
+        })
+        .collect()
+}
+
+// Objects each with a key of their own, so none of them can be skipped. Every key is
+// absent from all but one, fewer of them are enough.
+fn distinct() -> Value {
+    (0..ELEMENTS / 1000)
+        .map(|i| json!({ "id": i, format!("key_{i}"): {"value": i} }))
+        .collect()
+}
+
+fn bench(name: &str, mut run: impl FnMut()) {
+    let mut total = Duration::ZERO;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        run();
+        total += start.elapsed();
+    }
+    println!("{name:<24} {:>10.2?} per run", total / RUNS);
+}
+
+fn main() {
+    let opts = GeneratorOptions::default();
+    let alike = alike();
+    bench("shape of alike", || {
+        black_box(Shape::of(&alike));
+    });
+    bench("infer alike", || {
+        black_box(infer_schema(&alike, &opts).unwrap());
+    });
+    let distinct = distinct();
+    bench("shape of distinct", || {
+        black_box(Shape::of(&distinct));
+    });
+}
@@ -0,0 +1,49 @@
+// Times generating types from a ~50MB document, records with objects nested 20 deep, each
+// level a type holding the one below it. Run with `cargo bench --bench large`.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use jsonc::{generate_types, get_language_formatter, GeneratorOptions};
+use serde_json::{json, Value};
+
+const RECORDS: usize = 28_000;
+const DEPTH: usize = 20;
+const RUNS: u32 = 3;
+
+fn nested(record: usize, level: usize) -> Value {
+    let mut object = json!({
+        "id": record,
+        "name": format!("level {level} of record {record}"),
+        "score": 1.5,
+        "tags": ["a", "b"],
+        "active": true,
+    });
+    if level < DEPTH {
+        object[format!("level_{}", level + 1)] = nested(record, level + 1);
+    }
+    object
+}
+
+fn bench(name: &str, mut run: impl FnMut()) {
+    let mut total = Duration::ZERO;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        run();
+        total += start.elapsed();
+    }
+    println!("{name:<24} {:>10.2?} per run", total / RUNS);
+}
+
+fn main() {
+    let opts = GeneratorOptions::default();
+    let text =
+        serde_json::to_string(&(0..RECORDS).map(|i| nested(i, 1)).collect::<Value>()).unwrap();
+    println!("{:.1}MB of JSON", text.len() as f64 / 1e6);
+    let large: Value = serde_json::from_str(&text).unwrap();
+    for language in ["rust", "go", "java"] {
+        let lang = get_language_formatter(language, &opts).unwrap();
+        bench(&format!("generate {language}"), || {
+            black_box(generate_types(&large, lang.as_ref(), &opts).unwrap());
+        });
+    }
+}
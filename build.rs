@@ -0,0 +1,22 @@
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    // Embed the short commit hash when building from a git checkout, so that
+    // `jsonc --version` can tell source builds apart from released ones.
+    if Path::new(".git/HEAD").exists() {
+        println!("cargo:rerun-if-changed=.git/HEAD");
+        println!("cargo:rerun-if-changed=.git/refs/heads");
+    }
+
+    let git_hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+
+    if let Some(hash) = git_hash {
+        println!("cargo:rustc-env=JSONC_GIT_HASH={}", hash.trim());
+    }
+}
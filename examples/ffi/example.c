@@ -0,0 +1,36 @@
+/*
+ * Calls jsonc from C, once successfully and once with an error. From the repository root:
+ *   cargo rustc --release --lib --crate-type cdylib --no-default-features --features ffi
+ *   cc examples/ffi/example.c -Iexamples/ffi -Ltarget/release -ljsonc -o example
+ *   LD_LIBRARY_PATH=target/release ./example
+ */
+#include <stdio.h>
+
+#include "jsonc.h"
+
+static int generate(const char *json, const char *language, const char *options) {
+    char *out = NULL;
+    char *err = NULL;
+    int status = jsonc_generate(json, language, options, &out, &err);
+    if (status == 0) {
+        printf("%s", out);
+    } else {
+        fprintf(stderr, "error: %s\n", err);
+    }
+    jsonc_free(out);
+    jsonc_free(err);
+    return status;
+}
+
+int main(void) {
+    const char *json = "{\"name\": \"jsonc\", \"tags\": [\"json\", \"codegen\"]}";
+
+    if (generate(json, "go", "{\"root_name\": \"Package\"}") != 0) {
+        return 1;
+    }
+    /* Fails with the list of supported languages */
+    if (generate(json, "cobol", NULL) == 0) {
+        return 1;
+    }
+    return 0;
+}
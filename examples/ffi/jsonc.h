@@ -0,0 +1,31 @@
+/*
+ * C bindings of jsonc, built into libjsonc with:
+ *   cargo rustc --release --lib --crate-type cdylib --no-default-features --features ffi
+ */
+#ifndef JSONC_H
+#define JSONC_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Generates code in `language` from the JSON text `json`. `options_json` is a JSON object
+ * of generator options, e.g. {"root_name": "Package", "annotations": false}, or NULL for
+ * the defaults. All three are nul terminated UTF-8.
+ *
+ * Returns 0 and sets *out to the generated code on success, otherwise returns 1 and sets
+ * *err to an error message. The one not set is NULL. Both are owned by the caller and
+ * have to be released with jsonc_free, out and err may be NULL to ignore them.
+ */
+int jsonc_generate(const char *json, const char *language, const char *options_json,
+                   char **out, char **err);
+
+/* Releases a string returned by jsonc_generate, NULL is ignored */
+void jsonc_free(char *text);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
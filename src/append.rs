@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use jsonc::generate::Generated;
+use jsonc::LanguageFormatter;
+
+// A file generated before, with the types it was missing added at the end
+pub struct Appended {
+    pub text: String,
+    // How many types were added
+    pub added: usize,
+    // Types the file already has, but defined differently than they're generated now
+    pub conflicts: Vec<String>,
+}
+
+// The lines of every type defined in `existing`, by name, from the line that starts the
+// definition to the end of the file
+fn defined_types<'a>(
+    existing: &'a str,
+    lang: &dyn LanguageFormatter,
+) -> HashMap<String, Vec<&'a str>> {
+    let lines: Vec<&str> = existing.lines().collect();
+    let mut types = HashMap::new();
+    for (index, line) in lines.iter().enumerate() {
+        if let Some(name) = lang.defined_type_name(line) {
+            types.entry(name).or_insert_with(|| lines[index..].to_vec());
+        }
+    }
+    types
+}
+
+// Adds the types of `generated` that `existing` doesn't define after everything in it,
+// separated by `separator`. Types it already defines are left as they are, even if they
+// now come out different, those are listed as conflicts.
+pub fn append_types(
+    existing: &str,
+    generated: &Generated,
+    lang: &dyn LanguageFormatter,
+    separator: &str,
+) -> Appended {
+    let defined = defined_types(existing, lang);
+    let mut missing = vec![];
+    let mut conflicts = vec![];
+
+    for type_output in generated.dependencies_first() {
+        let Some(lines) = defined.get(&type_output.name) else {
+            missing.push(type_output.text.as_str());
+            continue;
+        };
+        // Compared from the line naming the type, lines before it, e.g. annotations, are
+        // left out of `defined` too. Indentation and alignment may have been changed by a
+        // formatter since.
+        let generated_lines: Vec<&str> = type_output
+            .text
+            .lines()
+            .skip_while(|line| lang.defined_type_name(line).is_none())
+            .collect();
+        let same = generated_lines
+            .iter()
+            .map(|line| line.split_whitespace())
+            .zip(lines)
+            .all(|(generated, line)| generated.eq(line.split_whitespace()));
+        if !same || lines.len() < generated_lines.len() {
+            conflicts.push(type_output.name.clone());
+        }
+    }
+
+    let mut text = existing.to_string();
+    if !missing.is_empty() {
+        text.truncate(existing.trim_end_matches('\n').len());
+        if !text.is_empty() {
+            text.push_str(separator);
+        }
+        text.push_str(&missing.join(separator));
+        text.push('\n');
+    }
+    Appended {
+        text,
+        added: missing.len(),
+        conflicts,
+    }
+}
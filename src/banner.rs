@@ -0,0 +1,84 @@
+use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use jsonc::constants::{GIT_HASH, VERSION};
+use jsonc::language::LanguageFormatter;
+
+pub fn version() -> String {
+    match GIT_HASH {
+        Some(hash) => format!("jsonc {VERSION} ({hash})"),
+        None => format!("jsonc {VERSION}"),
+    }
+}
+
+// Formats a unix timestamp as an RFC 3339 UTC date, e.g. 2023-06-01T12:30:00Z
+fn format_utc(unix_seconds: u64) -> String {
+    let days = (unix_seconds / 86_400) as i64;
+    let seconds_of_day = unix_seconds % 86_400;
+
+    // Howard Hinnant's days to civil date algorithm
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds_of_day / 3_600,
+        seconds_of_day % 3_600 / 60,
+        seconds_of_day % 60
+    )
+}
+
+// The options the program was run with, to regenerate the code with. --check only
+// compares the code and doesn't change it, so it's left out, or the code would never
+// match when checked.
+pub fn command_line() -> String {
+    env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--check"))
+        .map(|arg| shell_quote(&arg))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// `arg` the way a shell reads it back as a single word
+fn shell_quote(arg: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "-_./=,:@+%".contains(c);
+    if !arg.is_empty() && arg.chars().all(plain) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+// A comment stating that the code is generated, by which version, from what and how.
+// The timestamp is left out when `reproducible` is set, so that the output is stable.
+pub fn banner(
+    lang: &dyn LanguageFormatter,
+    source: &str,
+    command_line: &str,
+    reproducible: bool,
+) -> String {
+    let mut lines = vec![
+        format!(
+            "Code generated by {} from {source}. DO NOT EDIT.",
+            version()
+        ),
+        format!("Regenerate with: jsonc {command_line}"),
+    ];
+    if !reproducible {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        lines.push(format!("Generated at {}", format_utc(now)));
+    }
+    lines.iter().map(|line| lang.comment(line) + "\n").collect()
+}
@@ -0,0 +1,389 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use jsonc::examples::ExampleMode;
+use jsonc::ignore::IgnoreRules;
+use jsonc::input::{InputFormat, INPUT_FORMATS};
+use jsonc::naming::Case;
+use jsonc::options::RubyStyle;
+use jsonc::plural::PluralMap;
+use jsonc::rename::RenameMap;
+use jsonc::stats::StatsFormat;
+use jsonc::type_mapping::TypeMapping;
+use jsonc::warnings::{WarningCategory, Warnings};
+
+use crate::cli_error::Error;
+use crate::config::{
+    find_config, load_config, load_plural_map, load_rename_map, load_type_mapping,
+};
+use crate::highlight::ColorChoice;
+
+pub enum Command {
+    Help,
+    Version,
+    ListLanguages,
+    Generate(Box<Args>),
+}
+
+#[derive(Default)]
+pub struct Args {
+    pub language: Option<String>,
+    // Generates code with this Handlebars template, -l then only picks the comment syntax
+    pub template: Option<String>,
+    // Input files, or patterns matching them
+    pub inputs: Vec<String>,
+    pub input_format: Option<InputFormat>,
+    pub ndjson: bool,
+    pub streaming: bool,
+    // Reads NaN and Infinity, which JSON doesn't allow, as floats
+    pub lenient_numbers: bool,
+    pub merge: bool,
+    pub hidden: bool,
+    pub max_depth: Option<usize>,
+    // Types the parts of the input that can't be generated from as Any instead of failing
+    pub best_effort: bool,
+    pub max_size: Option<u64>,
+    pub jobs: Option<usize>,
+    pub select: Option<String>,
+    // Fields left out of the input before generating from it
+    pub ignored: IgnoreRules,
+    pub output: Option<String>,
+    pub output_dir: Option<String>,
+    // A file generated before, that only the types it doesn't define yet are added to
+    pub append: Option<String>,
+    pub watch: bool,
+    pub check: bool,
+    // Compared with instead of the -o file
+    pub check_file: Option<String>,
+    // Whitespace-only differences don't count
+    pub check_loose: bool,
+    pub color: ColorChoice,
+    pub banner: bool,
+    pub reproducible: bool,
+    pub separator: Option<String>,
+    // Every type is put in this namespace
+    pub namespace: Option<String>,
+    // Name of the root type instead of the language's default
+    pub root_name: Option<String>,
+    pub type_prefix: Option<String>,
+    pub type_suffix: Option<String>,
+    // Cases fields and types are named in instead of the language's
+    pub field_case: Option<Case>,
+    pub type_case: Option<Case>,
+    // What Ruby types are generated as
+    pub ruby_style: Option<RubyStyle>,
+    // Names given to keys instead of the ones made from them
+    pub renames: RenameMap,
+    // Types given to fields instead of the inferred ones
+    pub type_mapping: TypeMapping,
+    // Singulars of plural keys, from --plural-map and --plural
+    pub plurals: PluralMap,
+    // Leaves the namespace out when there's a single type
+    pub no_wrap_single: bool,
+    // Types fields holding a single-field object as what it holds, only under these keys
+    // unless there are none
+    pub flatten_wrappers: Option<Vec<String>>,
+    pub indent: Option<String>,
+    pub no_annotations: bool,
+    pub examples: Option<ExampleMode>,
+    // Types strings that are always numbers as numbers
+    pub coerce_numeric_strings: bool,
+    // Comments fields whose type couldn't be inferred with a TODO
+    pub todo_comments: bool,
+    // Tells fields that may be missing from fields that may be null
+    pub strict_null: bool,
+    // Follows the types with the input written as a value of them
+    pub emit_sample: bool,
+    // Fails the run if there are fields whose type couldn't be inferred
+    pub fail_on_any: bool,
+    pub stats: Option<StatsFormat>,
+    // Writes the inferred schema as JSON instead of generating code
+    pub emit_ir: bool,
+    // Writes the types and their references as a DOT digraph instead of generating code
+    pub graph: bool,
+    pub quiet: bool,
+    // Fails the run on warnings in these categories, or any if there are none
+    pub warnings_as_errors: Option<Vec<WarningCategory>>,
+    // Problems with the config file, reported along with the ones from generation
+    pub warnings: Warnings,
+}
+
+// Splits `--flag=value` into the flag and its inline value
+fn split_flag(arg: &str) -> (&str, Option<&str>) {
+    match arg.split_once('=') {
+        Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+        _ => (arg, None),
+    }
+}
+
+fn flag_value(
+    flag: &str,
+    inline: Option<&str>,
+    rest: &mut impl Iterator<Item = String>,
+) -> Result<String, Error> {
+    match inline {
+        Some(value) => Ok(value.to_string()),
+        None => rest
+            .next()
+            .ok_or_else(|| Error::Usage(format!("missing value for {flag}"))),
+    }
+}
+
+fn number_value<T: FromStr>(
+    flag: &str,
+    inline: Option<&str>,
+    rest: &mut impl Iterator<Item = String>,
+) -> Result<T, Error> {
+    let value = flag_value(flag, inline, rest)?;
+    value.parse().map_err(|_| {
+        Error::Usage(format!(
+            "invalid value '{value}' for {flag}, expected a number"
+        ))
+    })
+}
+
+fn case_value(
+    flag: &str,
+    inline: Option<&str>,
+    rest: &mut impl Iterator<Item = String>,
+) -> Result<Case, Error> {
+    let value = flag_value(flag, inline, rest)?;
+    Case::from_name(&value).ok_or_else(|| {
+        Error::Usage(format!(
+            "unknown case '{value}' for {flag}, expected snake, camel, pascal or keep"
+        ))
+    })
+}
+
+// Shells make it awkward to pass newlines and tabs, so they can be written as `\n` and `\t`
+fn unescape(value: &str) -> String {
+    value.replace("\\n", "\n").replace("\\t", "\t")
+}
+
+// Finds the config file to read defaults from, unless `--no-config` was passed
+fn config_path(args: &[String]) -> Result<Option<PathBuf>, Error> {
+    if args.iter().any(|arg| arg == "--no-config") {
+        return Ok(None);
+    }
+    let mut args = args.iter().cloned();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = split_flag(&arg);
+        if flag == "--config" {
+            return flag_value(flag, inline, &mut args).map(|path| Some(PathBuf::from(path)));
+        }
+    }
+    Ok(find_config())
+}
+
+// Parses the command line arguments, not including the application name.
+// Options from the config file are applied first, so the command line overrides them.
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, Error> {
+    let args: Vec<String> = args.into_iter().collect();
+    let mut parsed = Args::default();
+
+    if let Some(path) = config_path(&args)? {
+        let mut warnings = Warnings::default();
+        for (key, key_args) in load_config(&path, &mut warnings)? {
+            if let Err(e) = parse_into(&mut parsed, key_args, false) {
+                warnings.push(
+                    WarningCategory::Config,
+                    format!("{}: ignoring '{key}': {e}", path.display()),
+                );
+            }
+        }
+        parsed.warnings = warnings;
+    }
+
+    match parse_into(&mut parsed, args, true)? {
+        Some(command) => Ok(command),
+        None => Ok(Command::Generate(Box::new(parsed))),
+    }
+}
+
+// Applies `args` on top of `parsed`. Returns the command to run instead of generating,
+// if one was requested. Positional arguments are only accepted if `positional` is set.
+fn parse_into(
+    parsed: &mut Args,
+    args: impl IntoIterator<Item = String>,
+    positional: bool,
+) -> Result<Option<Command>, Error> {
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline) = split_flag(&arg);
+        match flag {
+            "-h" | "--help" if positional => return Ok(Some(Command::Help)),
+            "-V" | "--version" if positional => return Ok(Some(Command::Version)),
+            "--list-languages" if positional => return Ok(Some(Command::ListLanguages)),
+            // Already taken care of by `config_path`
+            "--config" if positional => {
+                flag_value(flag, inline, &mut args)?;
+            }
+            "--no-config" if positional => {}
+            "-l" | "--language" => parsed.language = Some(flag_value(flag, inline, &mut args)?),
+            "--input-format" => {
+                let name = flag_value(flag, inline, &mut args)?;
+                let format = InputFormat::from_name(&name).ok_or_else(|| {
+                    Error::Usage(format!(
+                        "unknown input format '{name}', supported formats are: {}",
+                        INPUT_FORMATS.join(", ")
+                    ))
+                })?;
+                parsed.input_format = Some(format);
+            }
+            "--ndjson" => parsed.ndjson = true,
+            "--streaming" => parsed.streaming = true,
+            "--lenient-numbers" => parsed.lenient_numbers = true,
+            "--merge" => parsed.merge = true,
+            "--hidden" => parsed.hidden = true,
+            "--best-effort" => parsed.best_effort = true,
+            "--jobs" => parsed.jobs = Some(number_value(flag, inline, &mut args)?),
+            "--max-depth" => parsed.max_depth = Some(number_value(flag, inline, &mut args)?),
+            "--max-size" => parsed.max_size = Some(number_value(flag, inline, &mut args)?),
+            "--select" => parsed.select = Some(flag_value(flag, inline, &mut args)?),
+            "--ignore-key" => parsed
+                .ignored
+                .insert_key(flag_value(flag, inline, &mut args)?),
+            "--ignore-path" => parsed
+                .ignored
+                .insert_path(&flag_value(flag, inline, &mut args)?),
+            "--emit-ir" => parsed.emit_ir = true,
+            "--graph" => parsed.graph = true,
+            "--template" => parsed.template = Some(flag_value(flag, inline, &mut args)?),
+            "-o" | "--output" => parsed.output = Some(flag_value(flag, inline, &mut args)?),
+            "--output-dir" => parsed.output_dir = Some(flag_value(flag, inline, &mut args)?),
+            "--append" => parsed.append = Some(flag_value(flag, inline, &mut args)?),
+            "--watch" => parsed.watch = true,
+            // The file is optional, so it can only be given inline
+            "--check" => {
+                parsed.check = true;
+                parsed.check_file = inline.map(String::from);
+            }
+            "--check-loose" => {
+                parsed.check = true;
+                parsed.check_loose = true;
+            }
+            // The mode is optional, so it can only be given inline
+            "--with-examples" => {
+                let name = inline.unwrap_or("values");
+                let mode = ExampleMode::from_name(name).ok_or_else(|| {
+                    Error::Usage(format!(
+                        "unknown example mode '{name}', expected values or redact-strings"
+                    ))
+                })?;
+                parsed.examples = Some(mode);
+            }
+            "--coerce-numeric-strings" => parsed.coerce_numeric_strings = true,
+            "--todo-comments" => parsed.todo_comments = true,
+            "--strict-null" => parsed.strict_null = true,
+            "--emit-sample" => parsed.emit_sample = true,
+            // The keys are optional, so they can only be given inline
+            "--flatten-wrappers" => {
+                let keys = match inline {
+                    None => vec![],
+                    Some(spec) => spec
+                        .strip_prefix("keys=")
+                        .ok_or_else(|| {
+                            Error::Usage(format!(
+                                "invalid --flatten-wrappers '{spec}', expected keys=KEY,KEY..."
+                            ))
+                        })?
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|key| !key.is_empty())
+                        .map(String::from)
+                        .collect(),
+                };
+                parsed.flatten_wrappers = Some(keys);
+            }
+            "--fail-on-any" => parsed.fail_on_any = true,
+            "--banner" => parsed.banner = true,
+            "--no-banner" => parsed.banner = false,
+            "--reproducible" => parsed.reproducible = true,
+            "--separator" => {
+                parsed.separator = Some(unescape(&flag_value(flag, inline, &mut args)?));
+            }
+            "--namespace" | "--wrap" => {
+                parsed.namespace = Some(flag_value(flag, inline, &mut args)?)
+            }
+            "--no-wrap-single" => parsed.no_wrap_single = true,
+            "--rename-map" => {
+                parsed.renames = load_rename_map(&flag_value(flag, inline, &mut args)?)?
+            }
+            "--type-mapping" => {
+                parsed.type_mapping = load_type_mapping(&flag_value(flag, inline, &mut args)?)?
+            }
+            "--plural-map" => parsed
+                .plurals
+                .extend(load_plural_map(&flag_value(flag, inline, &mut args)?)?),
+            "--plural" => {
+                let entry = flag_value(flag, inline, &mut args)?;
+                let (plural, singular) = entry.split_once('=').ok_or_else(|| {
+                    Error::Usage(format!(
+                        "invalid value '{entry}' for {flag}, expected plural=singular"
+                    ))
+                })?;
+                parsed.plurals.insert(plural, singular);
+            }
+            "--no-builtin-plurals" => parsed.plurals.builtins(false),
+            "--root-name" => parsed.root_name = Some(flag_value(flag, inline, &mut args)?),
+            "--type-prefix" => parsed.type_prefix = Some(flag_value(flag, inline, &mut args)?),
+            "--type-suffix" => parsed.type_suffix = Some(flag_value(flag, inline, &mut args)?),
+            "--field-case" => parsed.field_case = Some(case_value(flag, inline, &mut args)?),
+            "--type-case" => parsed.type_case = Some(case_value(flag, inline, &mut args)?),
+            "--ruby-style" => {
+                let name = flag_value(flag, inline, &mut args)?;
+                parsed.ruby_style = Some(RubyStyle::from_name(&name).ok_or_else(|| {
+                    Error::Usage(format!(
+                        "unknown Ruby style '{name}', expected struct or dry-struct"
+                    ))
+                })?);
+            }
+            "--indent" => parsed.indent = Some(unescape(&flag_value(flag, inline, &mut args)?)),
+            "--indent-width" => {
+                let width: usize = number_value(flag, inline, &mut args)?;
+                parsed.indent = Some(" ".repeat(width));
+            }
+            "--no-annotations" => parsed.no_annotations = true,
+            "--indent-tabs" => parsed.indent = Some(String::from("\t")),
+            "--stats" => parsed.stats = Some(StatsFormat::Text),
+            "--stats-json" => parsed.stats = Some(StatsFormat::Json),
+            "-q" | "--quiet" => parsed.quiet = true,
+            // The categories are optional, so they can only be given inline
+            "--warnings-as-errors" => {
+                let categories = inline
+                    .map(|names| {
+                        names
+                            .split(',')
+                            .map(|name| {
+                                WarningCategory::from_name(name.trim()).ok_or_else(|| {
+                                    Error::Usage(format!(
+                                        "unknown warning category '{name}' for {flag}"
+                                    ))
+                                })
+                            })
+                            .collect::<Result<Vec<_>, Error>>()
+                    })
+                    .transpose()?;
+                parsed.warnings_as_errors = Some(categories.unwrap_or_default());
+            }
+            "--color" => {
+                let name = flag_value(flag, inline, &mut args)?;
+                parsed.color = ColorChoice::from_name(&name).ok_or_else(|| {
+                    Error::Usage(format!(
+                        "unknown color choice '{name}', expected auto, always or never"
+                    ))
+                })?;
+            }
+            unknown if unknown.starts_with('-') => {
+                return Err(Error::Usage(format!("unknown option '{unknown}'")));
+            }
+            unexpected if !positional => {
+                return Err(Error::Usage(format!("unexpected value '{unexpected}'")));
+            }
+            filepath => parsed.inputs.push(filepath.to_string()),
+        }
+    }
+
+    Ok(None)
+}
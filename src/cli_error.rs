@@ -0,0 +1,91 @@
+use std::io;
+use std::path::Path;
+
+use jsonc::JsoncError;
+use thiserror::Error;
+
+use crate::counted;
+
+// Everything that can go wrong in a run of the command line interface
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Jsonc(#[from] JsoncError),
+    // Writing the generated code failed
+    #[error("could not write {path}: {source}")]
+    Write {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+    // A pattern given as input doesn't match any file
+    #[error("no files match '{0}'")]
+    NoMatch(String),
+    // Watching the input file for changes failed
+    #[error("could not watch {path}: {message}")]
+    Watch { path: String, message: String },
+    // The generated code differs from the files it's checked against
+    #[error("{} out of date (--check)", counted(*files, "file"))]
+    OutOfDate { files: usize },
+    // Some types couldn't be inferred and that was asked to fail the run. Lists the fields
+    // by their input and JSON pointer, even when warnings aren't shown.
+    #[error(
+        "{} could not be typed (--fail-on-any):\n  {}",
+        counted(fields.len(), "field"),
+        fields.join("\n  ")
+    )]
+    UntypedFields { fields: Vec<String> },
+    // Some of the input was typed as Any or skipped instead of failing the run
+    #[error(
+        "{} of the input typed as Any or skipped (--best-effort)",
+        counted(*parts, "part")
+    )]
+    Degraded { parts: usize },
+    // There were warnings and they were asked to be treated as errors
+    #[error(
+        "{} treated as errors (--warnings-as-errors)",
+        counted(*warnings, "warning")
+    )]
+    Warnings { warnings: usize },
+    // The config file could not be understood
+    #[error("invalid config file {path}: {message}")]
+    Config { path: String, message: String },
+    // The command line could not be understood
+    #[error("{0}")]
+    Usage(String),
+}
+
+impl Error {
+    pub fn write(path: &Path, source: io::Error) -> Self {
+        Error::Write {
+            path: path.display().to_string(),
+            source,
+        }
+    }
+
+    // Whether the user should be pointed at --help
+    pub fn is_usage(&self) -> bool {
+        matches!(
+            self,
+            Error::Usage(_) | Error::Jsonc(JsoncError::UnknownLanguage { .. })
+        )
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) | Error::Config { .. } => 2,
+            Error::Jsonc(JsoncError::UnknownLanguage { .. }) => 2,
+            // Scripts can tell inputs over the limits apart from invalid ones
+            Error::Jsonc(JsoncError::TooLarge { .. } | JsoncError::DepthExceeded { .. }) => 3,
+            // The code was generated, but not from all of the input
+            Error::Degraded { .. } => 4,
+            Error::Jsonc(_)
+            | Error::Write { .. }
+            | Error::NoMatch(_)
+            | Error::Watch { .. }
+            | Error::OutOfDate { .. }
+            | Error::UntypedFields { .. }
+            | Error::Warnings { .. } => 1,
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use jsonc::plural::PluralMap;
+use jsonc::rename::RenameMap;
+use jsonc::type_mapping::TypeMapping;
+use jsonc::warnings::{WarningCategory, Warnings};
+use jsonc::JsoncError;
+
+use crate::cli_error::Error;
+
+pub static CONFIG_FILE_NAME: &str = "jsonc.toml";
+
+// Looks for a config file in the current directory and then in each of its parents
+pub fn find_config() -> Option<PathBuf> {
+    let cwd = env::current_dir().ok()?;
+    cwd.ancestors()
+        .map(|dir| dir.join(CONFIG_FILE_NAME))
+        .find(|candidate| candidate.is_file())
+}
+
+// Reads a config file into the command line arguments it stands for, grouped by key.
+// Every key is the name of a long option, so `language = "go"` is `--language=go`,
+// `ndjson = true` is `--ndjson` and arrays repeat the option once per element. Tables do
+// too, once per entry given as key=value, e.g. `[plural] statuses = "status"`.
+pub fn load_config(
+    path: &Path,
+    warnings: &mut Warnings,
+) -> Result<Vec<(String, Vec<String>)>, Error> {
+    let display = path.display().to_string();
+    let text = fs::read_to_string(path).map_err(|e| JsoncError::io(Some(&display), e))?;
+    let table: toml::Table = text.parse().map_err(|e: toml::de::Error| Error::Config {
+        path: display.clone(),
+        message: e.to_string(),
+    })?;
+
+    let mut options = vec![];
+    for (key, value) in table {
+        let flag = format!("--{}", key.replace('_', "-"));
+        let values = match value {
+            toml::Value::Array(values) => values,
+            toml::Value::Table(entries) => entries
+                .into_iter()
+                .map(|(entry, value)| match value {
+                    toml::Value::String(s) => toml::Value::String(format!("{entry}={s}")),
+                    other => toml::Value::String(format!("{entry}={other}")),
+                })
+                .collect(),
+            single => vec![single],
+        };
+
+        let mut args = vec![];
+        for value in values {
+            match value {
+                toml::Value::Boolean(true) => args.push(flag.clone()),
+                toml::Value::Boolean(false) => {}
+                toml::Value::String(s) => args.push(format!("{flag}={s}")),
+                toml::Value::Integer(n) => args.push(format!("{flag}={n}")),
+                toml::Value::Float(n) => args.push(format!("{flag}={n}")),
+                other => {
+                    warnings.push(
+                        WarningCategory::Config,
+                        format!(
+                            "{display}: ignoring '{key}', a {} is not a valid option value",
+                            other.type_str()
+                        ),
+                    );
+                    args.clear();
+                    break;
+                }
+            }
+        }
+        if !args.is_empty() {
+            options.push((key, args));
+        }
+    }
+    Ok(options)
+}
+
+// Reads a --rename-map file, a TOML table or, for any other extension, a JSON object from
+// keys or JSON pointers to names
+pub fn load_rename_map(path: &str) -> Result<RenameMap, Error> {
+    let text = fs::read_to_string(path).map_err(|e| JsoncError::io(Some(path), e))?;
+    let invalid = |message: String| Error::Config {
+        path: path.to_string(),
+        message,
+    };
+    if !path.ends_with(".toml") {
+        return RenameMap::from_json(&text).map_err(|e| invalid(e.to_string()));
+    }
+    let table: toml::Table = text
+        .parse()
+        .map_err(|e: toml::de::Error| invalid(e.to_string()))?;
+    let mut renames = RenameMap::new();
+    for (entry, name) in table {
+        let name = name
+            .as_str()
+            .ok_or_else(|| invalid(format!("the rename of '{entry}' should be a string")))?;
+        renames.insert(&entry, name);
+    }
+    Ok(renames)
+}
+
+// Reads a --plural-map file of plural=singular lines, or a TOML table of strings
+pub fn load_plural_map(path: &str) -> Result<PluralMap, Error> {
+    let text = fs::read_to_string(path).map_err(|e| JsoncError::io(Some(path), e))?;
+    PluralMap::from_text(&text).map_err(|e| Error::Config {
+        path: path.to_string(),
+        message: e.to_string(),
+    })
+}
+
+// Reads a --type-mapping file, a TOML table or, for any other extension, a JSON object from
+// keys or JSON pointers to type names
+pub fn load_type_mapping(path: &str) -> Result<TypeMapping, Error> {
+    let text = fs::read_to_string(path).map_err(|e| JsoncError::io(Some(path), e))?;
+    let invalid = |message: String| Error::Config {
+        path: path.to_string(),
+        message,
+    };
+    if !path.ends_with(".toml") {
+        return TypeMapping::from_json(&text).map_err(|e| invalid(e.to_string()));
+    }
+    let table: toml::Table = text
+        .parse()
+        .map_err(|e: toml::de::Error| invalid(e.to_string()))?;
+    let mut mapping = TypeMapping::new();
+    for (entry, name) in table {
+        let name = name
+            .as_str()
+            .ok_or_else(|| invalid(format!("the type of '{entry}' should be a string")))?;
+        mapping
+            .insert_named(&entry, name)
+            .map_err(|e| invalid(e.to_string()))?;
+    }
+    Ok(mapping)
+}
@@ -5,7 +5,9 @@ pub static RUST_STRING: &str = "String";
 pub static RUST_INT: &str = "isize";
 pub static RUST_FLOAT: &str = "f64";
 pub static RUST_BOOL: &str = "bool";
-pub static RUST_AUTO_GENERATED: &str = "AutoGenerated";
+pub static RUST_AUTO_GENERATED: &str = "Root";
+// rustfmt indents with 4 spaces
+pub static RUST_INDENT: &str = "    ";
 
 // SCALA_TYPES
 pub static SCALA_ANY: &str = "Any";
@@ -13,7 +15,9 @@ pub static SCALA_STRING: &str = "String";
 pub static SCALA_INT: &str = "Int";
 pub static SCALA_FLOAT: &str = "Float";
 pub static SCALA_BOOL: &str = "Boolean";
-pub static SCALA_AUTO_GENERATED: &str = "AutoGenerated";
+pub static SCALA_AUTO_GENERATED: &str = "Root";
+// scalafmt indents with 2 spaces
+pub static SCALA_INDENT: &str = "  ";
 
 // GO_TYPES
 pub static GO_ANY: &str = "any";
@@ -31,6 +35,304 @@ pub static JAVA_STRING: &str = "String";
 pub static JAVA_INT: &str = "Integer";
 pub static JAVA_FLOAT: &str = "Float";
 pub static JAVA_BOOL: &str = "boolean";
-pub static JAVA_AUTO_GENERATED: &str = "AutoGenerated";
+pub static JAVA_AUTO_GENERATED: &str = "Root";
+pub static JAVA_INDENT: &str = "    ";
+
+// KOTLIN_TYPES
+// kotlinx.serialization has no serializer for Any
+pub static KOTLIN_ANY: &str = "JsonElement?";
+pub static KOTLIN_STRING: &str = "String";
+pub static KOTLIN_INT: &str = "Long";
+pub static KOTLIN_FLOAT: &str = "Double";
+pub static KOTLIN_BOOL: &str = "Boolean";
+pub static KOTLIN_AUTO_GENERATED: &str = "Root";
+// The Kotlin coding conventions indent with 4 spaces
+pub static KOTLIN_INDENT: &str = "    ";
+
+// PYTHON_TYPES
+pub static PYTHON_ANY: &str = "Any";
+pub static PYTHON_STRING: &str = "str";
+pub static PYTHON_INT: &str = "int";
+pub static PYTHON_FLOAT: &str = "float";
+pub static PYTHON_BOOL: &str = "bool";
+pub static PYTHON_AUTO_GENERATED: &str = "Model";
+// PEP 8 indents with 4 spaces
+pub static PYTHON_INDENT: &str = "    ";
+
+// DART_TYPES
+pub static DART_ANY: &str = "dynamic";
+pub static DART_STRING: &str = "String";
+pub static DART_INT: &str = "int";
+pub static DART_FLOAT: &str = "double";
+pub static DART_BOOL: &str = "bool";
+pub static DART_AUTO_GENERATED: &str = "Root";
+// dart format indents with 2 spaces
+pub static DART_INDENT: &str = "  ";
+
+// PROTO_TYPES
+pub static PROTO_ANY: &str = "google.protobuf.Value";
+pub static PROTO_STRING: &str = "string";
+pub static PROTO_INT: &str = "int64";
+pub static PROTO_FLOAT: &str = "double";
+pub static PROTO_BOOL: &str = "bool";
+// Arrays of arrays, repeated fields can't be repeated themselves
+pub static PROTO_LIST: &str = "google.protobuf.ListValue";
+pub static PROTO_AUTO_GENERATED: &str = "Root";
+// The style guide indents with 2 spaces
+pub static PROTO_INDENT: &str = "  ";
+
+// AVRO_TYPES
+// Avro has no type for any value, untyped fields are a union of null and these
+pub static AVRO_SCALARS: &[&str] = &["boolean", "long", "double", "string"];
+pub static AVRO_ANY: &str = "null";
+pub static AVRO_STRING: &str = "string";
+pub static AVRO_INT: &str = "long";
+pub static AVRO_FLOAT: &str = "double";
+pub static AVRO_BOOL: &str = "boolean";
+pub static AVRO_AUTO_GENERATED: &str = "Root";
+pub static AVRO_INDENT: &str = "  ";
+
+// GRAPHQL_TYPES
+// A custom scalar, declared before the types that use it
+pub static GRAPHQL_ANY: &str = "JSON";
+pub static GRAPHQL_STRING: &str = "String";
+pub static GRAPHQL_INT: &str = "Int";
+pub static GRAPHQL_FLOAT: &str = "Float";
+pub static GRAPHQL_BOOL: &str = "Boolean";
+pub static GRAPHQL_AUTO_GENERATED: &str = "Root";
+pub static GRAPHQL_INDENT: &str = "  ";
+
+// HASKELL_TYPES
+pub static HASKELL_ANY: &str = "Value";
+pub static HASKELL_STRING: &str = "Text";
+pub static HASKELL_INT: &str = "Int";
+pub static HASKELL_FLOAT: &str = "Double";
+pub static HASKELL_BOOL: &str = "Bool";
+pub static HASKELL_AUTO_GENERATED: &str = "Root";
+pub static HASKELL_INDENT: &str = "  ";
+
+// ELM_TYPES
+pub static ELM_ANY: &str = "Decode.Value";
+pub static ELM_STRING: &str = "String";
+pub static ELM_INT: &str = "Int";
+pub static ELM_FLOAT: &str = "Float";
+pub static ELM_BOOL: &str = "Bool";
+pub static ELM_AUTO_GENERATED: &str = "Root";
+// elm-format indents with 4 spaces
+pub static ELM_INDENT: &str = "    ";
+
+// OCAML_TYPES
+pub static OCAML_ANY: &str = "Yojson.Safe.t";
+pub static OCAML_STRING: &str = "string";
+pub static OCAML_INT: &str = "int";
+pub static OCAML_FLOAT: &str = "float";
+pub static OCAML_BOOL: &str = "bool";
+pub static OCAML_ROOT: &str = "t";
+pub static OCAML_INDENT: &str = "  ";
+
+// FSHARP_TYPES
+pub static FSHARP_ANY: &str = "JsonElement";
+pub static FSHARP_STRING: &str = "string";
+pub static FSHARP_INT: &str = "int64";
+pub static FSHARP_FLOAT: &str = "float";
+pub static FSHARP_BOOL: &str = "bool";
+pub static FSHARP_AUTO_GENERATED: &str = "Root";
+pub static FSHARP_INDENT: &str = "    ";
+
+// ZIG_TYPES
+pub static ZIG_ANY: &str = "std.json.Value";
+pub static ZIG_STRING: &str = "[]const u8";
+pub static ZIG_INT: &str = "i64";
+pub static ZIG_FLOAT: &str = "f64";
+pub static ZIG_BOOL: &str = "bool";
+pub static ZIG_AUTO_GENERATED: &str = "Root";
+// zig fmt indents with 4 spaces
+pub static ZIG_INDENT: &str = "    ";
+
+// C_TYPES
+// Untyped values are left to the reader, e.g. as a parsed JSON node
+pub static C_ANY: &str = "void *";
+pub static C_STRING: &str = "char *";
+pub static C_INT: &str = "int64_t";
+pub static C_FLOAT: &str = "double";
+pub static C_BOOL: &str = "bool";
+pub static C_AUTO_GENERATED: &str = "Root";
+pub static C_INDENT: &str = "    ";
+
+// CPP_TYPES
+pub static CPP_ANY: &str = "nlohmann::json";
+pub static CPP_STRING: &str = "std::string";
+pub static CPP_INT: &str = "int64_t";
+pub static CPP_FLOAT: &str = "double";
+pub static CPP_BOOL: &str = "bool";
+pub static CPP_AUTO_GENERATED: &str = "Root";
+pub static CPP_INDENT: &str = "    ";
+
+// PHP_TYPES
+pub static PHP_ANY: &str = "mixed";
+pub static PHP_STRING: &str = "string";
+pub static PHP_INT: &str = "int";
+pub static PHP_FLOAT: &str = "float";
+pub static PHP_BOOL: &str = "bool";
+pub static PHP_AUTO_GENERATED: &str = "Root";
+pub static PHP_INDENT: &str = "    ";
+
+// ELIXIR_TYPES
+pub static ELIXIR_ANY: &str = "any()";
+pub static ELIXIR_STRING: &str = "String.t()";
+pub static ELIXIR_INT: &str = "integer()";
+pub static ELIXIR_FLOAT: &str = "float()";
+pub static ELIXIR_BOOL: &str = "boolean()";
+pub static ELIXIR_AUTO_GENERATED: &str = "Root";
+pub static ELIXIR_INDENT: &str = "  ";
+
+// NIM_TYPES
+pub static NIM_ANY: &str = "JsonNode";
+pub static NIM_STRING: &str = "string";
+pub static NIM_INT: &str = "int";
+pub static NIM_FLOAT: &str = "float";
+pub static NIM_BOOL: &str = "bool";
+pub static NIM_AUTO_GENERATED: &str = "Root";
+pub static NIM_INDENT: &str = "  ";
+
+// CRYSTAL_TYPES
+pub static CRYSTAL_ANY: &str = "JSON::Any";
+pub static CRYSTAL_STRING: &str = "String";
+pub static CRYSTAL_INT: &str = "Int64";
+pub static CRYSTAL_FLOAT: &str = "Float64";
+pub static CRYSTAL_BOOL: &str = "Bool";
+pub static CRYSTAL_AUTO_GENERATED: &str = "Root";
+pub static CRYSTAL_INDENT: &str = "  ";
+
+// RUBY_TYPES
+// Struct.new members are typed in comments, with YARD's names for the types
+pub static RUBY_ANY: &str = "Object";
+pub static RUBY_STRING: &str = "String";
+pub static RUBY_INT: &str = "Integer";
+pub static RUBY_FLOAT: &str = "Float";
+pub static RUBY_BOOL: &str = "Boolean";
+pub static DRY_ANY: &str = "Types::Any";
+pub static DRY_STRING: &str = "Types::String";
+pub static DRY_INT: &str = "Types::Integer";
+pub static DRY_FLOAT: &str = "Types::Float";
+pub static DRY_BOOL: &str = "Types::Bool";
+pub static RUBY_AUTO_GENERATED: &str = "Root";
+pub static RUBY_INDENT: &str = "  ";
+
+// JULIA_TYPES
+pub static JULIA_ANY: &str = "Any";
+pub static JULIA_STRING: &str = "String";
+pub static JULIA_INT: &str = "Int64";
+pub static JULIA_FLOAT: &str = "Float64";
+pub static JULIA_BOOL: &str = "Bool";
+pub static JULIA_AUTO_GENERATED: &str = "Root";
+pub static JULIA_INDENT: &str = "    ";
+
+// GROOVY_TYPES
+pub static GROOVY_ANY: &str = "Object";
+pub static GROOVY_STRING: &str = "String";
+pub static GROOVY_INT: &str = "Long";
+pub static GROOVY_FLOAT: &str = "Double";
+pub static GROOVY_BOOL: &str = "Boolean";
+pub static GROOVY_AUTO_GENERATED: &str = "Root";
+pub static GROOVY_INDENT: &str = "    ";
+
+// OBJC_TYPES
+pub static OBJC_ANY: &str = "id";
+pub static OBJC_STRING: &str = "NSString *";
+pub static OBJC_NUMBER: &str = "NSNumber *";
+pub static OBJC_AUTO_GENERATED: &str = "Root";
+pub static OBJC_INDENT: &str = "    ";
+
+// THRIFT_TYPES
+// Thrift has no untyped values, they're JSON text, under a typedef saying so
+pub static THRIFT_ANY: &str = "Json";
+pub static THRIFT_STRING: &str = "string";
+pub static THRIFT_INT: &str = "i64";
+pub static THRIFT_FLOAT: &str = "double";
+pub static THRIFT_BOOL: &str = "bool";
+pub static THRIFT_AUTO_GENERATED: &str = "Root";
+pub static THRIFT_INDENT: &str = "  ";
+
+// FLATBUFFERS_TYPES
+// Untyped values are schemaless FlexBuffers nested in a byte vector
+pub static FLATBUFFERS_ANY: &str = "[ubyte] (flexbuffer)";
+pub static FLATBUFFERS_STRING: &str = "string";
+pub static FLATBUFFERS_INT: &str = "long";
+pub static FLATBUFFERS_FLOAT: &str = "double";
+pub static FLATBUFFERS_BOOL: &str = "bool";
+pub static FLATBUFFERS_AUTO_GENERATED: &str = "Root";
+pub static FLATBUFFERS_INDENT: &str = "  ";
+
+// CAPNP_TYPES
+pub static CAPNP_ANY: &str = "AnyPointer";
+pub static CAPNP_STRING: &str = "Text";
+pub static CAPNP_INT: &str = "Int64";
+pub static CAPNP_FLOAT: &str = "Float64";
+pub static CAPNP_BOOL: &str = "Bool";
+pub static CAPNP_AUTO_GENERATED: &str = "Root";
+pub static CAPNP_INDENT: &str = "  ";
+
+// ZOD_TYPES
+pub static ZOD_ANY: &str = "z.unknown()";
+pub static ZOD_STRING: &str = "z.string()";
+pub static ZOD_INT: &str = "z.number().int()";
+pub static ZOD_FLOAT: &str = "z.number()";
+pub static ZOD_BOOL: &str = "z.boolean()";
+pub static ZOD_AUTO_GENERATED: &str = "Root";
+pub static ZOD_INDENT: &str = "  ";
+
+// IO_TS_TYPES
+pub static IO_TS_ANY: &str = "t.unknown";
+pub static IO_TS_STRING: &str = "t.string";
+pub static IO_TS_NUMBER: &str = "t.number";
+pub static IO_TS_BOOL: &str = "t.boolean";
+pub static IO_TS_AUTO_GENERATED: &str = "Root";
+pub static IO_TS_INDENT: &str = "  ";
+
+// OPENAPI_TYPES
+pub static OPENAPI_ANY: &str = "{}";
+pub static OPENAPI_STRING: &str = "string";
+pub static OPENAPI_INT: &str = "integer";
+pub static OPENAPI_FLOAT: &str = "number";
+pub static OPENAPI_BOOL: &str = "boolean";
+pub static OPENAPI_AUTO_GENERATED: &str = "Root";
+pub static OPENAPI_INDENT: &str = "  ";
+
+// XSD_TYPES
+pub static XSD_ANY: &str = "xs:anyType";
+pub static XSD_STRING: &str = "xs:string";
+pub static XSD_INT: &str = "xs:long";
+pub static XSD_FLOAT: &str = "xs:double";
+pub static XSD_BOOL: &str = "xs:boolean";
+pub static XSD_AUTO_GENERATED: &str = "Root";
+pub static XSD_INDENT: &str = "  ";
+
+// PARQUET_TYPES
+pub static PARQUET_ANY: &str = "binary";
+pub static PARQUET_STRING: &str = "binary";
+pub static PARQUET_INT: &str = "int64";
+pub static PARQUET_FLOAT: &str = "double";
+pub static PARQUET_BOOL: &str = "boolean";
+pub static PARQUET_AUTO_GENERATED: &str = "Root";
+pub static PARQUET_INDENT: &str = "  ";
+
+// BIGQUERY_TYPES
+pub static BIGQUERY_ANY: &str = "JSON";
+pub static BIGQUERY_STRING: &str = "STRING";
+pub static BIGQUERY_INT: &str = "INTEGER";
+pub static BIGQUERY_FLOAT: &str = "FLOAT";
+pub static BIGQUERY_BOOL: &str = "BOOLEAN";
+pub static BIGQUERY_AUTO_GENERATED: &str = "Root";
+pub static BIGQUERY_INDENT: &str = "  ";
+
+// Name of unnamed root types when there's no language to name them, e.g. in --emit-ir
+pub static DEFAULT_TYPE_NAME: &str = "Root";
 
 pub static DEFAULT_LANG: &str = "rust";
+
+pub static VERSION: &str = env!("CARGO_PKG_VERSION");
+pub static GIT_HASH: Option<&str> = option_env!("JSONC_GIT_HASH");
+
+// A type name the default naming leaves as it is, rendered to find where names go in a header
+pub static TYPE_NAME_MARKER: &str = "Marker";
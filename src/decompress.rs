@@ -0,0 +1,124 @@
+use std::io::{BufRead, Read};
+
+use flate2::read::GzDecoder;
+
+use crate::error::JsoncError;
+use crate::input::read_limited;
+
+static GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+static ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Compression {
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    fn detect(bytes: &[u8], path: Option<&str>) -> Option<Compression> {
+        if bytes.starts_with(GZIP_MAGIC) {
+            Some(Compression::Gzip)
+        } else if bytes.starts_with(ZSTD_MAGIC) {
+            Some(Compression::Zstd)
+        } else if path.is_some_and(|path| path.ends_with(".gz")) {
+            // Still goes through the decoder, which reports the file as corrupt
+            Some(Compression::Gzip)
+        } else {
+            None
+        }
+    }
+}
+
+// `models.json.gz` is a JSON file as far as its format and name are concerned
+pub fn strip_compression_extension(path: &str) -> &str {
+    path.strip_suffix(".gz")
+        .or_else(|| path.strip_suffix(".zst"))
+        .unwrap_or(path)
+}
+
+fn decompress_error(path: Option<&str>, message: impl ToString) -> JsoncError {
+    JsoncError::Decompress {
+        path: path.map(String::from),
+        message: message.to_string(),
+    }
+}
+
+fn decode(
+    reader: impl Read,
+    max_size: Option<u64>,
+    path: Option<&str>,
+) -> Result<Vec<u8>, JsoncError> {
+    read_limited(reader, max_size, path, |e| decompress_error(path, e))
+}
+
+#[cfg(feature = "zstd")]
+fn zstd_reader<'a>(
+    reader: impl BufRead + 'a,
+    path: Option<&str>,
+) -> Result<Box<dyn Read + 'a>, JsoncError> {
+    let decoder =
+        zstd::stream::read::Decoder::with_buffer(reader).map_err(|e| decompress_error(path, e))?;
+    Ok(Box::new(decoder))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn zstd_reader<'a>(
+    _reader: impl BufRead + 'a,
+    path: Option<&str>,
+) -> Result<Box<dyn Read + 'a>, JsoncError> {
+    Err(zstd_unsupported(path))
+}
+
+#[cfg(feature = "zstd")]
+fn decode_zstd(
+    bytes: &[u8],
+    max_size: Option<u64>,
+    path: Option<&str>,
+) -> Result<Vec<u8>, JsoncError> {
+    let decoder = zstd::stream::read::Decoder::new(bytes).map_err(|e| decompress_error(path, e))?;
+    decode(decoder, max_size, path)
+}
+
+#[cfg(not(feature = "zstd"))]
+fn decode_zstd(
+    _bytes: &[u8],
+    _max_size: Option<u64>,
+    path: Option<&str>,
+) -> Result<Vec<u8>, JsoncError> {
+    Err(zstd_unsupported(path))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn zstd_unsupported(path: Option<&str>) -> JsoncError {
+    decompress_error(
+        path,
+        "zstd input is only supported when built with the `zstd` feature",
+    )
+}
+
+// Transparently decompresses gzip, and zstd with the `zstd` feature, input.
+// `max_size` applies to the decompressed size as well.
+pub fn decompress(
+    bytes: Vec<u8>,
+    max_size: Option<u64>,
+    path: Option<&str>,
+) -> Result<Vec<u8>, JsoncError> {
+    match Compression::detect(&bytes, path) {
+        None => Ok(bytes),
+        Some(Compression::Gzip) => decode(GzDecoder::new(bytes.as_slice()), max_size, path),
+        Some(Compression::Zstd) => decode_zstd(&bytes, max_size, path),
+    }
+}
+
+// Like `decompress`, for input that's parsed as it's read
+pub fn decompress_reader<'a>(
+    mut reader: impl BufRead + 'a,
+    path: Option<&str>,
+) -> Result<Box<dyn Read + 'a>, JsoncError> {
+    let start = reader.fill_buf().map_err(|e| JsoncError::io(path, e))?;
+    match Compression::detect(start, path) {
+        None => Ok(Box::new(reader)),
+        Some(Compression::Gzip) => Ok(Box::new(GzDecoder::new(reader))),
+        Some(Compression::Zstd) => zstd_reader(reader, path),
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt::Write;
+
+// Unchanged lines shown around every change
+static CONTEXT: usize = 3;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Edit {
+    Keep,
+    Delete,
+    Insert,
+}
+
+// The edits turning `old` into `new`, found through their longest common subsequence.
+// Lines both start or end with are left out of it, that's usually most of them.
+fn edits<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Edit, &'a str)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_changed = &old[prefix..old.len() - suffix];
+    let new_changed = &new[prefix..new.len() - suffix];
+
+    // lcs[i][j] is the length of the longest common subsequence of old_changed[i..] and new_changed[j..]
+    let (n, m) = (old_changed.len(), new_changed.len());
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_changed[i] == new_changed[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut result: Vec<(Edit, &str)> = old[..prefix]
+        .iter()
+        .map(|line| (Edit::Keep, *line))
+        .collect();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_changed[i] == new_changed[j] {
+            result.push((Edit::Keep, old_changed[i]));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            result.push((Edit::Delete, old_changed[i]));
+            i += 1;
+        } else {
+            result.push((Edit::Insert, new_changed[j]));
+            j += 1;
+        }
+    }
+    result.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| (Edit::Keep, *line)),
+    );
+    result
+}
+
+// Line numbers start at 1, an empty range is given as the line before it
+fn hunk_range(start: usize, len: usize) -> String {
+    match len {
+        0 => format!("{start},0"),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{len}", start + 1),
+    }
+}
+
+// Compares two texts line by line, in the unified format of `diff -u`
+pub fn unified_diff<'a>(old: &'a str, new: &'a str, old_name: &str, new_name: &str) -> String {
+    // An empty text has no lines rather than a single empty one, and a trailing
+    // newline ends the last line rather than starting another one
+    let lines = |text: &'a str| -> Vec<&'a str> {
+        if text.is_empty() {
+            vec![]
+        } else {
+            text.strip_suffix('\n')
+                .unwrap_or(text)
+                .split('\n')
+                .collect()
+        }
+    };
+    let old_lines = lines(old);
+    let new_lines = lines(new);
+    let edits = edits(&old_lines, &new_lines);
+    let changes: Vec<usize> = (0..edits.len())
+        .filter(|&index| edits[index].0 != Edit::Keep)
+        .collect();
+
+    let mut diff = format!("--- {old_name}\n+++ {new_name}\n");
+    let mut next = 0;
+    while next < changes.len() {
+        // Changes close enough for their context to overlap go in the same hunk
+        let start = changes[next].saturating_sub(CONTEXT);
+        let mut end = changes[next] + 1;
+        while next < changes.len() && changes[next] <= end + 2 * CONTEXT {
+            end = changes[next] + 1;
+            next += 1;
+        }
+        let end = (end + CONTEXT).min(edits.len());
+
+        let count = |edits: &[(Edit, &str)], skip: Edit| {
+            edits.iter().filter(|(edit, _)| *edit != skip).count()
+        };
+        let hunk = &edits[start..end];
+        let old_range = hunk_range(
+            count(&edits[..start], Edit::Insert),
+            count(hunk, Edit::Insert),
+        );
+        let new_range = hunk_range(
+            count(&edits[..start], Edit::Delete),
+            count(hunk, Edit::Delete),
+        );
+        let _ = writeln!(diff, "@@ -{old_range} +{new_range} @@");
+        for (edit, line) in hunk {
+            let marker = match edit {
+                Edit::Keep => ' ',
+                Edit::Delete => '-',
+                Edit::Insert => '+',
+            };
+            let _ = writeln!(diff, "{marker}{line}");
+        }
+    }
+    if changes.is_empty() && old != new {
+        diff.push_str("\\ Newline at end of file differs\n");
+    }
+    diff
+}
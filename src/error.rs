@@ -0,0 +1,152 @@
+use std::io;
+
+use thiserror::Error;
+
+// Where the JSON document was read from
+pub fn source_name(path: Option<&str>) -> String {
+    path.map(String::from)
+        .unwrap_or_else(|| String::from("<stdin>"))
+}
+
+// Parse errors without a known position have their line set to 0
+fn position(line: usize, column: usize) -> String {
+    if line == 0 {
+        String::new()
+    } else {
+        format!(" at line {line}, column {column}")
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum JsoncError {
+    // Reading the input failed, path is None for stdin
+    #[error("could not read {}: {source}", source_name(path.as_deref()))]
+    Io {
+        path: Option<String>,
+        #[source]
+        source: io::Error,
+    },
+    // The input is not a valid JSON document
+    #[error("invalid JSON in {}{}: {message}", source_name(path.as_deref()), position(*line, *column))]
+    Parse {
+        path: Option<String>,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    // The input is compressed, but could not be decompressed
+    #[error("could not decompress {}: {message}", source_name(path.as_deref()))]
+    Decompress {
+        path: Option<String>,
+        message: String,
+    },
+    // The input is not text in a supported encoding
+    #[error("could not decode {}: {message}", source_name(path.as_deref()))]
+    Encoding {
+        path: Option<String>,
+        message: String,
+    },
+    // The input has nothing but whitespace or comments in it
+    #[error("no JSON input provided in {}", source_name(path.as_deref()))]
+    EmptyInput { path: Option<String> },
+    // The input is larger than the size limit
+    #[error("{} is larger than the limit of {max_size} bytes", source_name(path.as_deref()))]
+    TooLarge { path: Option<String>, max_size: u64 },
+    // The input is nested deeper than the depth limit
+    #[error("{} is nested deeper than the limit of {max_depth} levels", source_name(path.as_deref()))]
+    DepthExceeded {
+        path: Option<String>,
+        max_depth: usize,
+    },
+    // The value to generate types from could not be found in the document
+    #[error("could not select {pointer}: {message}")]
+    Select { pointer: String, message: String },
+    // There's no formatter for the requested language
+    #[error("unknown language '{name}', supported languages are: {}", available.join(", "))]
+    UnknownLanguage {
+        name: String,
+        available: Vec<String>,
+    },
+    // Types can only be generated from objects and arrays
+    #[error("cannot generate types for a top-level {kind}")]
+    UnsupportedRoot { kind: &'static str },
+    // A template could not be compiled or rendered, path is the template file
+    #[error("invalid template {path}{}: {message}", position(*line, *column))]
+    Template {
+        path: String,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    // Options given as JSON could not be understood
+    #[error("invalid options: {message}")]
+    Options { message: String },
+    // Rendering the generated code into a sink failed
+    #[error("could not write the generated code: {source}")]
+    Write {
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl JsoncError {
+    pub fn io(path: Option<&str>, source: io::Error) -> Self {
+        JsoncError::Io {
+            path: path.map(String::from),
+            source,
+        }
+    }
+
+    pub fn json(path: Option<&str>, err: serde_json::Error) -> Self {
+        if err.is_io() {
+            return JsoncError::io(path, err.into());
+        }
+        let (line, column) = (err.line(), err.column());
+        // serde_json appends the position to its messages, it's reported separately here
+        let message = err.to_string();
+        let message = message
+            .strip_suffix(format!(" at line {line} column {column}").as_str())
+            .unwrap_or(message.as_str())
+            .to_string();
+        JsoncError::Parse {
+            path: path.map(String::from),
+            line,
+            column,
+            message,
+        }
+    }
+
+    pub fn json5(path: Option<&str>, err: json5::Error) -> Self {
+        match err {
+            json5::Error::Message { msg, location } => {
+                let (line, column) = location
+                    .map(|location| (location.line, location.column))
+                    .unwrap_or((0, 0));
+                JsoncError::Parse {
+                    path: path.map(String::from),
+                    line,
+                    column,
+                    message: msg,
+                }
+            }
+        }
+    }
+
+    // Moves a parse error `lines` lines down, for documents parsed out of a larger input
+    pub fn offset_lines(self, lines: usize) -> Self {
+        match self {
+            JsoncError::Parse {
+                path,
+                line,
+                column,
+                message,
+            } if line > 0 => JsoncError::Parse {
+                path,
+                line: line + lines,
+                column,
+                message,
+            },
+            other => other,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use serde_json::Value;
+
+use crate::language::LanguageFormatter;
+
+// Longest example that is shown as is, longer ones are truncated
+static MAX_EXAMPLE_CHARS: usize = 40;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ExampleMode {
+    Values,
+    // Strings may hold secrets, only their length is shown
+    RedactStrings,
+}
+
+impl ExampleMode {
+    pub fn from_name(name: &str) -> Option<ExampleMode> {
+        match name.to_lowercase().as_str() {
+            "values" => Some(ExampleMode::Values),
+            "redact-strings" => Some(ExampleMode::RedactStrings),
+            _ => None,
+        }
+    }
+}
+
+fn truncate(text: String) -> String {
+    if text.chars().count() <= MAX_EXAMPLE_CHARS {
+        text
+    } else {
+        text.chars().take(MAX_EXAMPLE_CHARS).collect::<String>() + "..."
+    }
+}
+
+// A short, single line rendition of `value`, nested objects have their own type and
+// don't get an example
+pub fn example(value: &Value, mode: ExampleMode) -> Option<String> {
+    match value {
+        Value::Object(_) => None,
+        Value::String(s) if mode == ExampleMode::RedactStrings => {
+            Some(format!("string, {} chars", s.chars().count()))
+        }
+        Value::Array(_) if mode == ExampleMode::RedactStrings => None,
+        // serde_json escapes quotes and line breaks, so the example stays on one line
+        other => serde_json::to_string(other).ok().map(truncate),
+    }
+}
+
+// Adds a comment at the end of a rendered field
+pub fn with_comment(field: String, comment: Option<&str>, lang: &dyn LanguageFormatter) -> String {
+    let Some(comment) = comment else {
+        return field;
+    };
+    let comment = format!(" {}", lang.comment(comment));
+    match field.strip_suffix('\n') {
+        Some(line) => format!("{line}{comment}\n"),
+        None => field + &comment,
+    }
+}
@@ -0,0 +1,83 @@
+// C bindings, declared for C and C++ in examples/ffi/jsonc.h along with the rules for
+// the pointers passed in, which is where their safety requirements are documented
+#![allow(clippy::missing_safety_doc)]
+
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use crate::generate::generate_code;
+use crate::options::options_from_json;
+
+fn to_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, String> {
+    if ptr.is_null() {
+        return Err(format!("{name} is null"));
+    }
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|_| format!("{name} is not valid UTF-8"))
+}
+
+// Allocated by Rust, so it has to be released with jsonc_free.
+// Interior nul bytes can't be represented and are dropped.
+fn into_c_string(text: String) -> *mut c_char {
+    let text = text.replace('\0', "");
+    CString::new(text).unwrap_or_default().into_raw()
+}
+
+// Generates code from a JSON text, `options_json` may be null for the default options.
+// Returns 0 and sets `*out` to the code on success, otherwise returns 1 and sets `*err` to
+// the error message. Both are null unless set, and have to be released with jsonc_free.
+#[no_mangle]
+pub unsafe extern "C" fn jsonc_generate(
+    json: *const c_char,
+    language: *const c_char,
+    options_json: *const c_char,
+    out: *mut *mut c_char,
+    err: *mut *mut c_char,
+) -> c_int {
+    if !out.is_null() {
+        *out = ptr::null_mut();
+    }
+    if !err.is_null() {
+        *err = ptr::null_mut();
+    }
+
+    // A panic must not unwind into C, it's reported like any other error
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let json = to_str(json, "json")?;
+        let language = to_str(language, "language")?;
+        let options_json = if options_json.is_null() {
+            ""
+        } else {
+            to_str(options_json, "options_json")?
+        };
+        options_from_json(options_json)
+            .and_then(|opts| generate_code(json, language, &opts))
+            .map_err(|e| e.to_string())
+    }))
+    .unwrap_or_else(|_| Err(String::from("jsonc panicked while generating code")));
+
+    match result {
+        Ok(code) => {
+            if !out.is_null() {
+                *out = into_c_string(code);
+            }
+            0
+        }
+        Err(message) => {
+            if !err.is_null() {
+                *err = into_c_string(message);
+            }
+            1
+        }
+    }
+}
+
+// Releases a string returned by jsonc_generate, null is ignored
+#[no_mangle]
+pub unsafe extern "C" fn jsonc_free(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
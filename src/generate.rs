@@ -0,0 +1,403 @@
+use std::collections::HashSet;
+use std::io::Write;
+
+use serde_json::Value;
+
+use crate::error::JsoncError;
+use crate::examples::with_comment;
+use crate::input::{parse_input, InputFormat, Parsed};
+use crate::language::{get_language_formatter, FieldContext, LanguageFormatter};
+use crate::options::GeneratorOptions;
+use crate::sample::sample_literal;
+use crate::schema::{
+    infer_schema_named, schema_from_shape_named, Field, LogicalType, Schema, TypeDef, TypeRef,
+};
+
+pub type StructValue = String;
+type ArrayType = String;
+
+// One generated type, named the way the language names it
+#[derive(Clone, Debug)]
+pub struct TypeOutput {
+    pub name: String,
+    pub text: StructValue,
+}
+
+// The types generated from a document. There's no root type when the document is an
+// array without objects in it.
+#[derive(Clone, Debug, Default)]
+pub struct Generated {
+    pub root: Option<TypeOutput>,
+    // The types the root depends on, each one comes after the types it depends on
+    pub dependencies: Vec<TypeOutput>,
+    // Another name for the type of the document, when it's an array
+    pub alias: Option<TypeOutput>,
+}
+
+impl Generated {
+    // Every type, in the order that needs no forward references: dependencies first
+    pub fn dependencies_first(&self) -> impl Iterator<Item = &TypeOutput> {
+        self.dependencies
+            .iter()
+            .chain(&self.root)
+            .chain(&self.alias)
+    }
+
+    // Every type, starting with the root, then the types it depends on in reverse, so
+    // every type comes before the ones it depends on
+    pub fn root_first(&self) -> impl Iterator<Item = &TypeOutput> {
+        self.alias
+            .iter()
+            .chain(&self.root)
+            .chain(self.dependencies.iter().rev())
+    }
+
+    pub fn len(&self) -> usize {
+        self.dependencies.len()
+            + usize::from(self.root.is_some())
+            + usize::from(self.alias.is_some())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// Formatters name primitive types after a sample value of the type
+fn sample(logical: LogicalType) -> Value {
+    match logical {
+        LogicalType::String | LogicalType::Date | LogicalType::Uuid => Value::from(""),
+        LogicalType::Int | LogicalType::TimestampMillis => Value::from(0),
+        LogicalType::Float | LogicalType::Decimal => Value::from(0.5),
+        LogicalType::Bool => Value::Bool(false),
+        LogicalType::Any => Value::Null,
+    }
+}
+
+pub(crate) fn type_name(type_ref: &TypeRef, lang: &dyn LanguageFormatter) -> ArrayType {
+    match type_ref {
+        TypeRef::Primitive(logical) => lang.premitive_type_name(&sample(*logical)).to_owned(),
+        TypeRef::Object(key) => lang.struct_or_class_name(key),
+        TypeRef::Array { element, optional } => {
+            lang.format_arr_type(type_name(element, lang), *optional)
+        }
+    }
+}
+
+fn field_context<'a>(
+    field: &'a Field,
+    index: usize,
+    name_key: &'a str,
+    type_name: &'a str,
+    path: &'a str,
+) -> FieldContext<'a> {
+    FieldContext {
+        json_key: &field.key,
+        index,
+        name_key,
+        type_name,
+        type_ref: &field.type_ref,
+        optional: field.optional,
+        absent: field.absent,
+        nullable: field.nullable,
+        path,
+        example: field.example.as_deref(),
+        string_encoded: field.string_encoded,
+        inferred: field,
+    }
+}
+
+fn render_field(field: &Field, context: &FieldContext, lang: &dyn LanguageFormatter) -> String {
+    let note = if field.string_encoded {
+        lang.string_encoded_note(context)
+    } else {
+        None
+    };
+    let line = lang.format_field(context);
+    with_comment(line, field_comment(field, note).as_deref(), lang)
+}
+
+// A TODO if the field's type couldn't be inferred, where its value is if it was wrapped, a
+// note on how it's sent, then its example
+fn field_comment(field: &Field, note: Option<String>) -> Option<String> {
+    let todo = field
+        .todo
+        .map(|reason| format!("TODO: type could not be inferred ({reason})"));
+    // Types only a type mapping gives are typed as what they're sent as
+    let note = match field.type_ref.innermost() {
+        TypeRef::Primitive(LogicalType::Decimal) => Some(String::from("decimal")),
+        TypeRef::Primitive(LogicalType::TimestampMillis) => {
+            Some(String::from("milliseconds since the epoch"))
+        }
+        _ => None,
+    }
+    .into_iter()
+    .chain(note);
+    let flattened =
+        (!field.flattened.is_empty()).then(|| format!("flattened from {}", &field.pointer()[1..]));
+    let example = field
+        .example
+        .as_ref()
+        .map(|example| format!("e.g. {example}"));
+    let parts: Vec<String> = todo
+        .into_iter()
+        .chain(flattened)
+        .chain(note)
+        .chain(example)
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("; "))
+}
+
+// What each field is named after, its name from the rename map or its key. Keys the
+// language can't name a field after, e.g. "" or " ", get placeholders field_1, field_2 and
+// so on in the order of the fields, skipping any that is the key of another field.
+pub(crate) fn name_keys(type_def: &TypeDef, lang: &dyn LanguageFormatter) -> Vec<String> {
+    let is_key = |name: &str| type_def.fields.iter().any(|field| field.key == name);
+    let mut next = 0;
+    type_def
+        .fields
+        .iter()
+        .map(|field| {
+            if let Some(name) = &field.name {
+                return name.clone();
+            }
+            if lang.nameable_key(&field.key) {
+                return field.key.clone();
+            }
+            loop {
+                next += 1;
+                let placeholder = format!("field_{next}");
+                if !is_key(&placeholder) {
+                    return placeholder;
+                }
+            }
+        })
+        .collect()
+}
+
+// Like `name_keys`, but fields the language would name like one before them, e.g. userId
+// and user_id in camelCase, are numbered: user_id_2
+pub(crate) fn unique_name_keys(type_def: &TypeDef, lang: &dyn LanguageFormatter) -> Vec<String> {
+    let mut names = HashSet::new();
+    name_keys(type_def, lang)
+        .into_iter()
+        .map(|name_key| {
+            let mut unique = name_key.clone();
+            let mut number = 1;
+            while !names.insert(lang.field_name(&unique)) {
+                number += 1;
+                unique = format!("{name_key}_{number}");
+            }
+            unique
+        })
+        .collect()
+}
+
+// Renders one of the schema's types, header, fields and footer
+pub fn render_type(type_def: &TypeDef, lang: &dyn LanguageFormatter) -> StructValue {
+    let mut struct_content = lang.struct_or_class_header(type_def.key.clone());
+    let name_keys = unique_name_keys(type_def, lang);
+    let type_names: Vec<String> = type_def
+        .fields
+        .iter()
+        .map(|field| type_name(&field.type_ref, lang))
+        .collect();
+    let paths: Vec<String> = type_def
+        .fields
+        .iter()
+        .map(|field| format!("{}{}", type_def.path, field.pointer()))
+        .collect();
+    let contexts: Vec<FieldContext> = (0..type_def.fields.len())
+        .map(|index| {
+            let field = &type_def.fields[index];
+            field_context(
+                field,
+                index,
+                &name_keys[index],
+                &type_names[index],
+                &paths[index],
+            )
+        })
+        .collect();
+    for (field, context) in type_def.fields.iter().zip(&contexts) {
+        struct_content.push_str(&render_field(field, context, lang));
+    }
+    struct_content.push_str(&lang.type_members(type_def.key.clone(), &contexts));
+    struct_content.push_str(&lang.struct_or_class_footer(Some(type_def.key.clone())));
+    lang.format_type(struct_content)
+}
+
+// The alias for a root array, named the way the language names types
+fn render_alias(schema: &Schema, lang: &dyn LanguageFormatter) -> Option<TypeOutput> {
+    let name = lang.struct_or_class_name(schema.alias.as_deref()?);
+    let text = lang.type_alias(&name, &type_name(&schema.root, lang));
+    Some(TypeOutput { name, text })
+}
+
+// Renders every type of an inferred schema in `lang`
+pub fn generate_from_schema(schema: &Schema, lang: &dyn LanguageFormatter) -> Generated {
+    // A single document is one output, named after the root
+    if let Some(text) = lang.render_schema(schema) {
+        let key = schema
+            .root_type()
+            .map(|root| root.key.as_str())
+            .or(schema.alias.as_deref())
+            .unwrap_or(lang.default_type_name());
+        let name = lang.struct_or_class_name(key);
+        return Generated {
+            root: Some(TypeOutput { name, text }),
+            ..Generated::default()
+        };
+    }
+    let output = |type_def: &TypeDef| TypeOutput {
+        name: lang.struct_or_class_name(&type_def.key),
+        text: render_type(type_def, lang),
+    };
+    let (dependencies, root) = match schema.root_type() {
+        Some(_) => schema.types.split_at(schema.types.len() - 1),
+        None => (schema.types.as_slice(), &[][..]),
+    };
+    Generated {
+        root: root.first().map(output),
+        dependencies: dependencies.iter().map(output).collect(),
+        alias: render_alias(schema, lang),
+    }
+}
+
+// Generates a type for the document, and one for every object nested in it. An unnamed
+// root is named the way the language names it by default.
+pub fn generate_types(
+    value: &Value,
+    lang: &dyn LanguageFormatter,
+    opts: &GeneratorOptions,
+) -> Result<Generated, JsoncError> {
+    let schema = infer_schema_named(value, opts, lang.default_type_name())?;
+    Ok(generate_from_schema(&schema, lang))
+}
+
+// Puts `indent` at the start of every line of `text` that isn't blank
+fn indent_lines(text: &str, indent: &str) -> String {
+    if indent.is_empty() {
+        return text.to_string();
+    }
+    text.split('\n')
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{indent}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Like `generate_types`, but writes every type into `sink` as soon as it's rendered rather
+// than holding on to all of them. Types are separated by `opts.separator` and the last
+// one ends with a line break. Returns the number of types written.
+pub fn render<W: Write + ?Sized>(
+    value: &Value,
+    lang: &dyn LanguageFormatter,
+    opts: &GeneratorOptions,
+    sink: &mut W,
+) -> Result<usize, JsoncError> {
+    let schema = infer_schema_named(value, opts, lang.default_type_name())?;
+    write_types(&schema, value, lang, opts, sink)
+}
+
+// Like `render`, with the types inferred from `value` already, e.g. from several samples
+// of it. `--emit-sample` writes `value` as a value of the types.
+pub fn write_types<W: Write + ?Sized>(
+    schema: &Schema,
+    value: &Value,
+    lang: &dyn LanguageFormatter,
+    opts: &GeneratorOptions,
+    sink: &mut W,
+) -> Result<usize, JsoncError> {
+    let type_count = schema.types.len() + usize::from(schema.alias.is_some());
+    if let Some(document) = lang.render_schema(schema) {
+        return writeln!(sink, "{document}")
+            .map(|_| type_count)
+            .map_err(|source| JsoncError::Write { source });
+    }
+    let namespace = opts
+        .namespace
+        .as_deref()
+        .filter(|_| type_count > 1 || (type_count == 1 && opts.wrap_single))
+        .map(|name| lang.namespace(name));
+    // Dependencies first, the schema already has them in that order, and the alias last
+    let texts = schema
+        .types
+        .iter()
+        .map(|type_def| render_type(type_def, lang))
+        .chain(render_alias(schema, lang).map(|alias| alias.text));
+    let sample = opts
+        .emit_sample
+        .then(|| lang.sample_declaration(&sample_literal(value, schema, lang)))
+        .flatten();
+    let preamble = lang.preamble(schema);
+    let imports = lang.imports(schema);
+    let epilogue = lang.epilogue(schema);
+    let write = |sink: &mut W| -> std::io::Result<usize> {
+        if let Some(preamble) = &preamble {
+            sink.write_all(preamble.as_bytes())?;
+        }
+        if let Some(namespace) = &namespace {
+            sink.write_all(namespace.open.as_bytes())?;
+        }
+        if let Some(imports) = &imports {
+            let indent = namespace.as_ref().map_or("", |namespace| &namespace.indent);
+            sink.write_all(indent_lines(imports, indent).as_bytes())?;
+        }
+        let mut count = 0;
+        for text in texts {
+            if count > 0 {
+                sink.write_all(opts.separator.as_bytes())?;
+            }
+            match &namespace {
+                Some(namespace) => {
+                    sink.write_all(indent_lines(&text, &namespace.indent).as_bytes())?
+                }
+                None => sink.write_all(text.as_bytes())?,
+            }
+            count += 1;
+        }
+        if count > 0 {
+            sink.write_all(b"\n")?;
+        }
+        if let Some(epilogue) = epilogue.filter(|_| count > 0) {
+            let indent = namespace.as_ref().map_or("", |namespace| &namespace.indent);
+            sink.write_all(indent_lines(&epilogue, indent).as_bytes())?;
+        }
+        match &namespace {
+            Some(namespace) if !namespace.close.is_empty() => {
+                writeln!(sink, "{}", namespace.close)?;
+            }
+            _ => {}
+        }
+        if let Some(sample) = sample.filter(|_| count > 0) {
+            writeln!(sink, "\n{sample}")?;
+        }
+        Ok(count)
+    };
+    write(sink).map_err(|source| JsoncError::Write { source })
+}
+
+// The whole pipeline in one call, for embedding: parses JSONC `text` and returns the code
+// generated from it in `language`
+pub fn generate_code(
+    text: &str,
+    language: &str,
+    opts: &GeneratorOptions,
+) -> Result<String, JsoncError> {
+    let lang = get_language_formatter(language, opts)?;
+    let mut code = vec![];
+    match parse_input(text, InputFormat::Jsonc, false, None)? {
+        Parsed::Document(value) => render(&value, lang.as_ref(), opts, &mut code)?,
+        Parsed::Samples(shape) => {
+            let schema = schema_from_shape_named(&shape, None, opts, lang.default_type_name())?;
+            write_types(&schema, &shape.to_value(), lang.as_ref(), opts, &mut code)?
+        }
+    };
+    Ok(String::from_utf8_lossy(&code).into_owned())
+}
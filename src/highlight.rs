@@ -0,0 +1,96 @@
+use std::env;
+use std::io::{self, IsTerminal};
+
+use jsonc::language::{LanguageFormatter, TokenKind};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ColorChoice {
+    // Colors only when writing to a terminal
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn from_name(name: &str) -> Option<ColorChoice> {
+        match name.to_lowercase().as_str() {
+            "auto" => Some(ColorChoice::Auto),
+            "always" => Some(ColorChoice::Always),
+            "never" => Some(ColorChoice::Never),
+            _ => None,
+        }
+    }
+
+    // Whether output written to stdout should be colored
+    pub fn use_color(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            // https://no-color.org
+            ColorChoice::Auto => io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none(),
+        }
+    }
+}
+
+fn ansi_color(kind: TokenKind) -> Option<&'static str> {
+    match kind {
+        TokenKind::Keyword => Some("\x1b[35m"),
+        TokenKind::TypeName => Some("\x1b[36m"),
+        TokenKind::StringLiteral => Some("\x1b[32m"),
+        TokenKind::Comment => Some("\x1b[90m"),
+        TokenKind::Plain => None,
+    }
+}
+
+fn paint(out: &mut String, token: &str, kind: TokenKind) {
+    match ansi_color(kind) {
+        Some(color) => {
+            out.push_str(color);
+            out.push_str(token);
+            out.push_str("\x1b[0m");
+        }
+        None => out.push_str(token),
+    }
+}
+
+// Adds ANSI colors to generated code. The text is split into comments, string
+// literals, words and punctuation, and the formatter decides what each word is.
+pub fn highlight(text: &str, lang: &dyn LanguageFormatter) -> String {
+    let mut out = String::with_capacity(text.len() * 2);
+    let comment_prefix = lang.comment_prefix();
+    let mut rest = text;
+    let mut first_in_line = true;
+
+    while let Some(ch) = rest.chars().next() {
+        let token_len = if rest.starts_with(comment_prefix) {
+            let len = rest.find('\n').unwrap_or(rest.len());
+            paint(&mut out, &rest[..len], TokenKind::Comment);
+            len
+        } else if ch == '"' || ch == '`' {
+            let mut escaped = false;
+            let closing = rest[1..].char_indices().find(|&(_, c)| {
+                let found = c == ch && !escaped;
+                escaped = c == '\\' && !escaped;
+                found
+            });
+            let len = closing.map(|(i, _)| i + 2).unwrap_or(rest.len());
+            paint(&mut out, &rest[..len], TokenKind::StringLiteral);
+            len
+        } else if ch.is_alphanumeric() || ch == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..len];
+            paint(&mut out, word, lang.classify_token(word, first_in_line));
+            first_in_line = false;
+            len
+        } else {
+            first_in_line |= ch == '\n';
+            out.push(ch);
+            ch.len_utf8()
+        };
+        rest = &rest[token_len..];
+    }
+    out
+}
@@ -0,0 +1,125 @@
+use serde_json::Value;
+
+use crate::shape::Shape;
+
+// Fields left out of the document before types are inferred from it: every field under
+// one of `keys`, wherever it is, and the ones at `paths`. Paths are JSON pointers where *
+// stands for any key or array element, e.g. /data/items/*/internal.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IgnoreRules {
+    keys: Vec<String>,
+    paths: Vec<Vec<String>>,
+}
+
+impl IgnoreRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert_key(&mut self, key: impl Into<String>) {
+        self.keys.push(key.into());
+    }
+
+    pub fn insert_path(&mut self, pointer: &str) {
+        let segments = pointer
+            .split('/')
+            .skip(1)
+            .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+            .collect();
+        self.paths.push(segments);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty() && self.paths.is_empty()
+    }
+
+    fn matches(&self, key: &str, path: &[String]) -> bool {
+        self.keys.iter().any(|ignored| ignored == key)
+            || self.paths.iter().any(|pattern| {
+                pattern.len() == path.len()
+                    && pattern
+                        .iter()
+                        .zip(path)
+                        .all(|(expected, segment)| expected == "*" || expected == segment)
+            })
+    }
+
+    // Removes the matching fields from `value`, returning how many were removed. Objects
+    // left without fields are kept, as empty objects.
+    pub fn apply(&self, value: &mut Value) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let mut removed = 0;
+        let mut pending = vec![(value, vec![])];
+        while let Some((value, path)) = pending.pop() {
+            match value {
+                Value::Object(obj) => {
+                    let before = obj.len();
+                    obj.retain(|key, _| {
+                        let mut field_path = path.clone();
+                        field_path.push(key.clone());
+                        !self.matches(key, &field_path)
+                    });
+                    removed += before - obj.len();
+                    for (key, field) in obj.iter_mut() {
+                        let mut field_path = path.clone();
+                        field_path.push(key.clone());
+                        pending.push((field, field_path));
+                    }
+                }
+                Value::Array(items) => {
+                    for (index, item) in items.iter_mut().enumerate() {
+                        let mut item_path = path.clone();
+                        item_path.push(index.to_string());
+                        pending.push((item, item_path));
+                    }
+                }
+                _ => {}
+            }
+        }
+        removed
+    }
+
+    // Like `apply`, on the shape of a document. The elements of an array are folded into
+    // one, which only a `*` in a path matches, and a field is counted once however many
+    // objects it was in.
+    pub fn apply_shape(&self, shape: &mut Shape) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let mut removed = 0;
+        let mut pending = vec![(shape, vec![])];
+        while let Some((shape, path)) = pending.pop() {
+            match shape {
+                Shape::Object {
+                    fields,
+                    absent,
+                    nullable,
+                } => {
+                    let before = fields.len();
+                    fields.retain(|key, _| {
+                        let mut field_path = path.clone();
+                        field_path.push(key.clone());
+                        !self.matches(key, &field_path)
+                    });
+                    removed += before - fields.len();
+                    absent.retain(|key| fields.contains_key(key));
+                    nullable.retain(|key| fields.contains_key(key));
+                    for (key, field) in fields.iter_mut() {
+                        let mut field_path = path.clone();
+                        field_path.push(key.clone());
+                        pending.push((field, field_path));
+                    }
+                }
+                Shape::Array { element, .. } => {
+                    let mut element_path = path;
+                    element_path.push(String::from("*"));
+                    pending.push((element.as_mut(), element_path));
+                }
+                _ => {}
+            }
+        }
+        removed
+    }
+}
@@ -0,0 +1,328 @@
+use std::io::{self, Read};
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::decompress::{decompress, strip_compression_extension};
+use crate::error::JsoncError;
+use crate::ignore::IgnoreRules;
+use crate::preprocess::strip_jsonc;
+use crate::select::{select, select_shape};
+use crate::shape::Shape;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum InputFormat {
+    // Plain JSON, anything else is rejected
+    Json,
+    // JSON with comments and trailing commas, the default since config files like
+    // tsconfig.json use them, and plain JSON is accepted as well
+    #[default]
+    Jsonc,
+    Json5,
+}
+
+pub static INPUT_FORMATS: &[&str] = &["json", "jsonc", "json5"];
+
+impl InputFormat {
+    pub fn from_name(name: &str) -> Option<InputFormat> {
+        match name.to_lowercase().as_str() {
+            "json" => Some(InputFormat::Json),
+            "jsonc" => Some(InputFormat::Jsonc),
+            "json5" => Some(InputFormat::Json5),
+            _ => None,
+        }
+    }
+
+    // Guesses the format from the file extension, e.g. `settings.json5` or `dump.jsonc.gz`
+    pub fn from_path(path: &str) -> Option<InputFormat> {
+        let path = strip_compression_extension(path);
+        let extension = Path::new(path).extension()?.to_str()?;
+        match extension.to_lowercase().as_str() {
+            "jsonc" => Some(InputFormat::Jsonc),
+            "json5" => Some(InputFormat::Json5),
+            _ => None,
+        }
+    }
+}
+
+// Turns raw input bytes into text. A UTF-8 byte order mark is dropped (Windows tools
+// like to add one), UTF-16 input is recognized by its byte order mark and transcoded.
+pub fn decode_input(bytes: Vec<u8>, path: Option<&str>) -> Result<String, JsoncError> {
+    let encoding_error = |message: String| JsoncError::Encoding {
+        path: path.map(String::from),
+        message,
+    };
+
+    match bytes.as_slice() {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8(rest.to_vec())
+            .map_err(|e| encoding_error(format!("invalid UTF-8 at byte {}", e.utf8_error().valid_up_to() + 3))),
+        [0xFF, 0xFE, 0x00, 0x00, ..] | [0x00, 0x00, 0xFE, 0xFF, ..] => Err(encoding_error(
+            String::from("UTF-32 input is not supported, convert it to UTF-8 first"),
+        )),
+        [0xFF, 0xFE, rest @ ..] => decode_utf16(rest, u16::from_le_bytes)
+            .ok_or_else(|| encoding_error(String::from("invalid UTF-16LE input"))),
+        [0xFE, 0xFF, rest @ ..] => decode_utf16(rest, u16::from_be_bytes)
+            .ok_or_else(|| encoding_error(String::from("invalid UTF-16BE input"))),
+        _ => String::from_utf8(bytes).map_err(|e| {
+            encoding_error(format!(
+                "input is not valid UTF-8 (invalid byte at offset {}), only UTF-8 and UTF-16 with a byte order mark are supported",
+                e.utf8_error().valid_up_to()
+            ))
+        }),
+    }
+}
+
+fn decode_utf16(bytes: &[u8], to_unit: fn([u8; 2]) -> u16) -> Option<String> {
+    let pairs = bytes.chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return None;
+    }
+    let units: Vec<u16> = pairs.map(|pair| to_unit([pair[0], pair[1]])).collect();
+    String::from_utf16(&units).ok()
+}
+
+// Reads everything from `reader`, refusing to go past `max_size` bytes if it's given
+pub fn read_limited(
+    reader: impl Read,
+    max_size: Option<u64>,
+    path: Option<&str>,
+    read_error: impl Fn(io::Error) -> JsoncError,
+) -> Result<Vec<u8>, JsoncError> {
+    let mut bytes = vec![];
+    // One byte more than allowed is enough to tell the input is too large
+    let limit = max_size.map_or(u64::MAX, |max_size| max_size.saturating_add(1));
+    // Errors in the middle of a stream say how far it got, what was read until then is kept
+    reader.take(limit).read_to_end(&mut bytes).map_err(|e| {
+        read_error(match bytes.len() {
+            0 => e,
+            offset => io::Error::new(e.kind(), format!("{e} (at byte offset {offset})")),
+        })
+    })?;
+
+    match max_size {
+        Some(max_size) if bytes.len() as u64 > max_size => Err(JsoncError::TooLarge {
+            path: path.map(String::from),
+            max_size,
+        }),
+        _ => Ok(bytes),
+    }
+}
+
+// Reads the whole input, decompressing it if needed
+pub fn read_input(
+    reader: impl Read,
+    max_size: Option<u64>,
+    path: Option<&str>,
+) -> Result<Vec<u8>, JsoncError> {
+    let bytes = read_limited(reader, max_size, path, |e| JsoncError::io(path, e))?;
+    decompress(bytes, max_size, path)
+}
+
+// Rejects documents with arrays and objects nested more than `max_depth` levels deep.
+// It walks the document without recursion, so deep documents can't overflow the stack here.
+pub fn check_depth(value: &Value, max_depth: usize, path: Option<&str>) -> Result<(), JsoncError> {
+    let mut pending = vec![(value, 0)];
+    while let Some((value, depth)) = pending.pop() {
+        let children: Vec<&Value> = match value {
+            Value::Array(items) => items.iter().collect(),
+            Value::Object(obj) => obj.values().collect(),
+            _ => continue,
+        };
+        if depth + 1 > max_depth {
+            return Err(JsoncError::DepthExceeded {
+                path: path.map(String::from),
+                max_depth,
+            });
+        }
+        pending.extend(children.into_iter().map(|child| (child, depth + 1)));
+    }
+    Ok(())
+}
+
+// Replaces the arrays and objects nested more than `max_depth` levels deep with null, so
+// they're typed as Any, and returns the JSON pointers to them
+pub fn prune_depth(value: &mut Value, max_depth: usize) -> Vec<String> {
+    let mut pruned = vec![];
+    let mut pending = vec![(value, 0, String::new())];
+    while let Some((value, depth, pointer)) = pending.pop() {
+        if !(value.is_array() || value.is_object()) {
+            continue;
+        }
+        if depth + 1 > max_depth {
+            *value = Value::Null;
+            pruned.push(pointer);
+            continue;
+        }
+        let child = |key: &str| format!("{pointer}/{}", key.replace('~', "~0").replace('/', "~1"));
+        match value {
+            Value::Array(items) => pending.extend(
+                items
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(index, item)| (item, depth + 1, child(&index.to_string()))),
+            ),
+            Value::Object(obj) => pending.extend(
+                obj.iter_mut()
+                    .map(|(key, item)| (item, depth + 1, child(key))),
+            ),
+            _ => {}
+        }
+    }
+    pruned.sort();
+    pruned
+}
+
+// Like `check_depth`, for the shape of a document, whose folded arrays are as deep as the
+// deepest of their elements
+pub fn check_shape_depth(
+    shape: &Shape,
+    max_depth: usize,
+    path: Option<&str>,
+) -> Result<(), JsoncError> {
+    match shape.depth() {
+        depth if depth > max_depth => Err(JsoncError::DepthExceeded {
+            path: path.map(String::from),
+            max_depth,
+        }),
+        _ => Ok(()),
+    }
+}
+
+// Like `prune_depth`, for the shape of a document. Folded array elements are `*` in the
+// JSON pointers, as they stand for every element.
+pub fn prune_shape_depth(shape: &mut Shape, max_depth: usize) -> Vec<String> {
+    let mut pruned = vec![];
+    let mut pending = vec![(shape, 0, String::new())];
+    while let Some((shape, depth, pointer)) = pending.pop() {
+        if !matches!(shape, Shape::Object { .. } | Shape::Array { .. }) {
+            continue;
+        }
+        if depth + 1 > max_depth {
+            *shape = Shape::Null;
+            pruned.push(pointer);
+            continue;
+        }
+        match shape {
+            Shape::Array { element, .. } => {
+                pending.push((element.as_mut(), depth + 1, format!("{pointer}/*")))
+            }
+            Shape::Object { fields, .. } => {
+                pending.extend(fields.iter_mut().map(|(key, field)| {
+                    let key = key.replace('~', "~0").replace('/', "~1");
+                    (field, depth + 1, format!("{pointer}/{key}"))
+                }))
+            }
+            _ => {}
+        }
+    }
+    pruned.sort();
+    pruned
+}
+
+pub fn parse_document(
+    text: &str,
+    format: InputFormat,
+    path: Option<&str>,
+) -> Result<Value, JsoncError> {
+    match format {
+        InputFormat::Json => serde_json::from_str(text).map_err(|e| JsoncError::json(path, e)),
+        InputFormat::Jsonc => {
+            serde_json::from_str(strip_jsonc(text).as_str()).map_err(|e| JsoncError::json(path, e))
+        }
+        InputFormat::Json5 => json5::from_str(text).map_err(|e| JsoncError::json5(path, e)),
+    }
+}
+
+// Parses newline delimited JSON, one document per line, blank lines are skipped. Every
+// document is folded into the shape of all of them as soon as it's parsed, which is
+// returned along with the number of documents.
+pub fn parse_ndjson(
+    text: &str,
+    format: InputFormat,
+    path: Option<&str>,
+) -> Result<(Shape, usize), JsoncError> {
+    let mut shape = Shape::Null;
+    let mut count = 0;
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let document = parse_document(line, format, path).map_err(|e| e.offset_lines(index))?;
+        shape = shape.merge(Shape::of(&document));
+        count += 1;
+    }
+    Ok((shape, count))
+}
+
+// What's read from an input: a single document, or the shape of several samples of one
+// folded together, e.g. the documents of NDJSON input, so that keys missing from some of
+// them are told apart from the ones that are always there
+#[derive(Clone, Debug, PartialEq)]
+pub enum Parsed {
+    Document(Value),
+    Samples(Shape),
+}
+
+impl Parsed {
+    pub fn check_depth(&self, max_depth: usize, path: Option<&str>) -> Result<(), JsoncError> {
+        match self {
+            Parsed::Document(value) => check_depth(value, max_depth, path),
+            Parsed::Samples(shape) => check_shape_depth(shape, max_depth, path),
+        }
+    }
+
+    pub fn prune_depth(&mut self, max_depth: usize) -> Vec<String> {
+        match self {
+            Parsed::Document(value) => prune_depth(value, max_depth),
+            Parsed::Samples(shape) => prune_shape_depth(shape, max_depth),
+        }
+    }
+
+    pub fn ignore(&mut self, rules: &IgnoreRules) -> usize {
+        match self {
+            Parsed::Document(value) => rules.apply(value),
+            Parsed::Samples(shape) => rules.apply_shape(shape),
+        }
+    }
+
+    pub fn select(self, pointer: &str) -> Result<(Parsed, Option<String>), JsoncError> {
+        match self {
+            Parsed::Document(value) => {
+                select(value, pointer).map(|(value, name)| (Parsed::Document(value), name))
+            }
+            Parsed::Samples(shape) => {
+                select_shape(shape, pointer).map(|(shape, name)| (Parsed::Samples(shape), name))
+            }
+        }
+    }
+}
+
+// Reads the input either as a single document or as NDJSON. When it isn't a valid
+// single document but every line is, it's treated as NDJSON even without `ndjson`.
+// The documents of NDJSON input are samples of a single type.
+pub fn parse_input(
+    text: &str,
+    format: InputFormat,
+    ndjson: bool,
+    path: Option<&str>,
+) -> Result<Parsed, JsoncError> {
+    // Otherwise it'd be reported as a confusing "EOF while parsing a value"
+    if strip_jsonc(text).trim().is_empty() {
+        return Err(JsoncError::EmptyInput {
+            path: path.map(String::from),
+        });
+    }
+
+    if ndjson {
+        return parse_ndjson(text, format, path).map(|(shape, _)| Parsed::Samples(shape));
+    }
+
+    match parse_document(text, format, path) {
+        Ok(document) => Ok(Parsed::Document(document)),
+        Err(e) => parse_ndjson(text, format, path)
+            .ok()
+            .filter(|(_, count)| *count > 1)
+            .map(|(shape, _)| Parsed::Samples(shape))
+            .ok_or(e),
+    }
+}
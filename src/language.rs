@@ -1,58 +1,466 @@
 use crate::constants::*;
+use crate::error::JsoncError;
+use crate::generate::{render_type, type_name, unique_name_keys};
+use crate::naming::{first_char_lower, first_char_upper, Case};
+use crate::options::{GeneratorOptions, RubyStyle, DEFAULT_INDENT};
+use crate::registry::{registered_formatter, registered_languages};
+use crate::sample::{self, Layout, Literal};
+use crate::schema::{Field, LogicalType, Schema, TypeDef, TypeRef};
 use serde_json::Value;
-use std::rc::Rc;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 
+// What a word of generated code is, used to highlight the output
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TokenKind {
+    Keyword,
+    TypeName,
+    StringLiteral,
+    Comment,
+    Plain,
+}
+
+// Everything known about a field when its declaration is rendered
+pub struct FieldContext<'a> {
+    // The JSON key, as it was in the input
+    pub json_key: &'a str,
+    // Where the field is among the fields of its type, from 0
+    pub index: usize,
+    // What the field is named after: the JSON key, or a placeholder like field_1 if the key
+    // has nothing a name could be made of, e.g. "" or " "
+    pub name_key: &'a str,
+    // The field's type, as rendered by the formatter
+    pub type_name: &'a str,
+    pub type_ref: &'a TypeRef,
+    // The sample value was null
+    pub optional: bool,
+    // The key was missing from some of the samples
+    pub absent: bool,
+    // The value was null in some of the samples, but not in all of them
+    pub nullable: bool,
+    // Where the field is in the document, e.g. /users/[]/name
+    pub path: &'a str,
+    // A short rendition of the sample value, when examples are asked for
+    pub example: Option<&'a str>,
+    // Numbers in the type are sent as strings
+    pub string_encoded: bool,
+    // The field of the schema it's rendered from
+    pub inferred: &'a Field,
+}
+
+impl FieldContext<'_> {
+    // Whether the field's name isn't made from its JSON key, so serialization needs to be
+    // told the key
+    pub fn is_placeholder(&self) -> bool {
+        self.name_key != self.json_key
+    }
+
+    // How many arrays the innermost element type is nested in
+    pub fn array_depth(&self) -> usize {
+        self.type_ref.array_depth()
+    }
+
+    // Whether the innermost element type is one of the generated types rather than a primitive
+    pub fn is_generated_type(&self) -> bool {
+        matches!(self.type_ref.innermost(), TypeRef::Object(_))
+    }
+}
+
+// What every generated type is put inside of with a namespace, e.g. a module
+pub struct Namespace {
+    // Text before the first type, including its line breaks
+    pub open: String,
+    // Text after the last type, without a line break at the end. Empty for a package
+    // declaration, which has nothing to close.
+    pub close: String,
+    // Put at the start of every line of the types
+    pub indent: String,
+}
+
+// Renders the pieces of generated types in one language. A type is rendered as its
+// header, one formatted field per key of the JSON object, and its footer.
+// Custom implementations can be made available with `register_language`.
 pub trait LanguageFormatter {
+    // Opens the type named after the JSON key `raw`, including the line break after it
     fn struct_or_class_header(&self, raw: String) -> String;
     // It's usually a '}' or ')'
     fn struct_or_class_footer(&self, struct_name: Option<String>) -> String;
 
+    // What a file of generated types starts with, before the namespace, e.g. a syntax
+    // declaration. Nothing by default.
+    fn preamble(&self, _schema: &Schema) -> Option<String> {
+        None
+    }
+
+    // What comes before the first type, inside the namespace, e.g. the imports the types
+    // of `schema` need. Nothing by default.
+    fn imports(&self, _schema: &Schema) -> Option<String> {
+        None
+    }
+
+    // What comes after the last type, inside the namespace, e.g. a declaration of which
+    // type is the root. Nothing by default.
+    fn epilogue(&self, _schema: &Schema) -> Option<String> {
+        None
+    }
+
+    // The whole output for `schema`, for languages whose output is a single structured
+    // document, e.g. a JSON schema, rather than types rendered one after the other. None
+    // for the rest.
+    fn render_schema(&self, _schema: &Schema) -> Option<String> {
+        None
+    }
+
+    // What comes after the fields of the type named after `raw`, e.g. a constructor or
+    // (de)serialization in languages that can't derive them. Nothing by default.
+    fn type_members(&self, _raw: String, _fields: &[FieldContext]) -> String {
+        String::new()
+    }
+
+    // Comment on a field whose numbers are sent as strings, None if the field's declaration
+    // already takes care of it
+    fn string_encoded_note(&self, _field: &FieldContext) -> Option<String> {
+        Some(String::from("sent as a string"))
+    }
+
+    // Declares `name` as another name for `type_name`, the type of a root array.
+    // Languages without aliases say it in a comment.
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        self.comment(&format!("{name} is a {type_name}"))
+    }
+
+    // Groups the generated types under `name`, e.g. my.models, the way the language
+    // usually does. Languages without a way to do it say it in a comment.
+    fn namespace(&self, name: &str) -> Namespace {
+        Namespace {
+            open: format!("{}\n\n", self.comment(name)),
+            close: String::new(),
+            indent: String::new(),
+        }
+    }
+
+    // Declares a variable holding `literal`, the input as a value of the generated types,
+    // for --emit-sample. None if the language doesn't say how.
+    fn sample_declaration(&self, _literal: &Literal) -> Option<String> {
+        None
+    }
+
+    // Last touches on a whole rendered type, e.g. aligning its fields into columns
+    fn format_type(&self, rendered: String) -> String {
+        rendered
+    }
+
+    // Name of the field holding the value of `json_key`
     fn field_name(&self, json_key: &str) -> String;
 
-    fn format_field_type(&self, tpe: &str, json_key: &str) -> String;
+    // Whether a field can be named after `json_key`. Keys without a letter or digit in them,
+    // e.g. "" or " ", can't in most languages, their fields get placeholder names.
+    fn nameable_key(&self, json_key: &str) -> bool {
+        json_key.chars().any(char::is_alphanumeric)
+    }
+
+    // A whole field declaration line, including indentation and the line break
+    fn format_field(&self, field: &FieldContext) -> String;
 
+    // A list of `arr_type`, whose elements may be null if `optional` is set
     fn format_arr_type(&self, arr_type: String, optional: bool) -> String;
 
+    // Type of a scalar JSON value, null values are of the language's "any" type
     fn premitive_type_name(&self, from: &Value) -> &'static str;
 
+    // Name of the type generated for an object under the JSON key `key`
     fn struct_or_class_name(&self, key: &str) -> String;
 
-    fn struct_name_from_array_key(&self, arr_key: &str) -> String {
-        if let Some(stripped) = arr_key.strip_suffix("ies") {
-            format!("{}y", self.field_name(stripped))
-        } else if let Some(stripped) = arr_key.strip_suffix("s") {
-            self.struct_or_class_name(stripped)
-        } else {
-            self.struct_or_class_name(arr_key)
+    // Starts a comment, e.g. "//", "#", "--" or "(*"
+    fn comment_prefix(&self) -> &'static str {
+        "//"
+    }
+
+    // Ends a comment, e.g. "*)", empty for comments that run until the end of the line
+    fn comment_suffix(&self) -> &'static str {
+        ""
+    }
+
+    // `text` as a comment on a single line
+    fn comment(&self, text: &str) -> String {
+        match self.comment_suffix() {
+            "" => format!("{} {text}", self.comment_prefix()),
+            suffix => format!("{} {text} {suffix}", self.comment_prefix()),
         }
     }
+
+    // Extension of source files in this language, without the leading dot
+    fn file_extension(&self) -> &'static str {
+        "txt"
+    }
+
+    // Reserved words that may show up in the generated code
+    fn keywords(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    // Name of the root type when none is given, before `struct_or_class_name` is applied
+    fn default_type_name(&self) -> &'static str {
+        DEFAULT_TYPE_NAME
+    }
+
+    // Name of the type `line` starts the definition of, if it's the first line of a type or
+    // alias rendered by this formatter
+    fn defined_type_name(&self, line: &str) -> Option<String> {
+        let marker = self.struct_or_class_name(TYPE_NAME_MARKER);
+        let header = self.struct_or_class_header(String::from(TYPE_NAME_MARKER));
+        let alias = self.type_alias(&marker, &marker);
+        let prefixes = [header.as_str(), alias.as_str()]
+            .into_iter()
+            .filter(|text| !text.starts_with(self.comment_prefix()))
+            // Only the line the name is on, headers may start with annotations
+            .filter_map(|text| text.find(&marker).map(|at| &text[..at]))
+            .map(|prefix| prefix.rsplit('\n').next().unwrap_or(prefix));
+        let name = prefixes
+            .filter_map(|prefix| line.strip_prefix(prefix))
+            .map(|rest| {
+                rest.split(|c: char| !c.is_alphanumeric() && c != '_')
+                    .next()
+                    .unwrap_or_default()
+            })
+            .find(|name| !name.is_empty())?;
+        Some(name.to_string())
+    }
+
+    // Tells keywords, type names and other words apart, for highlighting the output
+    fn classify_token(&self, word: &str, _first_in_line: bool) -> TokenKind {
+        classify_word(self, word)
+    }
 }
 
-fn first_char_upper(s: &str) -> String {
-    let mut c = s.chars();
-    match c.next() {
-        None => String::new(),
-        Some(ch) => c.fold(ch.to_uppercase().to_string(), |mut buff, ch| {
-            buff.push(ch);
-            buff
-        }),
+// Words are keywords, the language's primitive types, or generated type names,
+// which start with an uppercase letter in most languages
+fn classify_word<L: LanguageFormatter + ?Sized>(lang: &L, word: &str) -> TokenKind {
+    let primitives = [
+        Value::Bool(true),
+        Value::from(1),
+        Value::from(1.5),
+        Value::from(""),
+        Value::Null,
+    ];
+    if lang.keywords().contains(&word) {
+        TokenKind::Keyword
+    } else if primitives
+        .iter()
+        .flat_map(|sample| {
+            lang.premitive_type_name(sample)
+                .split(|c: char| !c.is_alphanumeric())
+        })
+        .any(|primitive| primitive == word)
+        || word.starts_with(char::is_uppercase)
+    {
+        TokenKind::TypeName
+    } else {
+        TokenKind::Plain
     }
 }
 
-pub fn get_language_formatter(lang: &str) -> Option<Rc<dyn LanguageFormatter>> {
-    match lang.to_lowercase().as_str() {
-        "go" => Some(Rc::new(Go {})),
-        "scala" => Some(Rc::new(Scala {})),
-        "java" => Some(Rc::new(Java {})),
-        "rust" => Some(Rc::new(Rust {})),
-        _ => None,
+// Builds the formatter of a built-in language
+pub type FormatterConstructor = fn(GeneratorOptions) -> Box<dyn LanguageFormatter>;
+
+// Every supported language id, followed by the aliases it can also be selected with and what
+// builds its formatter
+pub static LANGUAGES: &[(&str, &[&str], FormatterConstructor)] = &[
+    ("avro", &["avsc"], |opts| Box::new(Avro { opts })),
+    ("bigquery", &["bq"], |opts| Box::new(BigQuery { opts })),
+    ("c", &[], |opts| Box::new(C { opts })),
+    ("cpp", &["c++", "cxx"], |opts| Box::new(Cpp { opts })),
+    ("capnp", &["capnproto"], |opts| Box::new(CapnProto { opts })),
+    ("crystal", &["cr"], |opts| Box::new(Crystal { opts })),
+    ("dart", &[], |opts| Box::new(Dart { opts })),
+    ("elixir", &["ex", "exs"], |opts| {
+        Box::new(Elixir {
+            opts,
+            modules: HashMap::new(),
+        })
+    }),
+    ("elm", &[], |opts| Box::new(Elm { opts })),
+    ("fsharp", &["f#", "fs"], |opts| Box::new(FSharp { opts })),
+    ("flatbuffers", &["fbs"], |opts| {
+        Box::new(FlatBuffers { opts })
+    }),
+    ("go", &["golang"], |opts| Box::new(Go { opts })),
+    ("graphql", &["gql"], |opts| Box::new(GraphQL { opts })),
+    ("groovy", &[], |opts| Box::new(Groovy { opts })),
+    ("haskell", &["hs"], |opts| Box::new(Haskell { opts })),
+    ("io-ts", &["iots"], |opts| Box::new(IoTs { opts })),
+    ("java", &[], |opts| Box::new(Java { opts })),
+    ("julia", &["jl"], |opts| Box::new(Julia { opts })),
+    ("kotlin", &["kt"], |opts| Box::new(Kotlin { opts })),
+    ("nim", &[], |opts| Box::new(Nim { opts })),
+    ("objc", &["objective-c", "objectivec"], |opts| {
+        Box::new(ObjC { opts })
+    }),
+    ("ocaml", &["ml"], |opts| Box::new(OCaml { opts })),
+    ("openapi", &[], |opts| Box::new(OpenApi { opts })),
+    ("parquet", &[], |opts| Box::new(Parquet { opts })),
+    ("php", &[], |opts| Box::new(Php { opts })),
+    ("proto", &["proto3", "protobuf"], |opts| {
+        Box::new(Proto { opts })
+    }),
+    ("pydantic", &[], |opts| Box::new(Pydantic { opts })),
+    ("python", &["py"], |opts| Box::new(Python { opts })),
+    ("ruby", &["rb"], |opts| Box::new(Ruby { opts })),
+    ("rust", &["rs"], |opts| Box::new(Rust { opts })),
+    ("scala", &[], |opts| Box::new(Scala { opts })),
+    ("thrift", &[], |opts| Box::new(Thrift { opts })),
+    ("xsd", &[], |opts| Box::new(Xsd { opts })),
+    ("zig", &[], |opts| Box::new(Zig { opts })),
+    ("zod", &[], |opts| Box::new(Zod { opts })),
+];
+
+// The built-in languages and the ones registered at runtime
+pub fn available_languages() -> Vec<String> {
+    let mut languages: Vec<String> = LANGUAGES.iter().map(|(id, ..)| id.to_string()).collect();
+    languages.extend(registered_languages());
+    languages.sort();
+    languages.dedup();
+    languages
+}
+
+pub fn get_language_formatter(
+    lang: &str,
+    opts: &GeneratorOptions,
+) -> Result<Box<dyn LanguageFormatter>, JsoncError> {
+    if let Some(formatter) = registered_formatter(lang, opts) {
+        return Ok(formatter);
     }
+
+    let name = lang.to_lowercase();
+    LANGUAGES
+        .iter()
+        .find(|(id, aliases, _)| *id == name || aliases.contains(&name.as_str()))
+        .map(|(_, _, constructor)| constructor(opts.clone()))
+        .ok_or_else(|| JsoncError::UnknownLanguage {
+            name: lang.to_string(),
+            available: available_languages(),
+        })
+}
+
+pub struct Rust {
+    opts: GeneratorOptions,
+}
+pub struct Scala {
+    opts: GeneratorOptions,
+}
+pub struct Go {
+    opts: GeneratorOptions,
+}
+pub struct Java {
+    opts: GeneratorOptions,
+}
+pub struct Kotlin {
+    opts: GeneratorOptions,
+}
+pub struct Python {
+    opts: GeneratorOptions,
 }
+pub struct Pydantic {
+    opts: GeneratorOptions,
+}
+pub struct Dart {
+    opts: GeneratorOptions,
+}
+pub struct Proto {
+    opts: GeneratorOptions,
+}
+pub struct Avro {
+    opts: GeneratorOptions,
+}
+pub struct GraphQL {
+    opts: GeneratorOptions,
+}
+pub struct Haskell {
+    opts: GeneratorOptions,
+}
+pub struct Elm {
+    opts: GeneratorOptions,
+}
+pub struct OCaml {
+    opts: GeneratorOptions,
+}
+pub struct FSharp {
+    opts: GeneratorOptions,
+}
+pub struct Zig {
+    opts: GeneratorOptions,
+}
+pub struct C {
+    opts: GeneratorOptions,
+}
+pub struct Cpp {
+    opts: GeneratorOptions,
+}
+pub struct Php {
+    opts: GeneratorOptions,
+}
+pub struct Elixir {
+    opts: GeneratorOptions,
+    // The module of each type by its key, as it's named in its parent's module and in full
+    modules: HashMap<String, (String, String)>,
+}
+pub struct Nim {
+    opts: GeneratorOptions,
+}
+pub struct Crystal {
+    opts: GeneratorOptions,
+}
+pub struct Ruby {
+    opts: GeneratorOptions,
+}
+pub struct Julia {
+    opts: GeneratorOptions,
+}
+pub struct Groovy {
+    opts: GeneratorOptions,
+}
+pub struct ObjC {
+    opts: GeneratorOptions,
+}
+pub struct Thrift {
+    opts: GeneratorOptions,
+}
+pub struct FlatBuffers {
+    opts: GeneratorOptions,
+}
+pub struct CapnProto {
+    opts: GeneratorOptions,
+}
+pub struct Zod {
+    opts: GeneratorOptions,
+}
+pub struct IoTs {
+    opts: GeneratorOptions,
+}
+pub struct OpenApi {
+    opts: GeneratorOptions,
+}
+pub struct Xsd {
+    opts: GeneratorOptions,
+}
+pub struct Parquet {
+    opts: GeneratorOptions,
+}
+pub struct BigQuery {
+    opts: GeneratorOptions,
+}
+
+static RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
 
-pub struct Rust {}
-pub struct Scala {}
-pub struct Go {}
-pub struct Java {}
+// Keywords that can't be raw identifiers either
+static RUST_PATH_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
 
 impl LanguageFormatter for Rust {
     fn struct_or_class_header(&self, raw: String) -> String {
@@ -64,12 +472,95 @@ impl LanguageFormatter for Rust {
         String::from("}")
     }
 
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("pub type {name} = {type_name};")
+    }
+
+    fn sample_declaration(&self, literal: &Literal) -> Option<String> {
+        let layout = Layout {
+            indent: self.opts.indent_or(RUST_INDENT),
+            comment_prefix: self.comment_prefix(),
+        };
+        Some(format!(
+            "let sample = {};",
+            sample::rust(literal, &layout, 0)
+        ))
+    }
+
+    // A module in a module for every segment of the name
+    fn namespace(&self, name: &str) -> Namespace {
+        let indent = self.opts.indent_or(RUST_INDENT);
+        let modules: Vec<&str> = name.split(['.', ':']).filter(|m| !m.is_empty()).collect();
+        let open = modules
+            .iter()
+            .enumerate()
+            .map(|(depth, module)| format!("{}pub mod {module} {{\n", indent.repeat(depth)))
+            .collect();
+        let close = (0..modules.len())
+            .rev()
+            .map(|depth| format!("{}}}", indent.repeat(depth)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Namespace {
+            open,
+            close,
+            indent: indent.repeat(modules.len()),
+        }
+    }
+
+    // Keywords are raw identifiers, the serde rename keeps the key
     fn field_name(&self, json_key: &str) -> String {
-        String::from(json_key)
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| identifier(json_key));
+        if RUST_PATH_KEYWORDS.contains(&name.as_str()) {
+            format!("{name}_")
+        } else if RUST_KEYWORDS.contains(&name.as_str()) {
+            format!("r#{name}")
+        } else {
+            name
+        }
+    }
+
+    // Every struct derives serde's traits, the ones holding it need them from it too
+    fn format_type(&self, rendered: String) -> String {
+        if self.opts.annotations {
+            format!("#[derive(serde::Serialize, serde::Deserialize)]\n{rendered}")
+        } else {
+            rendered
+        }
+    }
+
+    // Fields that may be null or missing are Options. With strict nulls, the ones that may
+    // be missing also default to None.
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(RUST_INDENT);
+        let field_name = self.field_name(field.name_key);
+        let optional = field.inferred.may_be_missing();
+        let line = if optional {
+            format!("{indent}pub {field_name}: Option<{}>,\n", field.type_name)
+        } else {
+            format!("{indent}pub {field_name}: {},\n", field.type_name)
+        };
+        let mut attributes = vec![];
+        if field_name != field.json_key {
+            attributes.push(format!("rename = {:?}", field.json_key));
+        }
+        if self.opts.strict_null && field.absent {
+            attributes.push(String::from("default"));
+        }
+        if self.opts.annotations && !attributes.is_empty() {
+            format!("{indent}#[serde({})]\n{line}", attributes.join(", "))
+        } else {
+            line
+        }
     }
 
-    fn format_field_type(&self, tpe: &str, json_key: &str) -> String {
-        format!("\tpub {json_key}: {tpe},\n")
+    fn string_encoded_note(&self, _field: &FieldContext) -> Option<String> {
+        Some(String::from(
+            "sent as a string, deserialize with serde_with::DisplayFromStr",
+        ))
     }
 
     fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
@@ -99,15 +590,74 @@ impl LanguageFormatter for Rust {
     }
 
     fn struct_or_class_name(&self, key: &str) -> String {
-        key.split('_')
-            .map(first_char_upper)
-            .fold(String::new(), |mut acc, w| {
-                acc.push_str(&w);
-                acc
-            })
+        self.opts.type_name(key)
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "//"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "rs"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        RUST_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        RUST_KEYWORDS
     }
 }
 
+static SCALA_KEYWORDS: &[&str] = &[
+    "abstract",
+    "case",
+    "catch",
+    "class",
+    "def",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "for",
+    "forSome",
+    "given",
+    "if",
+    "implicit",
+    "import",
+    "lazy",
+    "macro",
+    "match",
+    "new",
+    "null",
+    "object",
+    "override",
+    "package",
+    "private",
+    "protected",
+    "return",
+    "sealed",
+    "super",
+    "then",
+    "this",
+    "throw",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "val",
+    "var",
+    "while",
+    "with",
+    "yield",
+    "_",
+];
+
 impl LanguageFormatter for Scala {
     fn struct_or_class_header(&self, raw: String) -> String {
         let class_name = self.struct_or_class_name(&raw);
@@ -115,26 +665,72 @@ impl LanguageFormatter for Scala {
         format!("case class {class_name}(\n")
     }
 
-    fn struct_or_class_footer(&self, struct_name: Option<String>) -> String {
-        let header_len = self
-            .struct_or_class_header(struct_name.unwrap_or(SCALA_AUTO_GENERATED.to_string()))
-            .len();
-        let tabs = header_len / 8;
-        let mut padding = (1..=tabs).fold(String::new(), |mut acc, _| {
-            acc.push('\t');
-            acc
-        });
-        padding.push(')');
-        padding
+    // Fields are a level deeper than the class, the parenthesis closes at its level. Classes
+    // in a namespace are indented as a whole.
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::from(")")
+    }
+
+    // Top-level aliases need Scala 3
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("type {name} = {type_name}")
+    }
+
+    fn sample_declaration(&self, literal: &Literal) -> Option<String> {
+        let layout = Layout {
+            indent: self.opts.indent_or(SCALA_INDENT),
+            comment_prefix: self.comment_prefix(),
+        };
+        Some(format!(
+            "val sample = {}",
+            sample::scala(literal, &layout, 0)
+        ))
+    }
+
+    // An object, in the package the rest of the name is
+    fn namespace(&self, name: &str) -> Namespace {
+        let (package, object) = match name.rsplit_once('.') {
+            Some((package, object)) => (format!("package {package}\n\n"), object),
+            None => (String::new(), name),
+        };
+        Namespace {
+            open: format!("{package}object {object} {{\n"),
+            close: String::from("}"),
+            indent: self.opts.indent_or(SCALA_INDENT).to_string(),
+        }
     }
 
+    // Keywords and anything else that isn't a name are in backticks, which can hold anything
+    // but backticks and line breaks
     fn field_name(&self, json_key: &str) -> String {
-        camelcase(json_key)
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| self.opts.naming.field_name(json_key))
+            .chars()
+            .map(|c| if c == '`' || c.is_control() { '_' } else { c })
+            .collect::<String>();
+        if is_identifier(&name) && !SCALA_KEYWORDS.contains(&name.as_str()) {
+            name
+        } else {
+            format!("`{name}`")
+        }
     }
 
-    fn format_field_type(&self, tpe: &str, json_key: &str) -> String {
-        let scala_field_name = self.field_name(json_key);
-        format!("\t\t{scala_field_name}: {tpe},\n")
+    // Fields that may be null or missing are Options, the ones that may be missing default
+    // to None
+    fn format_field(&self, field: &FieldContext) -> String {
+        let scala_field_name = self.field_name(field.name_key);
+        let indent = self.opts.indent_or(SCALA_INDENT);
+        let tpe = field.type_name;
+        match (
+            field.inferred.may_be_missing(),
+            field.inferred.may_be_absent(self.opts.strict_null),
+        ) {
+            (true, true) => format!("{indent}{scala_field_name}: Option[{tpe}] = None,\n"),
+            (true, false) => format!("{indent}{scala_field_name}: Option[{tpe}],\n"),
+            (false, _) => format!("{indent}{scala_field_name}: {tpe},\n"),
+        }
     }
 
     fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
@@ -164,18 +760,158 @@ impl LanguageFormatter for Scala {
     }
 
     fn struct_or_class_name(&self, key: &str) -> String {
-        key.split('_')
-            .map(first_char_upper)
-            .fold(String::new(), |mut acc, word| {
-                acc.push_str(word.as_str());
-                acc
-            })
+        self.opts.type_name(key)
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "//"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "scala"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        SCALA_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        SCALA_KEYWORDS
+    }
+}
+
+// `s` as a Go interpreted string literal, the way strconv.Quote writes it. Java string
+// literals are written the same way.
+pub(crate) fn quote(s: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// Whether `name` can be used as it is as a name in most languages
+pub(crate) fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+// Whether `name` can be used as it is in languages whose names are ASCII only
+fn is_ascii_identifier(name: &str) -> bool {
+    name.is_ascii() && is_identifier(name)
+}
+
+// `name` with anything but letters, digits and _ replaced by _, and _ before a leading digit
+fn identifier(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{name}")
+    } else {
+        name
+    }
+}
+
+// Whether the tag of a field has the string option, for numbers sent as strings. It's
+// only allowed on scalar fields.
+fn string_tag(field: &FieldContext, annotations: bool) -> bool {
+    annotations && field.string_encoded && field.array_depth() == 0
+}
+
+// The struct tag naming the JSON key of a field, followed by `options`. Tags are raw
+// string literals, unless the key has a backtick in it, which raw strings can't hold.
+fn go_tag(json_key: &str, options: &[&str]) -> String {
+    let options: String = options.iter().map(|option| format!(",{option}")).collect();
+    let tag = format!("json:{}", quote(&format!("{json_key}{options}")));
+    if tag.contains('`') {
+        quote(&tag)
+    } else {
+        format!("`{tag}`")
+    }
+}
+
+// The tab separated cells of a Go field line, with an example comment after the last one
+// split off into a cell of its own
+fn go_cells(field: &str) -> Vec<&str> {
+    let mut cells: Vec<&str> = field.split('\t').collect();
+    let Some(last) = cells.pop() else {
+        return cells;
+    };
+    // The comment starts after the tag or the type, tags are string literals that may
+    // have anything in them
+    let end = match last.chars().next() {
+        Some('`') => last[1..].find('`').map_or(last.len(), |i| i + 2),
+        Some('"') => {
+            let mut escaped = false;
+            last.char_indices()
+                .skip(1)
+                .find(|&(_, c)| {
+                    let closing = c == '"' && !escaped;
+                    escaped = c == '\\' && !escaped;
+                    closing
+                })
+                .map_or(last.len(), |(i, _)| i + 1)
+        }
+        _ => last.find(' ').unwrap_or(last.len()),
+    };
+    match last[end..].strip_prefix(' ') {
+        Some(comment) => cells.extend([&last[..end], comment]),
+        None => cells.push(last),
+    }
+    cells
+}
+
+// Pads cells into columns the way gofmt does, with at least one space between them.
+// A column spans the consecutive rows that have a cell in it, the last cell of a row
+// isn't part of any column.
+fn align_columns(rows: &[Vec<&str>]) -> Vec<String> {
+    let mut lines: Vec<String> = rows.iter().map(|_| String::new()).collect();
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    for column in 0..columns {
+        let in_column = |row: &Vec<&str>| column + 1 < row.len();
+        let mut start = 0;
+        while start < rows.len() {
+            if !in_column(&rows[start]) {
+                start += 1;
+                continue;
+            }
+            let end = (start..rows.len())
+                .find(|&index| !in_column(&rows[index]))
+                .unwrap_or(rows.len());
+            let width = rows[start..end]
+                .iter()
+                .map(|row| row[column].chars().count() + 1)
+                .max()
+                .unwrap_or(0);
+            for index in start..end {
+                let cell = rows[index][column];
+                lines[index].push_str(cell);
+                let padding = width - cell.chars().count();
+                lines[index].push_str(&" ".repeat(padding));
+            }
+            start = end;
+        }
     }
+    for (line, row) in lines.iter_mut().zip(rows) {
+        line.push_str(row.last().copied().unwrap_or_default());
+    }
+    lines
 }
 
 impl LanguageFormatter for Go {
     fn struct_or_class_header(&self, raw: String) -> String {
-        let go_struct_name = self.field_name(&raw);
+        let go_struct_name = self.struct_or_class_name(&raw);
         format!("type {go_struct_name} struct") + " {\n"
     }
 
@@ -183,19 +919,102 @@ impl LanguageFormatter for Go {
         String::from("}")
     }
 
-    fn field_name(&self, json_key: &str) -> String {
-        json_key
-            .split('_')
-            .map(first_char_upper)
-            .fold(String::new(), |mut buff, w| {
-                buff.push_str(w.as_str());
-                buff
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("type {name} {type_name}")
+    }
+
+    fn sample_declaration(&self, literal: &Literal) -> Option<String> {
+        let layout = Layout {
+            indent: self.opts.indent_or(DEFAULT_INDENT),
+            comment_prefix: self.comment_prefix(),
+        };
+        Some(format!("sample := {}", sample::go(literal, &layout, 0)))
+    }
+
+    // Packages are named after the last element of their import path
+    fn namespace(&self, name: &str) -> Namespace {
+        let package = name.rsplit(['.', '/']).next().unwrap_or(name);
+        Namespace {
+            open: format!("package {package}\n\n"),
+            close: String::new(),
+            indent: String::new(),
+        }
+    }
+
+    // Fields line up the way gofmt has them
+    fn format_type(&self, rendered: String) -> String {
+        let indent = self.opts.indent_or(DEFAULT_INDENT);
+        let rows: Vec<Vec<&str>> = rendered
+            .split('\n')
+            .map(|line| match line.strip_prefix(indent) {
+                Some(field) if field.contains('\t') => go_cells(field),
+                _ => vec![line],
+            })
+            .collect();
+        let is_field = |row: &Vec<&str>| row.len() > 1;
+        align_columns(&rows)
+            .into_iter()
+            .zip(&rows)
+            .map(|(line, row)| {
+                if is_field(row) {
+                    format!("{indent}{line}")
+                } else {
+                    line
+                }
             })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // Fields have to start with an uppercase letter to be exported, whatever the case. The
+    // ones that can't, e.g. after a digit, start with an X.
+    fn field_name(&self, json_key: &str) -> String {
+        let name = match self.opts.cased_field_name(json_key) {
+            Some(name) => first_char_upper(&name),
+            None => self.opts.naming.exported_field_name(json_key),
+        };
+        let name: String = name
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect();
+        if name.starts_with(char::is_uppercase) {
+            name
+        } else {
+            format!("X{}", name.trim_start_matches('_'))
+        }
+    }
+
+    // Fields that may be null or missing are pointers unless they're slices, which can be
+    // nil already, and the ones that may be missing are left out when empty
+    fn format_field(&self, field: &FieldContext) -> String {
+        let json_key = field.json_key;
+        let go_key = self.field_name(field.name_key);
+        let indent = self.opts.indent_or(DEFAULT_INDENT);
+        let optional = field.inferred.may_be_missing();
+        let tpe = if optional && field.array_depth() == 0 {
+            format!("{GO_PTR}{}", field.type_name)
+        } else {
+            field.type_name.to_string()
+        };
+        // Cells are separated by tabs, `format_type` lines them up
+        if self.opts.annotations {
+            let mut options = vec![];
+            if field.inferred.may_be_absent(self.opts.strict_null) {
+                options.push("omitempty");
+            }
+            if string_tag(field, true) {
+                options.push("string");
+            }
+            let tag = go_tag(json_key, &options);
+            format!("{indent}{go_key}\t{tpe}\t{tag}\n")
+        } else {
+            format!("{indent}{go_key}\t{tpe}\n")
+        }
     }
 
-    fn format_field_type(&self, tpe: &str, json_key: &str) -> String {
-        let go_key = self.field_name(&json_key);
-        format!("\t{go_key}\t{tpe}\t\t`json:\"{json_key}\"`\n")
+    // The string option of the tag converts numbers that aren't in an array
+    fn string_encoded_note(&self, field: &FieldContext) -> Option<String> {
+        (!string_tag(field, self.opts.annotations)).then(|| String::from("sent as a string"))
     }
 
     fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
@@ -221,19 +1040,118 @@ impl LanguageFormatter for Go {
     }
 
     fn struct_or_class_name(&self, key: &str) -> String {
-        self.field_name(key)
+        self.opts.type_name(key)
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "//"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "go"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        GO_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "type",
+            "struct",
+            "package",
+            "import",
+            "interface",
+            "map",
+            "func",
+        ]
+    }
+
+    // Exported field names start with an uppercase letter too, they come first on their line
+    fn classify_token(&self, word: &str, first_in_line: bool) -> TokenKind {
+        if first_in_line && !self.keywords().contains(&word) {
+            TokenKind::Plain
+        } else {
+            classify_word(self, word)
+        }
     }
 }
 
-fn camelcase(snake_case: &str) -> String {
-    let mut split = snake_case.split('_');
-    let mut first = String::from(split.next().unwrap_or("Unknown"));
-    while let Some(w) = split.next() {
-        first.push_str(first_char_upper(w).as_str());
+// What a Java field of the type of `field` starts out as when the key is missing
+fn java_default(field: &FieldContext) -> Option<String> {
+    match field.type_ref {
+        TypeRef::Primitive(LogicalType::String | LogicalType::Date | LogicalType::Uuid) => {
+            Some(String::from("\"\""))
+        }
+        TypeRef::Primitive(LogicalType::Int | LogicalType::TimestampMillis) => {
+            Some(String::from("0"))
+        }
+        TypeRef::Primitive(LogicalType::Float | LogicalType::Decimal) => Some(String::from("0f")),
+        TypeRef::Primitive(LogicalType::Bool) => Some(String::from("false")),
+        TypeRef::Primitive(LogicalType::Any) => None,
+        TypeRef::Array { .. } => Some(String::from("new ArrayList<>()")),
+        TypeRef::Object(_) => Some(format!("new {}()", field.type_name)),
     }
-    first
 }
 
+static JAVA_KEYWORDS: &[&str] = &[
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "true",
+    "try",
+    "var",
+    "void",
+    "volatile",
+    "while",
+    "_",
+];
+
 impl LanguageFormatter for Java {
     fn struct_or_class_header(&self, raw: String) -> String {
         let java_class_name = self.struct_or_class_name(&raw);
@@ -244,13 +1162,79 @@ impl LanguageFormatter for Java {
         String::from("}")
     }
 
+    fn namespace(&self, name: &str) -> Namespace {
+        Namespace {
+            open: format!("package {name};\n\n"),
+            close: String::new(),
+            indent: String::new(),
+        }
+    }
+
+    fn sample_declaration(&self, literal: &Literal) -> Option<String> {
+        let layout = Layout {
+            indent: self.opts.indent_or(JAVA_INDENT),
+            comment_prefix: self.comment_prefix(),
+        };
+        let type_name = match literal {
+            Literal::Object { type_name, .. } | Literal::Array { type_name, .. } => type_name,
+            _ => JAVA_ANY,
+        };
+        Some(format!(
+            "{type_name} sample = {};",
+            sample::java(literal, &layout, 0)
+        ))
+    }
+
+    // Keywords get an _ after them, @JsonProperty keeps the key
     fn field_name(&self, json_key: &str) -> String {
-        camelcase(json_key)
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| self.opts.naming.field_name(json_key));
+        let name = identifier(&name);
+        if JAVA_KEYWORDS.contains(&name.as_str()) {
+            format!("{name}_")
+        } else {
+            name
+        }
+    }
+
+    // Fields that may be null or missing are @Nullable. With strict nulls, the ones that
+    // may only be missing start out with a value of their type instead.
+    fn format_field(&self, field: &FieldContext) -> String {
+        let java_field_name = self.field_name(field.name_key);
+        let indent = self.opts.indent_or(JAVA_INDENT);
+        let strict_null = self.opts.strict_null;
+        let nullable = field.inferred.may_be_null(strict_null);
+        let initializer = match java_default(field) {
+            Some(value) if field.inferred.may_be_absent(strict_null) && !nullable => {
+                format!(" = {value}")
+            }
+            _ => String::new(),
+        };
+        let line = format!(
+            "{indent}public {} {java_field_name}{initializer};\n",
+            field.type_name
+        );
+        let mut annotations = vec![];
+        if java_field_name != field.json_key {
+            annotations.push(format!(
+                "{indent}@JsonProperty({})\n",
+                quote(field.json_key)
+            ));
+        }
+        if nullable {
+            annotations.push(format!("{indent}@Nullable\n"));
+        }
+        if self.opts.annotations {
+            annotations.concat() + &line
+        } else {
+            line
+        }
     }
 
-    fn format_field_type(&self, tpe: &str, json_key: &str) -> String {
-        let java_field_name = self.field_name(json_key);
-        format!("\tpublic {tpe} {java_field_name};\n")
+    fn string_encoded_note(&self, _field: &FieldContext) -> Option<String> {
+        Some(String::from("sent as a string, Jackson converts it"))
     }
 
     fn format_arr_type(&self, arr_type: String, _optional: bool) -> String {
@@ -275,11 +1259,6128 @@ impl LanguageFormatter for Java {
     }
 
     fn struct_or_class_name(&self, key: &str) -> String {
-        key.split('_')
-            .map(first_char_upper)
-            .fold(String::new(), |mut acc, w| {
-                acc.push_str(w.as_str());
-                acc
-            })
+        self.opts.type_name(key)
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "//"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "java"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        JAVA_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        JAVA_KEYWORDS
+    }
+}
+
+impl LanguageFormatter for Kotlin {
+    // kotlinx.serialization only serializes classes marked @Serializable
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let class_name = self.struct_or_class_name(&raw);
+        if self.opts.annotations {
+            format!("@Serializable\ndata class {class_name}(\n")
+        } else {
+            format!("data class {class_name}(\n")
+        }
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::from(")")
+    }
+
+    fn imports(&self, schema: &Schema) -> Option<String> {
+        let renamed = schema.types.iter().any(|type_def| {
+            type_def
+                .fields
+                .iter()
+                .zip(unique_name_keys(type_def, self))
+                .any(|(field, name_key)| self.field_name(&name_key).trim_matches('`') != field.key)
+        });
+        let untyped = schema
+            .type_refs()
+            .any(|type_ref| type_ref.innermost() == &TypeRef::Primitive(LogicalType::Any));
+        let mut imports = vec![];
+        if self.opts.annotations && renamed {
+            imports.push("import kotlinx.serialization.SerialName\n");
+        }
+        if self.opts.annotations {
+            imports.push("import kotlinx.serialization.Serializable\n");
+        }
+        if untyped {
+            imports.push("import kotlinx.serialization.json.JsonElement\n");
+        }
+        (!imports.is_empty()).then(|| imports.concat() + "\n")
+    }
+
+    // Data classes need at least one property, types without fields are plain classes
+    fn format_type(&self, rendered: String) -> String {
+        match rendered.strip_suffix("(\n)") {
+            Some(header) => header.replacen("data class", "class", 1),
+            None => rendered,
+        }
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("typealias {name} = {type_name}")
+    }
+
+    // Types without fields are plain classes, see `format_type`
+    fn defined_type_name(&self, line: &str) -> Option<String> {
+        let rest = ["data class ", "class ", "typealias "]
+            .into_iter()
+            .find_map(|prefix| line.strip_prefix(prefix))?;
+        let name = rest
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .unwrap_or_default();
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    fn namespace(&self, name: &str) -> Namespace {
+        Namespace {
+            open: format!("package {name}\n\n"),
+            close: String::new(),
+            indent: String::new(),
+        }
+    }
+
+    fn sample_declaration(&self, literal: &Literal) -> Option<String> {
+        let layout = Layout {
+            indent: self.opts.indent_or(KOTLIN_INDENT),
+            comment_prefix: self.comment_prefix(),
+        };
+        Some(format!(
+            "val sample = {}",
+            sample::kotlin(literal, &layout, 0)
+        ))
+    }
+
+    // Backticks, line breaks and what the JVM doesn't allow in names are replaced. Names
+    // that are keywords or still aren't identifiers, e.g. last-name, are quoted with backticks.
+    fn field_name(&self, json_key: &str) -> String {
+        let name: String = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| self.opts.naming.field_name(json_key))
+            .chars()
+            .map(|c| match c {
+                '`' | '.' | ';' | '[' | ']' | '/' | '<' | '>' | ':' | '\\' => '_',
+                c if c.is_control() => '_',
+                c => c,
+            })
+            .collect();
+        if !is_identifier(&name) || self.keywords().contains(&name.as_str()) {
+            format!("`{name}`")
+        } else {
+            name
+        }
+    }
+
+    // Fields that may be null or missing are nullable, the ones that may be missing also
+    // default to null
+    fn format_field(&self, field: &FieldContext) -> String {
+        let kotlin_field_name = self.field_name(field.name_key);
+        let indent = self.opts.indent_or(KOTLIN_INDENT);
+        let tpe = field.type_name;
+        let line = match (
+            field.inferred.may_be_missing(),
+            field.inferred.may_be_absent(self.opts.strict_null),
+        ) {
+            (true, true) => format!("{indent}val {kotlin_field_name}: {tpe}? = null,\n"),
+            (true, false) => format!("{indent}val {kotlin_field_name}: {tpe}?,\n"),
+            (false, _) => format!("{indent}val {kotlin_field_name}: {tpe},\n"),
+        };
+        if self.opts.annotations && kotlin_field_name.trim_matches('`') != field.json_key {
+            format!(
+                "{indent}@SerialName({})\n{line}",
+                kotlin_string(field.json_key)
+            )
+        } else {
+            line
+        }
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        if optional && !arr_type.ends_with('?') {
+            format!("List<{arr_type}?>")
+        } else {
+            format!("List<{arr_type}>")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => KOTLIN_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    KOTLIN_FLOAT
+                } else {
+                    KOTLIN_INT
+                }
+            }
+            Value::String(_) => KOTLIN_STRING,
+            Value::Null => KOTLIN_ANY,
+            // Non-primitives should not be passed to this function
+            _ => KOTLIN_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        self.opts.type_name(key)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "kt"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        KOTLIN_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "as",
+            "class",
+            "data",
+            "false",
+            "fun",
+            "in",
+            "interface",
+            "is",
+            "null",
+            "object",
+            "package",
+            "return",
+            "true",
+            "typealias",
+            "val",
+            "var",
+            "when",
+        ]
+    }
+}
+
+// `s` as a Kotlin string literal, where $ starts a template
+pub(crate) fn kotlin_string(s: &str) -> String {
+    quote(s).replace('$', "\\$")
+}
+
+// Python's reserved words, fields named like one get an underscore after the name
+static PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+// `key` in snake_case, or the case asked for, as a Python identifier
+fn python_identifier(key: &str, opts: &GeneratorOptions) -> String {
+    let name = opts
+        .cased_field_name(key)
+        .unwrap_or_else(|| Case::Snake.apply(key));
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("field_{name}")
+    } else if PYTHON_KEYWORDS.contains(&name.as_str()) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+// A class body is its indented lines, and `pass` if there are none. In dataclasses, fields
+// with a default have to come after the ones without, which `defaults_last` sees to.
+fn python_body(rendered: String, indent: &str, defaults_last: bool) -> String {
+    let lines: Vec<&str> = rendered.trim_end_matches('\n').split('\n').collect();
+    let (fields, header): (Vec<&str>, Vec<&str>) =
+        lines.into_iter().partition(|line| line.starts_with(indent));
+    let has_default = |line: &&str| {
+        let declaration = line.split(" # ").next().unwrap_or_default();
+        declaration.contains(" = ")
+    };
+    let (defaults, required): (Vec<&str>, Vec<&str>) = fields
+        .into_iter()
+        .partition(|line| defaults_last && has_default(line));
+    let pass = format!("{indent}pass");
+    let mut body: Vec<&str> = required.into_iter().chain(defaults).collect();
+    if body.is_empty() {
+        body.push(&pass);
+    }
+    header
+        .into_iter()
+        .chain(body)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn python_list(arr_type: String, optional: bool) -> String {
+    if optional && arr_type != PYTHON_ANY {
+        format!("list[Optional[{arr_type}]]")
+    } else {
+        format!("list[{arr_type}]")
+    }
+}
+
+// Whether `type_ref` is an array whose elements are Optional, see `python_list`
+fn has_optional_elements(type_ref: &TypeRef) -> bool {
+    match type_ref {
+        TypeRef::Array { element, optional } => {
+            (*optional && **element != TypeRef::Primitive(LogicalType::Any))
+                || has_optional_elements(element)
+        }
+        _ => false,
+    }
+}
+
+// The import of what the types of `schema` use from typing, if anything
+fn python_typing(schema: &Schema) -> Option<String> {
+    let fields = || schema.types.iter().flat_map(|type_def| &type_def.fields);
+    let mut names = vec![];
+    if schema
+        .type_refs()
+        .any(|type_ref| type_ref.innermost() == &TypeRef::Primitive(LogicalType::Any))
+    {
+        names.push("Any");
+    }
+    if fields().any(Field::may_be_missing) || schema.type_refs().any(has_optional_elements) {
+        names.push("Optional");
+    }
+    (!names.is_empty()).then(|| format!("from typing import {}\n", names.join(", ")))
+}
+
+fn python_primitive(from: &Value) -> &'static str {
+    match from {
+        Value::Bool(_) => PYTHON_BOOL,
+        Value::Number(n) => {
+            if n.is_f64() {
+                PYTHON_FLOAT
+            } else {
+                PYTHON_INT
+            }
+        }
+        Value::String(_) => PYTHON_STRING,
+        Value::Null => PYTHON_ANY,
+        // Non-primitives should not be passed to this function
+        _ => PYTHON_ANY,
+    }
+}
+
+// Classes and aliases start at the beginning of their line, aliases are the only other
+// assignments to names starting with an uppercase letter
+fn python_defined_type_name(line: &str) -> Option<String> {
+    let name = match line.strip_prefix("class ") {
+        Some(rest) => rest,
+        None => line.split_once(" = ")?.0,
+    };
+    let name: String = name
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    name.starts_with(char::is_uppercase).then_some(name)
+}
+
+impl LanguageFormatter for Python {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let class_name = self.struct_or_class_name(&raw);
+        format!("@dataclass\nclass {class_name}:\n")
+    }
+
+    fn imports(&self, schema: &Schema) -> Option<String> {
+        let mut imports = vec![];
+        if !schema.types.is_empty() {
+            imports.push(String::from("from dataclasses import dataclass\n"));
+        }
+        imports.extend(python_typing(schema));
+        (!imports.is_empty()).then(|| imports.concat() + "\n")
+    }
+
+    // The body ends where the indentation does
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::new()
+    }
+
+    fn format_type(&self, rendered: String) -> String {
+        python_body(rendered, self.opts.indent_or(PYTHON_INDENT), true)
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("{name} = {type_name}")
+    }
+
+    fn defined_type_name(&self, line: &str) -> Option<String> {
+        python_defined_type_name(line)
+    }
+
+    fn sample_declaration(&self, literal: &Literal) -> Option<String> {
+        let layout = Layout {
+            indent: self.opts.indent_or(PYTHON_INDENT),
+            comment_prefix: self.comment_prefix(),
+        };
+        Some(format!("sample = {}", sample::python(literal, &layout, 0)))
+    }
+
+    fn field_name(&self, json_key: &str) -> String {
+        python_identifier(json_key, &self.opts)
+    }
+
+    // Fields that may be null or missing are Optional, the ones that may be missing also
+    // default to None
+    fn format_field(&self, field: &FieldContext) -> String {
+        let python_field_name = self.field_name(field.name_key);
+        let indent = self.opts.indent_or(PYTHON_INDENT);
+        let tpe = field.type_name;
+        match (
+            field.inferred.may_be_missing(),
+            field.inferred.may_be_absent(self.opts.strict_null),
+        ) {
+            (true, true) => format!("{indent}{python_field_name}: Optional[{tpe}] = None\n"),
+            (true, false) => format!("{indent}{python_field_name}: Optional[{tpe}]\n"),
+            (false, _) => format!("{indent}{python_field_name}: {tpe}\n"),
+        }
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        python_list(arr_type, optional)
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        python_primitive(from)
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        self.opts.type_name(key)
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "#"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "py"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        PYTHON_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        PYTHON_KEYWORDS
+    }
+}
+
+impl LanguageFormatter for Pydantic {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let class_name = self.struct_or_class_name(&raw);
+        format!("class {class_name}(BaseModel):\n")
+    }
+
+    fn imports(&self, schema: &Schema) -> Option<String> {
+        let aliased = self.opts.annotations
+            && schema.types.iter().any(|type_def| {
+                type_def
+                    .fields
+                    .iter()
+                    .zip(unique_name_keys(type_def, self))
+                    .any(|(field, name_key)| self.field_name(&name_key) != field.key)
+            });
+        let mut names = vec![];
+        if !schema.types.is_empty() {
+            names.push("BaseModel");
+        }
+        if aliased {
+            names.extend(["ConfigDict", "Field"]);
+        }
+        let mut imports = vec![];
+        if !names.is_empty() {
+            imports.push(format!("from pydantic import {}\n", names.join(", ")));
+        }
+        imports.extend(python_typing(schema));
+        (!imports.is_empty()).then(|| imports.concat() + "\n")
+    }
+
+    // The body ends where the indentation does
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::new()
+    }
+
+    // Models with aliases can still be made with the names of their fields, the way
+    // --emit-sample makes them
+    fn format_type(&self, rendered: String) -> String {
+        let indent = self.opts.indent_or(PYTHON_INDENT);
+        let body = python_body(rendered, indent, false);
+        if !body.contains("alias=") {
+            return body;
+        }
+        let config = format!("{indent}model_config = ConfigDict(populate_by_name=True)\n");
+        match body.split_once('\n') {
+            Some((header, fields)) => format!("{header}\n{config}{fields}"),
+            None => body,
+        }
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("{name} = {type_name}")
+    }
+
+    fn defined_type_name(&self, line: &str) -> Option<String> {
+        python_defined_type_name(line)
+    }
+
+    fn sample_declaration(&self, literal: &Literal) -> Option<String> {
+        let layout = Layout {
+            indent: self.opts.indent_or(PYTHON_INDENT),
+            comment_prefix: self.comment_prefix(),
+        };
+        Some(format!("sample = {}", sample::python(literal, &layout, 0)))
+    }
+
+    // Pydantic takes names starting with _ for private attributes rather than fields
+    fn field_name(&self, json_key: &str) -> String {
+        let name = python_identifier(json_key, &self.opts);
+        if name.starts_with('_') {
+            format!("field{name}")
+        } else {
+            name
+        }
+    }
+
+    // Fields named other than their key, e.g. last_name for last-name, are validated from
+    // the key by an alias. Fields that may be null or missing are Optional, and the ones that
+    // may be missing also default to None.
+    fn format_field(&self, field: &FieldContext) -> String {
+        let python_field_name = self.field_name(field.name_key);
+        let indent = self.opts.indent_or(PYTHON_INDENT);
+        let tpe = if field.inferred.may_be_missing() {
+            format!("Optional[{}]", field.type_name)
+        } else {
+            field.type_name.to_string()
+        };
+        let default = field
+            .inferred
+            .may_be_absent(self.opts.strict_null)
+            .then_some("None");
+        let alias = self.opts.annotations && python_field_name != field.json_key;
+        match (alias, default) {
+            (true, Some(default)) => format!(
+                "{indent}{python_field_name}: {tpe} = Field(default={default}, alias={})\n",
+                quote(field.json_key)
+            ),
+            (true, None) => format!(
+                "{indent}{python_field_name}: {tpe} = Field(alias={})\n",
+                quote(field.json_key)
+            ),
+            (false, Some(default)) => format!("{indent}{python_field_name}: {tpe} = {default}\n"),
+            (false, None) => format!("{indent}{python_field_name}: {tpe}\n"),
+        }
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        python_list(arr_type, optional)
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        python_primitive(from)
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        self.opts.type_name(key)
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "#"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "py"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        PYTHON_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        PYTHON_KEYWORDS
+    }
+}
+
+// Dart's reserved words, fields named like one get an underscore after the name
+static DART_KEYWORDS: &[&str] = &[
+    "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else",
+    "enum", "extends", "false", "final", "finally", "for", "if", "in", "is", "new", "null",
+    "rethrow", "return", "super", "switch", "this", "throw", "true", "try", "var", "void", "while",
+    "with",
+];
+
+// `s` as a Dart string literal, single quoted the way dart format has them. $ starts an
+// interpolation.
+pub(crate) fn dart_string(s: &str) -> String {
+    let mut quoted = String::from('\'');
+    for c in s.chars() {
+        match c {
+            '\'' => quoted.push_str("\\'"),
+            '\\' => quoted.push_str("\\\\"),
+            '$' => quoted.push_str("\\$"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+impl Dart {
+    // Reads a value of `type_ref` out of `value`, a dynamic from jsonDecode
+    fn read_json(&self, type_ref: &TypeRef, value: &str) -> String {
+        match type_ref {
+            TypeRef::Primitive(LogicalType::Any) => value.to_string(),
+            // Whole numbers are decoded as ints
+            TypeRef::Primitive(LogicalType::Float | LogicalType::Decimal) => {
+                format!("({value} as num).toDouble()")
+            }
+            TypeRef::Primitive(_) => format!("{value} as {}", type_name(type_ref, self)),
+            TypeRef::Object(key) => format!(
+                "{}.fromJson({value} as Map<String, dynamic>)",
+                self.struct_or_class_name(key)
+            ),
+            TypeRef::Array { element, optional } => {
+                let item = self.read_json(element, "e");
+                let item = if *optional && **element != TypeRef::Primitive(LogicalType::Any) {
+                    format!("e == null ? null : {item}")
+                } else {
+                    item
+                };
+                format!("({value} as List<dynamic>).map((e) => {item}).toList()")
+            }
+        }
+    }
+
+    // Writes `value` of `type_ref` the way jsonEncode takes it, generated types as maps
+    fn write_json(&self, type_ref: &TypeRef, value: &str, nullable: bool) -> String {
+        let access = if nullable { "?." } else { "." };
+        match type_ref {
+            TypeRef::Object(_) => format!("{value}{access}toJson()"),
+            TypeRef::Array { element, optional }
+                if matches!(type_ref.innermost(), TypeRef::Object(_)) =>
+            {
+                let item = self.write_json(element, "e", *optional);
+                format!("{value}{access}map((e) => {item}).toList()")
+            }
+            _ => value.to_string(),
+        }
+    }
+
+    fn is_nullable(&self, field: &FieldContext) -> bool {
+        field.inferred.may_be_missing()
+    }
+}
+
+impl LanguageFormatter for Dart {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let class_name = self.struct_or_class_name(&raw);
+        format!("class {class_name} ") + "{\n"
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::from("}")
+    }
+
+    // A constructor with a named parameter per field, then fromJson and toJson, which
+    // Dart can't derive without code generation
+    fn type_members(&self, raw: String, fields: &[FieldContext]) -> String {
+        let class_name = self.struct_or_class_name(&raw);
+        let indent = self.opts.indent_or(DART_INDENT);
+        let (one, two, three) = (indent, indent.repeat(2), indent.repeat(3));
+        let names: Vec<String> = fields
+            .iter()
+            .map(|field| self.field_name(field.name_key))
+            .collect();
+        if fields.is_empty() {
+            let mut members = format!("{one}{class_name}();\n");
+            if self.opts.annotations {
+                members.push_str(&format!(
+                    "\n{one}factory {class_name}.fromJson(Map<String, dynamic> json) => {class_name}();\n"
+                ));
+                members.push_str(&format!("\n{one}Map<String, dynamic> toJson() => {{}};\n"));
+            }
+            return members;
+        }
+        let mut members = format!("\n{one}{class_name}({{\n");
+        for (field, name) in fields.iter().zip(&names) {
+            let required = if field.inferred.may_be_absent(self.opts.strict_null) {
+                ""
+            } else {
+                "required "
+            };
+            members.push_str(&format!("{two}{required}this.{name},\n"));
+        }
+        members.push_str(&format!("{one}}});\n"));
+        if !self.opts.annotations {
+            return members;
+        }
+
+        members.push_str(&format!(
+            "\n{one}factory {class_name}.fromJson(Map<String, dynamic> json) {{\n"
+        ));
+        members.push_str(&format!("{two}return {class_name}(\n"));
+        for (field, name) in fields.iter().zip(&names) {
+            let value = format!("json[{}]", dart_string(field.json_key));
+            let read = self.read_json(field.type_ref, &value);
+            let read = if self.is_nullable(field) {
+                format!("{value} == null ? null : {read}")
+            } else {
+                read
+            };
+            members.push_str(&format!("{three}{name}: {read},\n"));
+        }
+        members.push_str(&format!("{two});\n{one}}}\n"));
+
+        members.push_str(&format!("\n{one}Map<String, dynamic> toJson() {{\n"));
+        members.push_str(&format!("{two}return {{\n"));
+        for (field, name) in fields.iter().zip(&names) {
+            let write = self.write_json(field.type_ref, name, self.is_nullable(field));
+            members.push_str(&format!(
+                "{three}{}: {write},\n",
+                dart_string(field.json_key)
+            ));
+        }
+        members.push_str(&format!("{two}}};\n{one}}}\n"));
+        members
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("typedef {name} = {type_name};")
+    }
+
+    fn namespace(&self, name: &str) -> Namespace {
+        Namespace {
+            open: format!("library {name};\n\n"),
+            close: String::new(),
+            indent: String::new(),
+        }
+    }
+
+    fn sample_declaration(&self, literal: &Literal) -> Option<String> {
+        let layout = Layout {
+            indent: self.opts.indent_or(DART_INDENT),
+            comment_prefix: self.comment_prefix(),
+        };
+        Some(format!(
+            "final sample = {};",
+            sample::dart(literal, &layout, 0)
+        ))
+    }
+
+    // Keys that don't make a name as they are, e.g. last-name, are put in camelCase, and
+    // names that are keywords get an underscore
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| self.opts.naming.field_name(json_key));
+        // Names are ASCII only
+        let name = if is_ascii_identifier(&name) {
+            name
+        } else {
+            Case::Camel.apply(&ascii_identifier(json_key))
+        };
+        if !is_ascii_identifier(&name) {
+            format!("field{}", first_char_upper(&name))
+        } else if DART_KEYWORDS.contains(&name.as_str()) {
+            format!("{name}_")
+        } else {
+            name
+        }
+    }
+
+    // With strict nulls, fields that may be missing or null are nullable
+    fn format_field(&self, field: &FieldContext) -> String {
+        let dart_field_name = self.field_name(field.name_key);
+        let indent = self.opts.indent_or(DART_INDENT);
+        let nullable = self.is_nullable(field) && field.type_name != DART_ANY;
+        let mark = if nullable { "?" } else { "" };
+        format!(
+            "{indent}final {}{mark} {dart_field_name};\n",
+            field.type_name
+        )
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        if optional && arr_type != DART_ANY {
+            format!("List<{arr_type}?>")
+        } else {
+            format!("List<{arr_type}>")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => DART_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    DART_FLOAT
+                } else {
+                    DART_INT
+                }
+            }
+            Value::String(_) => DART_STRING,
+            Value::Null => DART_ANY,
+            // Non-primitives should not be passed to this function
+            _ => DART_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        self.opts.type_name(key)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "dart"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        DART_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "class", "final", "required", "factory", "return", "this", "typedef", "library",
+            "dynamic", "null",
+        ]
+    }
+}
+
+// The JSON name protobuf gives a field by default, its name in lowerCamelCase
+fn proto_json_name(name: &str) -> String {
+    let mut json_name = String::new();
+    let mut upper = false;
+    for c in name.chars() {
+        match c {
+            '_' => upper = true,
+            c if upper => {
+                json_name.extend(c.to_uppercase());
+                upper = false;
+            }
+            c => json_name.push(c),
+        }
+    }
+    json_name
+}
+
+impl LanguageFormatter for Proto {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let message_name = self.struct_or_class_name(&raw);
+        format!("message {message_name} ") + "{\n"
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::from("}")
+    }
+
+    fn preamble(&self, _schema: &Schema) -> Option<String> {
+        Some(String::from("syntax = \"proto3\";\n\n"))
+    }
+
+    // Untyped fields and arrays of arrays are typed with the well-known types
+    fn imports(&self, schema: &Schema) -> Option<String> {
+        let well_known = |type_ref: &TypeRef| {
+            type_ref.innermost() == &TypeRef::Primitive(LogicalType::Any)
+                || type_ref.array_depth() > 1
+        };
+        schema
+            .type_refs()
+            .any(well_known)
+            .then(|| String::from("import \"google/protobuf/struct.proto\";\n\n"))
+    }
+
+    fn namespace(&self, name: &str) -> Namespace {
+        Namespace {
+            open: format!("package {name};\n\n"),
+            close: String::new(),
+            indent: String::new(),
+        }
+    }
+
+    // Field names are snake_case, the way the style guide has them
+    fn field_name(&self, json_key: &str) -> String {
+        let name: String = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| Case::Snake.apply(json_key))
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            name
+        } else {
+            format!("field_{name}")
+        }
+    }
+
+    // Fields are numbered in the order they're declared. The JSON name is given when
+    // protobuf's own, the name in lowerCamelCase, isn't the key.
+    fn format_field(&self, field: &FieldContext) -> String {
+        let proto_field_name = self.field_name(field.name_key);
+        let indent = self.opts.indent_or(PROTO_INDENT);
+        let optional = field.inferred.may_be_missing()
+            && field.array_depth() == 0
+            && field.type_name != PROTO_ANY;
+        let label = if optional { "optional " } else { "" };
+        let number = field.index + 1;
+        let json_name =
+            if self.opts.annotations && proto_json_name(&proto_field_name) != field.json_key {
+                format!(" [json_name = {}]", quote(field.json_key))
+            } else {
+                String::new()
+            };
+        format!(
+            "{indent}{label}{} {proto_field_name} = {number}{json_name};\n",
+            field.type_name
+        )
+    }
+
+    // Repeated fields can't hold nulls, nor other repeated fields
+    fn format_arr_type(&self, arr_type: String, _optional: bool) -> String {
+        if arr_type.starts_with("repeated ") {
+            format!("repeated {PROTO_LIST}")
+        } else {
+            format!("repeated {arr_type}")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => PROTO_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    PROTO_FLOAT
+                } else {
+                    PROTO_INT
+                }
+            }
+            Value::String(_) => PROTO_STRING,
+            Value::Null => PROTO_ANY,
+            // Non-primitives should not be passed to this function
+            _ => PROTO_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        self.opts.type_name(key)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "proto"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        PROTO_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "syntax",
+            "package",
+            "import",
+            "message",
+            "repeated",
+            "optional",
+            "json_name",
+        ]
+    }
+}
+
+// `name` with anything but ASCII letters, digits and _ replaced, which is what Avro and
+// GraphQL names can have
+fn ascii_identifier(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{name}")
+    } else {
+        name
+    }
+}
+
+// An Avro schema, or part of one, or of a BigQuery one. Objects keep their keys in the
+// order they're written in, the way Avro schemas usually have them, "type" first.
+enum AvroJson {
+    Null,
+    String(String),
+    Array(Vec<AvroJson>),
+    Object(Vec<(&'static str, AvroJson)>),
+}
+
+impl AvroJson {
+    fn name(name: &str) -> AvroJson {
+        AvroJson::String(name.to_string())
+    }
+
+    // Pretty printed, except for arrays of names, e.g. unions, which stay on one line
+    fn write(&self, indent: &str, depth: usize, out: &mut String) {
+        let inner = indent.repeat(depth + 1);
+        match self {
+            AvroJson::Null => out.push_str("null"),
+            AvroJson::String(s) => out.push_str(&quote(s)),
+            AvroJson::Array(items)
+                if items
+                    .iter()
+                    .all(|item| matches!(item, AvroJson::Null | AvroJson::String(_))) =>
+            {
+                out.push('[');
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        out.push_str(", ");
+                    }
+                    item.write(indent, depth, out);
+                }
+                out.push(']');
+            }
+            AvroJson::Array(items) => {
+                out.push_str("[\n");
+                for (index, item) in items.iter().enumerate() {
+                    out.push_str(&inner);
+                    item.write(indent, depth + 1, out);
+                    out.push_str(if index + 1 < items.len() { ",\n" } else { "\n" });
+                }
+                out.push_str(&indent.repeat(depth));
+                out.push(']');
+            }
+            AvroJson::Object(entries) => {
+                out.push_str("{\n");
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    out.push_str(&format!("{inner}{}: ", quote(key)));
+                    value.write(indent, depth + 1, out);
+                    out.push_str(if index + 1 < entries.len() {
+                        ",\n"
+                    } else {
+                        "\n"
+                    });
+                }
+                out.push_str(&indent.repeat(depth));
+                out.push('}');
+            }
+        }
+    }
+}
+
+impl Avro {
+    // The schema of `type_ref`. A record is defined where it's first used and referred to
+    // by name after that, `defined` has the ones defined so far.
+    fn avro_type(
+        &self,
+        type_ref: &TypeRef,
+        schema: &Schema,
+        defined: &mut Vec<String>,
+    ) -> AvroJson {
+        let logical = |base: &str, logical_type: &str| {
+            AvroJson::Object(vec![
+                ("type", AvroJson::name(base)),
+                ("logicalType", AvroJson::name(logical_type)),
+            ])
+        };
+        match type_ref {
+            TypeRef::Primitive(LogicalType::Any) => AvroJson::Array(
+                std::iter::once(AvroJson::name("null"))
+                    .chain(AVRO_SCALARS.iter().map(|name| AvroJson::name(name)))
+                    .collect(),
+            ),
+            TypeRef::Primitive(LogicalType::Uuid) => logical(AVRO_STRING, "uuid"),
+            TypeRef::Primitive(LogicalType::TimestampMillis) => {
+                logical(AVRO_INT, "timestamp-millis")
+            }
+            TypeRef::Primitive(_) => AvroJson::String(type_name(type_ref, self)),
+            TypeRef::Object(key) => {
+                let name = self.struct_or_class_name(key);
+                let type_def = schema.types.iter().find(|type_def| &type_def.key == key);
+                let Some(type_def) = type_def.filter(|_| !defined.contains(&name)) else {
+                    return AvroJson::String(name);
+                };
+                let first = defined.is_empty();
+                defined.push(name.clone());
+                let names = unique_name_keys(type_def, self);
+                let fields = type_def
+                    .fields
+                    .iter()
+                    .zip(&names)
+                    .map(|(field, name_key)| {
+                        let field_type = self.avro_type(&field.type_ref, schema, defined);
+                        // Fields that may be null or missing are unions with null, and the
+                        // ones that may be missing default to it
+                        let field_type = if field.may_be_missing() {
+                            AvroJson::Array(vec![AvroJson::name("null"), field_type])
+                        } else {
+                            field_type
+                        };
+                        let mut entries = vec![
+                            ("name", AvroJson::String(self.field_name(name_key))),
+                            ("type", field_type),
+                        ];
+                        if field.may_be_absent(self.opts.strict_null) {
+                            entries.push(("default", AvroJson::Null));
+                        }
+                        AvroJson::Object(entries)
+                    })
+                    .collect();
+                let mut record = vec![
+                    ("type", AvroJson::name("record")),
+                    ("name", AvroJson::String(name)),
+                ];
+                // The namespace goes on the first record, the others are in it too
+                if let Some(namespace) = self.opts.namespace.as_deref().filter(|_| first) {
+                    record.push(("namespace", AvroJson::name(namespace)));
+                }
+                record.push(("fields", AvroJson::Array(fields)));
+                AvroJson::Object(record)
+            }
+            TypeRef::Array { element, optional } => {
+                let items = self.avro_type(element, schema, defined);
+                let nullable = *optional && **element != TypeRef::Primitive(LogicalType::Any);
+                let items = if nullable {
+                    AvroJson::Array(vec![AvroJson::name("null"), items])
+                } else {
+                    items
+                };
+                AvroJson::Object(vec![("type", AvroJson::name("array")), ("items", items)])
+            }
+        }
+    }
+}
+
+impl LanguageFormatter for Avro {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let record_name = self.struct_or_class_name(&raw);
+        format!(
+            "{{\"type\": \"record\", \"name\": {}, \"fields\": [\n",
+            quote(&record_name)
+        )
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::from("]}")
+    }
+
+    // A single .avsc document, with every record nested where it's first used
+    fn render_schema(&self, schema: &Schema) -> Option<String> {
+        let mut text = String::new();
+        self.avro_type(&schema.root, schema, &mut vec![]).write(
+            self.opts.indent_or(AVRO_INDENT),
+            0,
+            &mut text,
+        );
+        Some(text)
+    }
+
+    fn field_name(&self, json_key: &str) -> String {
+        ascii_identifier(
+            &self
+                .opts
+                .cased_field_name(json_key)
+                .unwrap_or_else(|| String::from(json_key)),
+        )
+    }
+
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(AVRO_INDENT);
+        let name = quote(&self.field_name(field.name_key));
+        format!(
+            "{indent}{{\"name\": {name}, \"type\": {}}},\n",
+            field.type_name
+        )
+    }
+
+    fn format_arr_type(&self, arr_type: String, _optional: bool) -> String {
+        format!("{{\"type\": \"array\", \"items\": {arr_type}}}")
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => AVRO_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    AVRO_FLOAT
+                } else {
+                    AVRO_INT
+                }
+            }
+            Value::String(_) => AVRO_STRING,
+            Value::Null => AVRO_ANY,
+            // Non-primitives should not be passed to this function
+            _ => AVRO_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&self.opts.type_name(key))
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "avsc"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        AVRO_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "type",
+            "record",
+            "name",
+            "fields",
+            "array",
+            "items",
+            "default",
+            "namespace",
+        ]
+    }
+}
+
+impl LanguageFormatter for GraphQL {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let type_name = self.struct_or_class_name(&raw);
+        format!("type {type_name} ") + "{\n"
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::from("}")
+    }
+
+    // Untyped fields are of a custom scalar, which has to be declared
+    fn imports(&self, schema: &Schema) -> Option<String> {
+        schema
+            .type_refs()
+            .any(|type_ref| type_ref.innermost() == &TypeRef::Primitive(LogicalType::Any))
+            .then(|| format!("scalar {GRAPHQL_ANY}\n\n"))
+    }
+
+    // Object types need at least one field, objects without fields are scalars
+    fn format_type(&self, rendered: String) -> String {
+        match rendered.strip_suffix(" {\n}") {
+            Some(header) => header.replacen("type", "scalar", 1),
+            None => rendered,
+        }
+    }
+
+    // Types without fields are scalars, see `format_type`
+    fn defined_type_name(&self, line: &str) -> Option<String> {
+        let rest = ["type ", "scalar "]
+            .into_iter()
+            .find_map(|prefix| line.strip_prefix(prefix))?;
+        let name = rest
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .unwrap_or_default();
+        (!name.is_empty() && name != GRAPHQL_ANY).then(|| name.to_string())
+    }
+
+    // Keys are kept as field names, so that queries return the same JSON
+    fn field_name(&self, json_key: &str) -> String {
+        ascii_identifier(
+            &self
+                .opts
+                .cased_field_name(json_key)
+                .unwrap_or_else(|| String::from(json_key)),
+        )
+    }
+
+    // Fields are non-null unless they were null or missing in a sample
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(GRAPHQL_INDENT);
+        let graphql_field_name = self.field_name(field.name_key);
+        let nullable = field.optional
+            || field.inferred.may_be_missing()
+            || field.type_ref == &TypeRef::Primitive(LogicalType::Any);
+        let mark = if nullable { "" } else { "!" };
+        format!("{indent}{graphql_field_name}: {}{mark}\n", field.type_name)
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        if optional || arr_type == GRAPHQL_ANY {
+            format!("[{arr_type}]")
+        } else {
+            format!("[{arr_type}!]")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => GRAPHQL_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    GRAPHQL_FLOAT
+                } else {
+                    GRAPHQL_INT
+                }
+            }
+            Value::String(_) => GRAPHQL_STRING,
+            Value::Null => GRAPHQL_ANY,
+            // Non-primitives should not be passed to this function
+            _ => GRAPHQL_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&self.opts.type_name(key))
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "#"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "graphql"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        GRAPHQL_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &["type", "scalar"]
+    }
+}
+
+// Haskell's reserved words, fields named like one get an underscore after the name
+static HASKELL_KEYWORDS: &[&str] = &[
+    "case", "class", "data", "default", "deriving", "do", "else", "foreign", "if", "import", "in",
+    "infix", "infixl", "infixr", "instance", "let", "module", "newtype", "of", "then", "type",
+    "where",
+];
+
+// `s` as a Haskell string literal. Control characters are written as their decimal code,
+// followed by \& when a digit comes next.
+pub(crate) fn haskell_string(s: &str) -> String {
+    let mut quoted = String::from('"');
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => {
+                quoted.push_str(&format!("\\{}", c as u32));
+                if chars.peek().is_some_and(char::is_ascii_digit) {
+                    quoted.push_str("\\&");
+                }
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl Haskell {
+    fn is_maybe(&self, field: &FieldContext) -> bool {
+        field.inferred.may_be_missing()
+    }
+}
+
+impl LanguageFormatter for Haskell {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let type_name = self.struct_or_class_name(&raw);
+        format!("data {type_name} = {type_name}\n")
+    }
+
+    // The record is closed by `type_members`
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::new()
+    }
+
+    // Records may have fields named alike, e.g. id, and Aeson is told the keys
+    fn preamble(&self, _schema: &Schema) -> Option<String> {
+        let mut extensions = vec!["DuplicateRecordFields"];
+        if self.opts.annotations {
+            extensions.extend(["DeriveGeneric", "OverloadedStrings"]);
+        }
+        let pragmas: Vec<String> = extensions
+            .iter()
+            .map(|extension| format!("{{-# LANGUAGE {extension} #-}}"))
+            .collect();
+        Some(pragmas.join("\n") + "\n\n")
+    }
+
+    fn imports(&self, schema: &Schema) -> Option<String> {
+        let uses = |logical: LogicalType| {
+            let primitive = TypeRef::Primitive(logical);
+            schema.type_refs().any(|type_ref| {
+                type_name(type_ref.innermost(), self) == type_name(&primitive, self)
+            })
+        };
+        let mut imports = vec![];
+        if self.opts.annotations || uses(LogicalType::Any) {
+            imports.push("import Data.Aeson");
+        }
+        if uses(LogicalType::String) {
+            imports.push("import Data.Text (Text)");
+        }
+        if self.opts.annotations {
+            imports.push("import GHC.Generics (Generic)");
+        }
+        (!imports.is_empty()).then(|| imports.join("\n") + "\n\n")
+    }
+
+    // Closes the record, then with annotations derives Show and Generic and reads it with
+    // Aeson, from the keys as they are
+    fn type_members(&self, raw: String, fields: &[FieldContext]) -> String {
+        let type_name = self.struct_or_class_name(&raw);
+        let indent = self.opts.indent_or(HASKELL_INDENT);
+        let mut members = String::new();
+        if !fields.is_empty() {
+            members.push_str(&format!("{indent}}}\n"));
+        }
+        if !self.opts.annotations {
+            return members;
+        }
+        members.push_str(&format!("{indent}deriving (Show, Generic)\n\n"));
+        members.push_str(&format!("instance FromJSON {type_name} where\n"));
+        let parse = format!(
+            "{indent}parseJSON = withObject {}",
+            haskell_string(&type_name)
+        );
+        if fields.is_empty() {
+            members.push_str(&format!("{parse} $ \\_ -> pure {type_name}\n"));
+            return members;
+        }
+        members.push_str(&format!("{parse} $ \\v ->\n"));
+        members.push_str(&format!("{}{type_name}\n", indent.repeat(2)));
+        for field in fields {
+            let operator = if field.index == 0 { "<$>" } else { "<*>" };
+            let lookup = if self.is_maybe(field) { ".:?" } else { ".:" };
+            members.push_str(&format!(
+                "{}{operator} v {lookup} {}\n",
+                indent.repeat(3),
+                haskell_string(field.json_key)
+            ));
+        }
+        members
+    }
+
+    // Without fields, the record's header is the whole type
+    fn format_type(&self, rendered: String) -> String {
+        rendered.trim_end().to_string()
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("type {name} = {type_name}")
+    }
+
+    // Module names are capitalized, e.g. My.Models for my.models
+    fn namespace(&self, name: &str) -> Namespace {
+        let module: Vec<String> = name.split('.').map(first_char_upper).collect();
+        Namespace {
+            open: format!("module {} where\n\n", module.join(".")),
+            close: String::new(),
+            indent: String::new(),
+        }
+    }
+
+    fn sample_declaration(&self, literal: &Literal) -> Option<String> {
+        let layout = Layout {
+            indent: self.opts.indent_or(HASKELL_INDENT),
+            comment_prefix: self.comment_prefix(),
+        };
+        let type_name = match literal {
+            Literal::Object { type_name, .. } | Literal::Array { type_name, .. } => type_name,
+            _ => return None,
+        };
+        Some(format!(
+            "sample :: {type_name}\nsample =\n{}{}",
+            layout.indent,
+            sample::haskell(literal, &layout, 1)
+        ))
+    }
+
+    // Field names are in camelCase and start with a lowercase letter
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| Case::Camel.apply(json_key));
+        if !name.starts_with(|c: char| c.is_lowercase() || c == '_') || !is_identifier(&name) {
+            format!("field{}", first_char_upper(&name))
+        } else if HASKELL_KEYWORDS.contains(&name.as_str()) {
+            format!("{name}_")
+        } else {
+            name
+        }
+    }
+
+    // With strict nulls, fields that may be missing or null are Maybes
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(HASKELL_INDENT);
+        let haskell_field_name = self.field_name(field.name_key);
+        let open = if field.index == 0 { "{" } else { "," };
+        let field_type = if self.is_maybe(field) {
+            format!("Maybe {}", haskell_argument(field.type_name))
+        } else {
+            field.type_name.to_string()
+        };
+        format!("{indent}{open} {haskell_field_name} :: {field_type}\n")
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        if optional {
+            format!("[Maybe {}]", haskell_argument(&arr_type))
+        } else {
+            format!("[{arr_type}]")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => HASKELL_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    HASKELL_FLOAT
+                } else {
+                    HASKELL_INT
+                }
+            }
+            Value::String(_) => HASKELL_STRING,
+            Value::Null => HASKELL_ANY,
+            // Non-primitives should not be passed to this function
+            _ => HASKELL_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        first_char_upper(&ascii_identifier(&self.opts.type_name(key)))
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "--"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "hs"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        HASKELL_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "data", "type", "deriving", "instance", "where", "module", "import", "Maybe",
+        ]
+    }
+}
+
+// `type_name` as the argument of a type constructor, in parentheses if it's applied itself
+fn haskell_argument(type_name: &str) -> String {
+    if type_name.contains(' ') && !type_name.starts_with('[') {
+        format!("({type_name})")
+    } else {
+        type_name.to_string()
+    }
+}
+
+// Elm's reserved words, fields named like one get an underscore after the name
+static ELM_KEYWORDS: &[&str] = &[
+    "if", "then", "else", "case", "of", "let", "in", "type", "module", "where", "import",
+    "exposing", "as", "port", "alias",
+];
+
+// `s` as an Elm string literal, where other characters are escaped as \u{XXXX}
+pub(crate) fn elm_string(s: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{{{:04X}}}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl Elm {
+    fn is_maybe(&self, field: &FieldContext) -> bool {
+        field.inferred.may_be_missing()
+    }
+
+    // The decoder of the type named `type_name`, e.g. userDecoder for User
+    fn decoder_name(&self, type_name: &str) -> String {
+        format!("{}Decoder", first_char_lower(type_name))
+    }
+
+    // Decodes a value of `type_ref`, in parentheses if it's an application
+    fn decoder(&self, type_ref: &TypeRef) -> String {
+        match type_ref {
+            TypeRef::Primitive(LogicalType::Any) => String::from("Decode.value"),
+            TypeRef::Primitive(_) => format!("Decode.{}", type_name(type_ref, self).to_lowercase()),
+            TypeRef::Object(key) => self.decoder_name(&self.struct_or_class_name(key)),
+            TypeRef::Array {
+                element,
+                optional: true,
+            } => {
+                format!("(Decode.list (Decode.nullable {}))", self.decoder(element))
+            }
+            TypeRef::Array { element, .. } => format!("(Decode.list {})", self.decoder(element)),
+        }
+    }
+}
+
+impl LanguageFormatter for Elm {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let type_name = self.struct_or_class_name(&raw);
+        format!("type alias {type_name} =\n")
+    }
+
+    // The record is closed by `type_members`
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::new()
+    }
+
+    fn imports(&self, schema: &Schema) -> Option<String> {
+        let any = schema
+            .type_refs()
+            .any(|type_ref| type_ref.innermost() == &TypeRef::Primitive(LogicalType::Any));
+        let mut imports = vec![];
+        if self.opts.annotations {
+            imports.push("import Json.Decode as Decode exposing (Decoder)");
+            imports.push("import Json.Decode.Pipeline exposing (optional, required)");
+        } else if any {
+            imports.push("import Json.Decode as Decode");
+        }
+        if any && self.opts.emit_sample {
+            imports.push("import Json.Encode as Encode");
+        }
+        (!imports.is_empty()).then(|| imports.join("\n") + "\n\n\n")
+    }
+
+    // Closes the record, then with annotations decodes it with a pipeline from the keys as
+    // they are
+    fn type_members(&self, raw: String, fields: &[FieldContext]) -> String {
+        let type_name = self.struct_or_class_name(&raw);
+        let indent = self.opts.indent_or(ELM_INDENT);
+        let mut members = if fields.is_empty() {
+            format!("{indent}{{}}\n")
+        } else {
+            format!("{indent}}}\n")
+        };
+        if !self.opts.annotations {
+            return members;
+        }
+        let decoder_name = self.decoder_name(&type_name);
+        members.push_str(&format!("\n\n{decoder_name} : Decoder {type_name}\n"));
+        // Empty records have no constructor
+        let constructor = if fields.is_empty() { "{}" } else { &type_name };
+        members.push_str(&format!(
+            "{decoder_name} =\n{indent}Decode.succeed {constructor}\n"
+        ));
+        for field in fields {
+            let key = elm_string(field.json_key);
+            let decoder = self.decoder(field.type_ref);
+            let step = if self.is_maybe(field) {
+                format!("optional {key} (Decode.nullable {decoder}) Nothing")
+            } else {
+                format!("required {key} {decoder}")
+            };
+            members.push_str(&format!("{}|> {step}\n", indent.repeat(2)));
+        }
+        members
+    }
+
+    fn format_type(&self, rendered: String) -> String {
+        rendered.trim_end().to_string()
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        let indent = self.opts.indent_or(ELM_INDENT);
+        format!("type alias {name} =\n{indent}{type_name}")
+    }
+
+    // Module names are capitalized, e.g. My.Models for my.models
+    fn namespace(&self, name: &str) -> Namespace {
+        let module: Vec<String> = name.split('.').map(first_char_upper).collect();
+        Namespace {
+            open: format!("module {} exposing (..)\n\n", module.join(".")),
+            close: String::new(),
+            indent: String::new(),
+        }
+    }
+
+    fn sample_declaration(&self, literal: &Literal) -> Option<String> {
+        let layout = Layout {
+            indent: self.opts.indent_or(ELM_INDENT),
+            comment_prefix: self.comment_prefix(),
+        };
+        let type_name = match literal {
+            Literal::Object { type_name, .. } | Literal::Array { type_name, .. } => type_name,
+            _ => return None,
+        };
+        Some(format!(
+            "sample : {type_name}\nsample =\n{}{}",
+            layout.indent,
+            sample::elm(literal, &layout, 1)
+        ))
+    }
+
+    // Field names are in camelCase and start with a lowercase letter
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| Case::Camel.apply(json_key));
+        if !name.starts_with(char::is_lowercase) || !is_identifier(&name) {
+            format!("field{}", first_char_upper(&name))
+        } else if ELM_KEYWORDS.contains(&name.as_str()) {
+            format!("{name}_")
+        } else {
+            name
+        }
+    }
+
+    // With strict nulls, fields that may be missing or null are Maybes
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(ELM_INDENT);
+        let elm_field_name = self.field_name(field.name_key);
+        let open = if field.index == 0 { "{" } else { "," };
+        let field_type = if self.is_maybe(field) {
+            format!("Maybe {}", elm_argument(field.type_name))
+        } else {
+            field.type_name.to_string()
+        };
+        format!("{indent}{open} {elm_field_name} : {field_type}\n")
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        if optional {
+            format!("List (Maybe {})", elm_argument(&arr_type))
+        } else {
+            format!("List {}", elm_argument(&arr_type))
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => ELM_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    ELM_FLOAT
+                } else {
+                    ELM_INT
+                }
+            }
+            Value::String(_) => ELM_STRING,
+            Value::Null => ELM_ANY,
+            // Non-primitives should not be passed to this function
+            _ => ELM_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        first_char_upper(&ascii_identifier(&self.opts.type_name(key)))
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "--"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "elm"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        ELM_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "type", "alias", "module", "exposing", "import", "as", "List", "Maybe", "Decoder",
+        ]
+    }
+}
+
+// `type_name` as the argument of a type constructor, in parentheses if it's applied itself
+fn elm_argument(type_name: &str) -> String {
+    if type_name.contains(' ') {
+        format!("({type_name})")
+    } else {
+        type_name.to_string()
+    }
+}
+
+// OCaml's reserved words, names like one get an underscore after them
+static OCAML_KEYWORDS: &[&str] = &[
+    "and",
+    "as",
+    "assert",
+    "begin",
+    "class",
+    "constraint",
+    "do",
+    "done",
+    "downto",
+    "else",
+    "end",
+    "exception",
+    "external",
+    "false",
+    "for",
+    "fun",
+    "function",
+    "functor",
+    "if",
+    "in",
+    "include",
+    "inherit",
+    "initializer",
+    "lazy",
+    "let",
+    "match",
+    "method",
+    "module",
+    "mutable",
+    "new",
+    "nonrec",
+    "object",
+    "of",
+    "open",
+    "or",
+    "private",
+    "rec",
+    "sig",
+    "struct",
+    "then",
+    "to",
+    "true",
+    "try",
+    "type",
+    "val",
+    "virtual",
+    "when",
+    "while",
+    "with",
+];
+
+// `name` in snake_case, the way OCaml names both types and fields, which start with a
+// lowercase letter or _
+fn ocaml_identifier(name: &str) -> String {
+    let name = ascii_identifier(&Case::Snake.apply(name)).to_lowercase();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{name}")
+    } else if OCAML_KEYWORDS.contains(&name.as_str()) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+// `s` as an OCaml string literal, where other control characters are escaped as \DDD
+pub(crate) fn ocaml_string(s: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() && (c as u32) < 256 => {
+                quoted.push_str(&format!("\\{:03}", c as u32))
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl OCaml {
+    fn deriving(&self) -> &'static str {
+        if self.opts.annotations {
+            " [@@deriving yojson]"
+        } else {
+            ""
+        }
+    }
+}
+
+impl LanguageFormatter for OCaml {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let type_name = self.struct_or_class_name(&raw);
+        format!("type {type_name} = ") + "{\n"
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        format!("}}{}", self.deriving())
+    }
+
+    // Records need at least one field, objects without fields are kept as JSON
+    fn format_type(&self, rendered: String) -> String {
+        match rendered.split_once(" = {\n}") {
+            Some((header, deriving)) => format!("{header} = {OCAML_ANY}{deriving}"),
+            None => rendered,
+        }
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("type {name} = {type_name}{}", self.deriving())
+    }
+
+    fn namespace(&self, name: &str) -> Namespace {
+        let module = first_char_upper(&ocaml_identifier(name));
+        Namespace {
+            open: format!("module {module} = struct\n"),
+            close: String::from("end"),
+            indent: self.opts.indent_or(OCAML_INDENT).to_string(),
+        }
+    }
+
+    fn sample_declaration(&self, literal: &Literal) -> Option<String> {
+        let layout = Layout {
+            indent: self.opts.indent_or(OCAML_INDENT),
+            comment_prefix: self.comment_prefix(),
+        };
+        let type_name = match literal {
+            Literal::Object { type_name, .. } | Literal::Array { type_name, .. } => type_name,
+            _ => return None,
+        };
+        Some(format!(
+            "let sample : {type_name} =\n{}{}",
+            layout.indent,
+            sample::ocaml(literal, &layout, 1)
+        ))
+    }
+
+    fn field_name(&self, json_key: &str) -> String {
+        match self.opts.cased_field_name(json_key) {
+            Some(name) => ocaml_identifier(&name),
+            None => ocaml_identifier(json_key),
+        }
+    }
+
+    // Fields that may be null or missing are options, and the ones that may be missing
+    // default to None. Keys that aren't the field's name are given.
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(OCAML_INDENT);
+        let ocaml_field_name = self.field_name(field.name_key);
+        let mut field_type = if field.inferred.may_be_missing() {
+            format!("{} option", field.type_name)
+        } else {
+            field.type_name.to_string()
+        };
+        if self.opts.annotations {
+            if ocaml_field_name != field.json_key {
+                field_type.push_str(&format!(" [@key {}]", ocaml_string(field.json_key)));
+            }
+            if field.inferred.may_be_absent(self.opts.strict_null) {
+                field_type.push_str(" [@default None]");
+            }
+        }
+        format!("{indent}{ocaml_field_name} : {field_type};\n")
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        if optional {
+            format!("{arr_type} option list")
+        } else {
+            format!("{arr_type} list")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => OCAML_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    OCAML_FLOAT
+                } else {
+                    OCAML_INT
+                }
+            }
+            Value::String(_) => OCAML_STRING,
+            Value::Null => OCAML_ANY,
+            // Non-primitives should not be passed to this function
+            _ => OCAML_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ocaml_identifier(&self.opts.type_name(key))
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "(*"
+    }
+
+    fn comment_suffix(&self) -> &'static str {
+        "*)"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "ml"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        OCAML_ROOT
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &["type", "module", "struct", "end", "let", "option", "list"]
+    }
+}
+
+// F#'s reserved words, names like one are put in double backticks
+static FSHARP_KEYWORDS: &[&str] = &[
+    "abstract",
+    "and",
+    "as",
+    "assert",
+    "base",
+    "begin",
+    "class",
+    "default",
+    "delegate",
+    "do",
+    "done",
+    "downcast",
+    "downto",
+    "elif",
+    "else",
+    "end",
+    "exception",
+    "extern",
+    "false",
+    "finally",
+    "fixed",
+    "for",
+    "fun",
+    "function",
+    "global",
+    "if",
+    "in",
+    "inherit",
+    "inline",
+    "interface",
+    "internal",
+    "lazy",
+    "let",
+    "match",
+    "member",
+    "module",
+    "mutable",
+    "namespace",
+    "new",
+    "not",
+    "null",
+    "of",
+    "open",
+    "or",
+    "override",
+    "private",
+    "public",
+    "rec",
+    "return",
+    "select",
+    "static",
+    "struct",
+    "then",
+    "to",
+    "true",
+    "try",
+    "type",
+    "upcast",
+    "use",
+    "val",
+    "void",
+    "when",
+    "while",
+    "with",
+    "yield",
+];
+
+impl LanguageFormatter for FSharp {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let type_name = self.struct_or_class_name(&raw);
+        let indent = self.opts.indent_or(FSHARP_INDENT);
+        format!("type {type_name} =\n{indent}") + "{\n"
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        format!("{}}}", self.opts.indent_or(FSHARP_INDENT))
+    }
+
+    // Untyped fields are JSON elements, and keys are mapped with System.Text.Json
+    fn imports(&self, schema: &Schema) -> Option<String> {
+        let mut imports = vec![];
+        if schema
+            .type_refs()
+            .any(|type_ref| type_ref.innermost() == &TypeRef::Primitive(LogicalType::Any))
+        {
+            imports.push("open System.Text.Json");
+        }
+        if self.opts.annotations {
+            imports.push("open System.Text.Json.Serialization");
+        }
+        (!imports.is_empty()).then(|| imports.join("\n") + "\n\n")
+    }
+
+    // Records need at least one field, types without fields are empty classes
+    fn format_type(&self, rendered: String) -> String {
+        let indent = self.opts.indent_or(FSHARP_INDENT);
+        match rendered.split_once(&format!(" =\n{indent}{{\n{indent}}}")) {
+            Some((header, _)) => format!("{header}() =\n{indent}class end"),
+            None => rendered,
+        }
+    }
+
+    // Types without fields are classes, see `format_type`
+    fn defined_type_name(&self, line: &str) -> Option<String> {
+        let name = line
+            .strip_prefix("type ")?
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .unwrap_or_default();
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("type {name} = {type_name}")
+    }
+
+    fn namespace(&self, name: &str) -> Namespace {
+        Namespace {
+            open: format!("namespace {name}\n\n"),
+            close: String::new(),
+            indent: String::new(),
+        }
+    }
+
+    fn sample_declaration(&self, literal: &Literal) -> Option<String> {
+        let layout = Layout {
+            indent: self.opts.indent_or(FSHARP_INDENT),
+            comment_prefix: self.comment_prefix(),
+        };
+        let type_name = match literal {
+            Literal::Object { type_name, .. } | Literal::Array { type_name, .. } => type_name,
+            _ => return None,
+        };
+        Some(format!(
+            "let sample: {type_name} =\n{}{}",
+            layout.indent,
+            sample::fsharp(literal, &layout, 1)
+        ))
+    }
+
+    // Record fields are in PascalCase, names that aren't identifiers are put in double
+    // backticks
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| Case::Pascal.apply(json_key));
+        if !is_identifier(&name) || FSHARP_KEYWORDS.contains(&name.as_str()) {
+            format!("``{}``", name.replace('`', ""))
+        } else {
+            name
+        }
+    }
+
+    // Fields that may be missing or null are options
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(FSHARP_INDENT).repeat(2);
+        let fsharp_field_name = self.field_name(field.name_key);
+        let field_type = if field.inferred.may_be_missing() {
+            format!("{} option", field.type_name)
+        } else {
+            field.type_name.to_string()
+        };
+        let line = format!("{indent}{fsharp_field_name}: {field_type}\n");
+        if self.opts.annotations && fsharp_field_name != field.json_key {
+            format!(
+                "{indent}[<JsonPropertyName({})>]\n{line}",
+                quote(field.json_key)
+            )
+        } else {
+            line
+        }
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        if optional {
+            format!("{arr_type} option list")
+        } else {
+            format!("{arr_type} list")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => FSHARP_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    FSHARP_FLOAT
+                } else {
+                    FSHARP_INT
+                }
+            }
+            Value::String(_) => FSHARP_STRING,
+            Value::Null => FSHARP_ANY,
+            // Non-primitives should not be passed to this function
+            _ => FSHARP_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        first_char_upper(&ascii_identifier(&self.opts.type_name(key)))
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "fs"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        FSHARP_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "type",
+            "namespace",
+            "open",
+            "let",
+            "option",
+            "list",
+            "class",
+            "end",
+        ]
+    }
+}
+
+// Zig's keywords and primitive names, fields named like one are written @"name"
+static ZIG_KEYWORDS: &[&str] = &[
+    "addrspace",
+    "align",
+    "allowzero",
+    "and",
+    "anyframe",
+    "anytype",
+    "asm",
+    "async",
+    "await",
+    "break",
+    "callconv",
+    "catch",
+    "comptime",
+    "const",
+    "continue",
+    "defer",
+    "else",
+    "enum",
+    "errdefer",
+    "error",
+    "export",
+    "extern",
+    "fn",
+    "for",
+    "if",
+    "inline",
+    "linksection",
+    "noalias",
+    "noinline",
+    "nosuspend",
+    "opaque",
+    "or",
+    "orelse",
+    "packed",
+    "pub",
+    "resume",
+    "return",
+    "struct",
+    "suspend",
+    "switch",
+    "test",
+    "threadlocal",
+    "try",
+    "union",
+    "unreachable",
+    "usingnamespace",
+    "var",
+    "volatile",
+    "while",
+    "anyerror",
+    "bool",
+    "false",
+    "noreturn",
+    "null",
+    "true",
+    "type",
+    "undefined",
+    "void",
+];
+
+// `s` as a Zig string literal, where other control characters are escaped as \xNN
+pub(crate) fn zig_string(s: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl LanguageFormatter for Zig {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let struct_name = self.struct_or_class_name(&raw);
+        format!("pub const {struct_name} = struct ") + "{\n"
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::from("};")
+    }
+
+    // zig fmt puts structs without fields on one line
+    fn format_type(&self, rendered: String) -> String {
+        match rendered.strip_suffix("{\n};") {
+            Some(header) => format!("{header}{{}};"),
+            None => rendered,
+        }
+    }
+
+    // Untyped fields are std.json.Values
+    fn imports(&self, schema: &Schema) -> Option<String> {
+        schema
+            .type_refs()
+            .any(|type_ref| type_ref.innermost() == &TypeRef::Primitive(LogicalType::Any))
+            .then(|| String::from("const std = @import(\"std\");\n\n"))
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("pub const {name} = {type_name};")
+    }
+
+    fn namespace(&self, name: &str) -> Namespace {
+        Namespace {
+            open: format!("pub const {} = struct ", ascii_identifier(name)) + "{\n",
+            close: String::from("};"),
+            indent: self.opts.indent_or(ZIG_INDENT).to_string(),
+        }
+    }
+
+    fn sample_declaration(&self, literal: &Literal) -> Option<String> {
+        let layout = Layout {
+            indent: self.opts.indent_or(ZIG_INDENT),
+            comment_prefix: self.comment_prefix(),
+        };
+        Some(format!(
+            "pub const sample = {};",
+            sample::zig(literal, &layout, 0)
+        ))
+    }
+
+    // std.json reads fields from the keys named like them, so keys are kept as they are,
+    // as @"key" if they aren't identifiers
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| String::from(json_key));
+        let identifier = name.is_ascii() && is_identifier(&name);
+        if !identifier || ZIG_KEYWORDS.contains(&name.as_str()) {
+            format!("@{}", zig_string(&name))
+        } else {
+            name
+        }
+    }
+
+    // Any key but "" can be written @"key"
+    fn nameable_key(&self, json_key: &str) -> bool {
+        !json_key.is_empty()
+    }
+
+    // Fields that may be null or missing are optional, and the ones that may be missing
+    // default to null
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(ZIG_INDENT);
+        let zig_field_name = self.field_name(field.name_key);
+        let mark = if field.inferred.may_be_missing() {
+            "?"
+        } else {
+            ""
+        };
+        let default = if field.inferred.may_be_absent(self.opts.strict_null) {
+            " = null"
+        } else {
+            ""
+        };
+        format!(
+            "{indent}{zig_field_name}: {mark}{}{default},\n",
+            field.type_name
+        )
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        if optional {
+            format!("[]const ?{arr_type}")
+        } else {
+            format!("[]const {arr_type}")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => ZIG_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    ZIG_FLOAT
+                } else {
+                    ZIG_INT
+                }
+            }
+            Value::String(_) => ZIG_STRING,
+            Value::Null => ZIG_ANY,
+            // Non-primitives should not be passed to this function
+            _ => ZIG_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&self.opts.type_name(key))
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "zig"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        ZIG_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &["pub", "const", "struct", "null", "bool"]
+    }
+}
+
+// C's reserved words, fields named like one get an underscore after the name
+static C_KEYWORDS: &[&str] = &[
+    "auto", "bool", "break", "case", "char", "const", "continue", "default", "do", "double",
+    "else", "enum", "extern", "float", "for", "goto", "if", "inline", "int", "long", "register",
+    "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+    "union", "unsigned", "void", "volatile", "while",
+];
+
+// `name` declared as a `type_name`, pointers have the * next to the name
+fn c_declaration(type_name: &str, name: &str) -> String {
+    if type_name.ends_with('*') {
+        format!("{type_name}{name}")
+    } else {
+        format!("{type_name} {name}")
+    }
+}
+
+impl LanguageFormatter for C {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let struct_name = self.struct_or_class_name(&raw);
+        format!("typedef struct {struct_name} ") + "{\n"
+    }
+
+    fn struct_or_class_footer(&self, struct_name: Option<String>) -> String {
+        let struct_name = struct_name
+            .map(|key| self.struct_or_class_name(&key))
+            .unwrap_or_default();
+        format!("}} {struct_name};")
+    }
+
+    fn preamble(&self, _schema: &Schema) -> Option<String> {
+        Some(String::from(
+            "#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n\n",
+        ))
+    }
+
+    // Every struct is declared before any is defined, so that they can point to each
+    // other in any order. C11 allows the typedef to be repeated by the definition.
+    fn imports(&self, schema: &Schema) -> Option<String> {
+        let declarations: Vec<String> = schema
+            .types
+            .iter()
+            .map(|type_def| {
+                let struct_name = self.struct_or_class_name(&type_def.key);
+                format!("typedef struct {struct_name} {struct_name};\n")
+            })
+            .collect();
+        (!declarations.is_empty()).then(|| declarations.concat() + "\n")
+    }
+
+    // Structs need at least one member
+    fn type_members(&self, _raw: String, fields: &[FieldContext]) -> String {
+        if fields.is_empty() {
+            let indent = self.opts.indent_or(C_INDENT);
+            format!("{indent}char unused;\n")
+        } else {
+            String::new()
+        }
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("typedef {};", c_declaration(type_name, name))
+    }
+
+    // Struct definitions and aliases, not the declarations before them
+    fn defined_type_name(&self, line: &str) -> Option<String> {
+        let rest = line.strip_prefix("typedef ")?;
+        let name = if let Some(rest) = rest.strip_prefix("struct ") {
+            rest.strip_suffix(" {")?
+        } else {
+            rest.strip_suffix(';')?.rsplit([' ', '*']).next()?
+        };
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    // Fields are in snake_case
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| Case::Snake.apply(json_key));
+        let name = ascii_identifier(&name);
+        if C_KEYWORDS.contains(&name.as_str()) {
+            format!("{name}_")
+        } else {
+            name
+        }
+    }
+
+    // Arrays are pointers followed by their length. Fields that may be missing or null are
+    // pointers too, NULL when they are.
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(C_INDENT);
+        let c_field_name = self.field_name(field.name_key);
+        let pointer = field.inferred.may_be_missing() && !field.type_name.ends_with('*');
+        let field_type = if pointer {
+            format!("{} *", field.type_name)
+        } else {
+            field.type_name.to_string()
+        };
+        let mut declaration = format!("{indent}{};\n", c_declaration(&field_type, &c_field_name));
+        if field.array_depth() > 0 {
+            declaration.push_str(&format!("{indent}size_t {c_field_name}_count;\n"));
+        }
+        declaration
+    }
+
+    // Elements that may be null are pointers
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        let arr_type = if optional && !arr_type.ends_with('*') {
+            format!("{arr_type} *")
+        } else {
+            arr_type
+        };
+        if arr_type.ends_with('*') {
+            format!("{arr_type}*")
+        } else {
+            format!("{arr_type} *")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => C_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    C_FLOAT
+                } else {
+                    C_INT
+                }
+            }
+            Value::String(_) => C_STRING,
+            Value::Null => C_ANY,
+            // Non-primitives should not be passed to this function
+            _ => C_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&self.opts.type_name(key))
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "h"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        C_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "struct", "typedef", "char", "double", "bool", "void", "size_t", "int64_t",
+        ]
+    }
+}
+
+// C++'s reserved words, fields named like one get an underscore after the name
+static CPP_KEYWORDS: &[&str] = &[
+    "alignas",
+    "alignof",
+    "and",
+    "asm",
+    "auto",
+    "bool",
+    "break",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "constexpr",
+    "continue",
+    "decltype",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "explicit",
+    "export",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "friend",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "mutable",
+    "namespace",
+    "new",
+    "noexcept",
+    "not",
+    "nullptr",
+    "operator",
+    "or",
+    "private",
+    "protected",
+    "public",
+    "register",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "typeid",
+    "typename",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "while",
+    "xor",
+];
+
+// `s` as a C++ string literal. Other ASCII control characters are escaped as three octal digits,
+// which unlike hex escapes can't run into the characters after them.
+pub(crate) fn cpp_string(s: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_ascii_control() => quoted.push_str(&format!("\\{:03o}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl Cpp {
+    // nlohmann/json doesn't read or write std::optional, until told to as null or the value
+    fn optional_serializer(&self) -> String {
+        let indent = self.opts.indent_or(CPP_INDENT);
+        let lines = [
+            "namespace nlohmann {",
+            "template <typename T>",
+            "struct adl_serializer<std::optional<T>> {",
+            "\tstatic void to_json(json &j, const std::optional<T> &value) {",
+            "\t\tif (value) {",
+            "\t\t\tj = *value;",
+            "\t\t} else {",
+            "\t\t\tj = nullptr;",
+            "\t\t}",
+            "\t}",
+            "",
+            "\tstatic void from_json(const json &j, std::optional<T> &value) {",
+            "\t\tif (j.is_null()) {",
+            "\t\t\tvalue = std::nullopt;",
+            "\t\t} else {",
+            "\t\t\tvalue = j.get<T>();",
+            "\t\t}",
+            "\t}",
+            "};",
+            "} // namespace nlohmann",
+        ];
+        lines
+            .iter()
+            .map(|line| line.replace('\t', indent) + "\n")
+            .collect()
+    }
+
+    // Fields that may be null or missing are std::optionals
+    fn is_optional(&self, field: &FieldContext) -> bool {
+        field.inferred.may_be_missing()
+    }
+
+    // Reading and writing the type with nlohmann/json. The macros name the keys after the
+    // fields, so types with fields named other than their key get the functions written out.
+    fn json_functions(&self, type_name: &str, fields: &[FieldContext]) -> String {
+        let indent = self.opts.indent_or(CPP_INDENT);
+        let names: Vec<String> = fields
+            .iter()
+            .map(|field| self.field_name(field.name_key))
+            .collect();
+        let keys_as_names = fields
+            .iter()
+            .zip(&names)
+            .all(|(field, name)| field.json_key == name);
+        if !fields.is_empty() && keys_as_names {
+            // Fields missing from the JSON are left as they are by the _WITH_DEFAULT macro
+            let with_default = if fields.iter().any(|field| field.absent) {
+                "_WITH_DEFAULT"
+            } else {
+                ""
+            };
+            return format!(
+                "NLOHMANN_DEFINE_TYPE_NON_INTRUSIVE{with_default}({type_name}, {})\n",
+                names.join(", ")
+            );
+        }
+        if fields.is_empty() {
+            return format!(
+                "inline void to_json(nlohmann::json &j, const {type_name} &) {{\n\
+                 {indent}j = nlohmann::json::object();\n}}\n\n\
+                 inline void from_json(const nlohmann::json &, {type_name} &) {{}}\n"
+            );
+        }
+        let pairs: Vec<String> = fields
+            .iter()
+            .zip(&names)
+            .map(|(field, name)| format!("{{{}, x.{name}}}", cpp_string(field.json_key)))
+            .collect();
+        let mut functions =
+            format!("inline void to_json(nlohmann::json &j, const {type_name} &x) {{\n");
+        functions.push_str(&format!("{indent}j = nlohmann::json{{\n"));
+        for pair in pairs {
+            functions.push_str(&format!("{}{pair},\n", indent.repeat(2)));
+        }
+        functions.push_str(&format!("{indent}}};\n}}\n\n"));
+        functions.push_str(&format!(
+            "inline void from_json(const nlohmann::json &j, {type_name} &x) {{\n"
+        ));
+        for (field, name) in fields.iter().zip(&names) {
+            let key = cpp_string(field.json_key);
+            if field.absent {
+                functions.push_str(&format!("{indent}x.{name} = j.value({key}, x.{name});\n"));
+            } else {
+                functions.push_str(&format!("{indent}j.at({key}).get_to(x.{name});\n"));
+            }
+        }
+        functions.push_str("}\n");
+        functions
+    }
+}
+
+impl LanguageFormatter for Cpp {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let struct_name = self.struct_or_class_name(&raw);
+        format!("struct {struct_name} ") + "{\n"
+    }
+
+    // The struct is closed by `type_members`
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::new()
+    }
+
+    fn preamble(&self, schema: &Schema) -> Option<String> {
+        let uses = |logical: LogicalType| {
+            let primitive = TypeRef::Primitive(logical);
+            schema.type_refs().any(|type_ref| {
+                type_name(type_ref.innermost(), self) == type_name(&primitive, self)
+            })
+        };
+        let fields = || schema.types.iter().flat_map(|type_def| &type_def.fields);
+        let optional_fields = fields().any(Field::may_be_missing);
+        let optional_elements = schema
+            .type_refs()
+            .any(|type_ref| matches!(type_ref, TypeRef::Array { optional: true, .. }));
+        let mut includes = vec![];
+        if uses(LogicalType::Int) {
+            includes.push("<cstdint>");
+        }
+        if self.opts.annotations || uses(LogicalType::Any) {
+            includes.push("<nlohmann/json.hpp>");
+        }
+        if optional_fields || optional_elements {
+            includes.push("<optional>");
+        }
+        if uses(LogicalType::String) {
+            includes.push("<string>");
+        }
+        if schema
+            .type_refs()
+            .any(|type_ref| type_ref.array_depth() > 0)
+        {
+            includes.push("<vector>");
+        }
+        let mut preamble: String = includes
+            .iter()
+            .map(|include| format!("#include {include}\n"))
+            .collect();
+        if self.opts.annotations && (optional_fields || optional_elements) {
+            preamble.push('\n');
+            preamble.push_str(&self.optional_serializer());
+        }
+        (!preamble.is_empty()).then(|| preamble + "\n")
+    }
+
+    // Closes the struct, then with annotations adds its nlohmann/json functions
+    fn type_members(&self, raw: String, fields: &[FieldContext]) -> String {
+        let mut members = String::from("};\n");
+        if self.opts.annotations {
+            let type_name = self.struct_or_class_name(&raw);
+            members.push('\n');
+            members.push_str(&self.json_functions(&type_name, fields));
+        }
+        members
+    }
+
+    // Structs without fields are on one line
+    fn format_type(&self, rendered: String) -> String {
+        rendered.replacen("{\n};", "{};", 1).trim_end().to_string()
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("using {name} = {type_name};")
+    }
+
+    fn namespace(&self, name: &str) -> Namespace {
+        let name = name
+            .split(['.', ':'])
+            .filter(|part| !part.is_empty())
+            .map(ascii_identifier)
+            .collect::<Vec<_>>()
+            .join("::");
+        Namespace {
+            open: format!("namespace {name} ") + "{\n\n",
+            close: format!("\n}} // namespace {name}"),
+            indent: String::new(),
+        }
+    }
+
+    // Fields are named after the keys, when they're identifiers, so that the macros can
+    // read and write them
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| String::from(json_key));
+        let name = ascii_identifier(&name);
+        if CPP_KEYWORDS.contains(&name.as_str()) {
+            format!("{name}_")
+        } else {
+            name
+        }
+    }
+
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(CPP_INDENT);
+        let cpp_field_name = self.field_name(field.name_key);
+        if self.is_optional(field) {
+            format!(
+                "{indent}std::optional<{}> {cpp_field_name};\n",
+                field.type_name
+            )
+        } else {
+            format!("{indent}{} {cpp_field_name};\n", field.type_name)
+        }
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        if optional {
+            format!("std::vector<std::optional<{arr_type}>>")
+        } else {
+            format!("std::vector<{arr_type}>")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => CPP_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    CPP_FLOAT
+                } else {
+                    CPP_INT
+                }
+            }
+            Value::String(_) => CPP_STRING,
+            Value::Null => CPP_ANY,
+            // Non-primitives should not be passed to this function
+            _ => CPP_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&self.opts.type_name(key))
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "hpp"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        CPP_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "struct",
+            "using",
+            "namespace",
+            "inline",
+            "void",
+            "const",
+            "bool",
+            "double",
+        ]
+    }
+}
+
+// Names PHP reserves, which classes can't be named in any case
+static PHP_RESERVED: &[&str] = &[
+    "abstract",
+    "and",
+    "array",
+    "as",
+    "bool",
+    "break",
+    "callable",
+    "case",
+    "catch",
+    "class",
+    "clone",
+    "const",
+    "continue",
+    "declare",
+    "default",
+    "do",
+    "echo",
+    "else",
+    "elseif",
+    "empty",
+    "enddeclare",
+    "endfor",
+    "endforeach",
+    "endif",
+    "endswitch",
+    "endwhile",
+    "enum",
+    "eval",
+    "exit",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "fn",
+    "for",
+    "foreach",
+    "function",
+    "global",
+    "goto",
+    "if",
+    "implements",
+    "include",
+    "include_once",
+    "instanceof",
+    "insteadof",
+    "int",
+    "interface",
+    "isset",
+    "iterable",
+    "list",
+    "match",
+    "mixed",
+    "namespace",
+    "never",
+    "new",
+    "null",
+    "object",
+    "or",
+    "parent",
+    "print",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "require",
+    "require_once",
+    "return",
+    "self",
+    "static",
+    "string",
+    "switch",
+    "throw",
+    "trait",
+    "true",
+    "try",
+    "unset",
+    "use",
+    "var",
+    "void",
+    "while",
+    "xor",
+    "yield",
+];
+
+// `s` as a single-quoted PHP string, where only quotes and backslashes are escaped, unless it
+// has control characters. Only double-quoted strings have escapes for those, and $ is escaped
+// in them.
+pub(crate) fn php_string(s: &str) -> String {
+    if !s.contains(char::is_control) {
+        return format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'"));
+    }
+    let mut quoted = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '$' => quoted.push_str("\\$"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl Php {
+    // PHP's arrays aren't typed, their elements are given by a docblock
+    fn native_type<'a>(&self, type_name: &'a str) -> &'a str {
+        if type_name.starts_with("list<") {
+            "array"
+        } else {
+            type_name
+        }
+    }
+
+    fn is_nullable(&self, field: &FieldContext) -> bool {
+        field.inferred.may_be_missing()
+    }
+}
+
+impl LanguageFormatter for Php {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let class_name = self.struct_or_class_name(&raw);
+        format!("final class {class_name}\n") + "{\n"
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::from("}")
+    }
+
+    fn preamble(&self, _schema: &Schema) -> Option<String> {
+        Some(String::from("<?php\n\ndeclare(strict_types=1);\n\n"))
+    }
+
+    // Symfony's serializer is told the keys of fields named other than them
+    fn imports(&self, schema: &Schema) -> Option<String> {
+        let renamed = schema.types.iter().any(|type_def| {
+            type_def
+                .fields
+                .iter()
+                .zip(unique_name_keys(type_def, self))
+                .any(|(field, name_key)| self.field_name(&name_key) != field.key)
+        });
+        (self.opts.annotations && renamed).then(|| {
+            String::from("use Symfony\\Component\\Serializer\\Attribute\\SerializedName;\n\n")
+        })
+    }
+
+    // The fields are the constructor's promoted parameters, the constructor is closed here
+    fn type_members(&self, _raw: String, fields: &[FieldContext]) -> String {
+        if fields.is_empty() {
+            return String::new();
+        }
+        let indent = self.opts.indent_or(PHP_INDENT);
+        format!("{indent}) {{\n{indent}}}\n")
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        self.comment(&format!("{name} is a {type_name}"))
+    }
+
+    fn namespace(&self, name: &str) -> Namespace {
+        let name = name
+            .split(['.', ':', '\\'])
+            .filter(|part| !part.is_empty())
+            .map(|part| ascii_identifier(&first_char_upper(part)))
+            .collect::<Vec<_>>()
+            .join("\\");
+        Namespace {
+            open: format!("namespace {name};\n\n"),
+            close: String::new(),
+            indent: String::new(),
+        }
+    }
+
+    // Properties are in camelCase, as PSR-12 code usually has them
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| Case::Camel.apply(json_key));
+        let name = ascii_identifier(&name);
+        if name == "this" {
+            format!("{name}_")
+        } else {
+            name
+        }
+    }
+
+    // Promoted readonly properties, with the type of array elements in a docblock. The
+    // first one opens the constructor.
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(PHP_INDENT);
+        let php_field_name = self.field_name(field.name_key);
+        let mut parameter = String::new();
+        if field.index == 0 {
+            parameter.push_str(&format!("{indent}public function __construct(\n"));
+        }
+        let two = indent.repeat(2);
+        let native_type = self.native_type(field.type_name);
+        let nullable = self.is_nullable(field);
+        if native_type != field.type_name {
+            let doc_type = if nullable {
+                format!("{}|null", field.type_name)
+            } else {
+                field.type_name.to_string()
+            };
+            parameter.push_str(&format!("{two}/** @var {doc_type} */\n"));
+        }
+        if self.opts.annotations && php_field_name != field.json_key {
+            parameter.push_str(&format!(
+                "{two}#[SerializedName({})]\n",
+                php_string(field.json_key)
+            ));
+        }
+        let mark = if nullable { "?" } else { "" };
+        parameter.push_str(&format!(
+            "{two}public readonly {mark}{native_type} ${php_field_name},\n"
+        ));
+        parameter
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        if optional && arr_type != PHP_ANY {
+            format!("list<?{arr_type}>")
+        } else {
+            format!("list<{arr_type}>")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => PHP_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    PHP_FLOAT
+                } else {
+                    PHP_INT
+                }
+            }
+            Value::String(_) => PHP_STRING,
+            Value::Null => PHP_ANY,
+            // Non-primitives should not be passed to this function
+            _ => PHP_ANY,
+        }
+    }
+
+    // Classes named like a reserved word, in any case, get an underscore after the name
+    fn struct_or_class_name(&self, key: &str) -> String {
+        let name = ascii_identifier(&self.opts.type_name(key));
+        if PHP_RESERVED.contains(&name.to_lowercase().as_str()) {
+            format!("{name}_")
+        } else {
+            name
+        }
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "php"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        PHP_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "final",
+            "class",
+            "public",
+            "function",
+            "readonly",
+            "namespace",
+            "use",
+            "declare",
+        ]
+    }
+}
+
+impl Elixir {
+    // Each type is nested in the module of the first type using it, reached from the root's
+    // fields in order
+    fn nest(
+        &self,
+        key: &str,
+        parent: &str,
+        schema: &Schema,
+        modules: &mut HashMap<String, (String, String)>,
+        children: &mut HashMap<String, Vec<String>>,
+    ) {
+        let name = self.opts.type_name(key);
+        let name = ascii_identifier(&first_char_upper(&name));
+        let full_name = if parent.is_empty() {
+            name.clone()
+        } else {
+            format!("{parent}.{name}")
+        };
+        modules.insert(key.to_string(), (name, full_name.clone()));
+        let Some(type_def) = schema.types.iter().find(|type_def| type_def.key == key) else {
+            return;
+        };
+        for field in &type_def.fields {
+            if let TypeRef::Object(child) = field.type_ref.innermost() {
+                if !modules.contains_key(child) {
+                    children
+                        .entry(key.to_string())
+                        .or_default()
+                        .push(child.clone());
+                    self.nest(child, &full_name, schema, modules, children);
+                }
+            }
+        }
+    }
+
+    // The module of the type named after `key`, with those nested in it after its header
+    fn render_module(
+        &self,
+        key: &str,
+        schema: &Schema,
+        children: &HashMap<String, Vec<String>>,
+    ) -> String {
+        let Some(type_def) = schema.types.iter().find(|type_def| type_def.key == key) else {
+            return String::new();
+        };
+        let indent = self.opts.indent_or(ELIXIR_INDENT);
+        let module = render_type(type_def, self);
+        let Some((header, body)) = module.split_once('\n') else {
+            return module;
+        };
+        let nested: String = children
+            .get(key)
+            .into_iter()
+            .flatten()
+            .map(|child| {
+                let child_module: Vec<String> = self
+                    .render_module(child, schema, children)
+                    .lines()
+                    .map(|line| {
+                        if line.is_empty() {
+                            String::new()
+                        } else {
+                            format!("{indent}{line}")
+                        }
+                    })
+                    .collect();
+                child_module.join("\n") + "\n\n"
+            })
+            .collect();
+        format!("{header}\n{nested}{body}")
+    }
+
+    // The typespec of a type, generated types by their t
+    fn spec(&self, type_name: &str) -> String {
+        if type_name.ends_with(')') {
+            type_name.to_string()
+        } else {
+            format!("{type_name}.t()")
+        }
+    }
+}
+
+impl LanguageFormatter for Elixir {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let module_name = match self.modules.get(&raw) {
+            Some((name, _)) => name.clone(),
+            None => self.struct_or_class_name(&raw),
+        };
+        let indent = self.opts.indent_or(ELIXIR_INDENT);
+        format!("defmodule {module_name} do\n{indent}@type t :: %__MODULE__") + "{\n"
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::from("end")
+    }
+
+    // Modules are nested in the modules of the types using them, so the document is
+    // rendered from the root down
+    fn render_schema(&self, schema: &Schema) -> Option<String> {
+        let prefix = self
+            .opts
+            .namespace
+            .iter()
+            .flat_map(|name| name.split(['.', ':']))
+            .filter(|part| !part.is_empty())
+            .map(|part| ascii_identifier(&first_char_upper(part)))
+            .collect::<Vec<_>>()
+            .join(".");
+        let mut modules = HashMap::new();
+        let mut children = HashMap::new();
+        let mut roots = vec![];
+        for type_def in schema.types.iter().rev() {
+            if !modules.contains_key(&type_def.key) {
+                roots.push(type_def.key.clone());
+                self.nest(&type_def.key, &prefix, schema, &mut modules, &mut children);
+            }
+        }
+        // Top-level modules are named in full
+        for root in &roots {
+            if let Some((name, full_name)) = modules.get_mut(root) {
+                name.clone_from(full_name);
+            }
+        }
+        let nested = Elixir {
+            opts: self.opts.clone(),
+            modules,
+        };
+        let mut texts: Vec<String> = roots
+            .iter()
+            .rev()
+            .map(|root| nested.render_module(root, schema, &children))
+            .collect();
+        if let Some(alias) = &schema.alias {
+            let name = nested.struct_or_class_name(alias);
+            let name = if prefix.is_empty() {
+                name
+            } else {
+                format!("{prefix}.{name}")
+            };
+            texts.push(nested.type_alias(&name, &type_name(&schema.root, &nested)));
+        }
+        Some(texts.join(&self.opts.separator))
+    }
+
+    // Closes the typespec, then defines the struct with a key per field
+    fn type_members(&self, _raw: String, fields: &[FieldContext]) -> String {
+        let indent = self.opts.indent_or(ELIXIR_INDENT);
+        let keys: Vec<String> = fields
+            .iter()
+            .map(|field| format!(":{}", self.field_name(field.name_key)))
+            .collect();
+        format!("{indent}}}\n\n{indent}defstruct [{}]\n", keys.join(", "))
+    }
+
+    // Structs without fields have an empty typespec on one line
+    fn format_type(&self, rendered: String) -> String {
+        let indent = self.opts.indent_or(ELIXIR_INDENT);
+        rendered.replacen(&format!("%__MODULE__{{\n{indent}}}"), "%__MODULE__{}", 1)
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        let indent = self.opts.indent_or(ELIXIR_INDENT);
+        format!(
+            "defmodule {name} do\n{indent}@type t :: {}\nend",
+            self.spec(type_name)
+        )
+    }
+
+    // Struct keys are atoms in snake_case
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| Case::Snake.apply(json_key));
+        ascii_identifier(&name)
+    }
+
+    // Fields that may be null or missing may be nil
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(ELIXIR_INDENT);
+        let elixir_field_name = self.field_name(field.name_key);
+        let nil = if field.inferred.may_be_missing() {
+            " | nil"
+        } else {
+            ""
+        };
+        format!(
+            "{}{elixir_field_name}: {}{nil},\n",
+            indent.repeat(2),
+            self.spec(field.type_name)
+        )
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        let element = self.spec(&arr_type);
+        if optional && element != ELIXIR_ANY {
+            format!("list({element} | nil)")
+        } else {
+            format!("list({element})")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => ELIXIR_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    ELIXIR_FLOAT
+                } else {
+                    ELIXIR_INT
+                }
+            }
+            Value::String(_) => ELIXIR_STRING,
+            Value::Null => ELIXIR_ANY,
+            // Non-primitives should not be passed to this function
+            _ => ELIXIR_ANY,
+        }
+    }
+
+    // Types are named in full, with the modules they're nested in
+    fn struct_or_class_name(&self, key: &str) -> String {
+        match self.modules.get(key) {
+            Some((_, full_name)) => full_name.clone(),
+            None => ascii_identifier(&first_char_upper(&self.opts.type_name(key))),
+        }
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "#"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "ex"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        ELIXIR_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &["defmodule", "do", "end", "defstruct", "@type", "nil"]
+    }
+}
+
+static NIM_KEYWORDS: &[&str] = &[
+    "addr",
+    "and",
+    "as",
+    "asm",
+    "bind",
+    "block",
+    "break",
+    "case",
+    "cast",
+    "concept",
+    "const",
+    "continue",
+    "converter",
+    "defer",
+    "discard",
+    "distinct",
+    "div",
+    "do",
+    "elif",
+    "else",
+    "end",
+    "enum",
+    "except",
+    "export",
+    "finally",
+    "for",
+    "from",
+    "func",
+    "if",
+    "import",
+    "in",
+    "include",
+    "interface",
+    "is",
+    "isnot",
+    "iterator",
+    "let",
+    "macro",
+    "method",
+    "mixin",
+    "mod",
+    "nil",
+    "not",
+    "notin",
+    "object",
+    "of",
+    "or",
+    "out",
+    "proc",
+    "ptr",
+    "raise",
+    "ref",
+    "return",
+    "shl",
+    "shr",
+    "static",
+    "template",
+    "try",
+    "tuple",
+    "type",
+    "using",
+    "var",
+    "when",
+    "while",
+    "xor",
+    "yield",
+];
+
+// `name` without what Nim doesn't allow in identifiers: anything but ASCII letters, digits
+// and single underscores between them. Names left not starting with a letter get field
+// before them.
+fn nim_identifier(name: &str) -> String {
+    let ascii = ascii_identifier(name);
+    let parts: Vec<&str> = ascii.split('_').filter(|part| !part.is_empty()).collect();
+    let name = parts.join("_");
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("field{name}")
+    }
+}
+
+// `s` as a Nim string literal, where other control characters are escaped as \xNN
+pub(crate) fn nim_string(s: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_ascii_control() => quoted.push_str(&format!("\\x{:02x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl Nim {
+    // The field's identifier, without the backticks keywords are quoted in
+    fn identifier(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| String::from(json_key));
+        nim_identifier(&name)
+    }
+}
+
+impl LanguageFormatter for Nim {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let type_name = self.struct_or_class_name(&raw);
+        format!("type {type_name}* = object\n")
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::new()
+    }
+
+    fn imports(&self, schema: &Schema) -> Option<String> {
+        let uses_any = schema
+            .type_refs()
+            .any(|type_ref| type_ref.innermost() == &TypeRef::Primitive(LogicalType::Any));
+        let optional_fields = schema
+            .types
+            .iter()
+            .flat_map(|type_def| &type_def.fields)
+            .any(Field::may_be_missing);
+        let optional_elements = schema
+            .type_refs()
+            .any(|type_ref| matches!(type_ref, TypeRef::Array { optional: true, .. }));
+        let mut imports = vec![];
+        if uses_any {
+            imports.push("import std/json");
+        }
+        if optional_fields || optional_elements {
+            imports.push("import std/options");
+        }
+        (!imports.is_empty()).then(|| imports.join("\n") + "\n\n")
+    }
+
+    // With annotations, jsony is told the keys of fields named other than them. It finds
+    // the hook by its name, so jsony doesn't need to be imported.
+    fn type_members(&self, raw: String, fields: &[FieldContext]) -> String {
+        let renamed: Vec<(&str, String)> = fields
+            .iter()
+            .map(|field| (field.json_key, self.identifier(field.name_key)))
+            .filter(|(json_key, name)| json_key != name)
+            .collect();
+        if !self.opts.annotations || renamed.is_empty() {
+            return String::new();
+        }
+        let indent = self.opts.indent_or(NIM_INDENT);
+        let type_name = self.struct_or_class_name(&raw);
+        let mut hook = format!(
+            "\nproc renameHook*(v: var {type_name}, fieldName: var string) =\n\
+             {indent}case fieldName\n"
+        );
+        for (json_key, name) in renamed {
+            hook.push_str(&format!(
+                "{indent}of {}: fieldName = {}\n",
+                nim_string(json_key),
+                nim_string(&name)
+            ));
+        }
+        hook.push_str(&format!("{indent}else: discard\n"));
+        hook
+    }
+
+    fn format_type(&self, rendered: String) -> String {
+        rendered.trim_end().to_string()
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("type {name}* = {type_name}")
+    }
+
+    // Fields are named after the keys, which std/json reads them from, and quoted in
+    // backticks if they're keywords
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self.identifier(json_key);
+        if NIM_KEYWORDS.contains(&name.as_str()) {
+            format!("`{name}`")
+        } else {
+            name
+        }
+    }
+
+    // Fields that may be null or missing are Options
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(NIM_INDENT);
+        let nim_field_name = self.field_name(field.name_key);
+        if field.inferred.may_be_missing() {
+            format!("{indent}{nim_field_name}*: Option[{}]\n", field.type_name)
+        } else {
+            format!("{indent}{nim_field_name}*: {}\n", field.type_name)
+        }
+    }
+
+    // JsonNodes are refs, which are nil already
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        if optional && arr_type != NIM_ANY {
+            format!("seq[Option[{arr_type}]]")
+        } else {
+            format!("seq[{arr_type}]")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => NIM_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    NIM_FLOAT
+                } else {
+                    NIM_INT
+                }
+            }
+            Value::String(_) => NIM_STRING,
+            Value::Null => NIM_ANY,
+            // Non-primitives should not be passed to this function
+            _ => NIM_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        nim_identifier(&first_char_upper(&self.opts.type_name(key)))
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "#"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "nim"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        NIM_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "type", "object", "proc", "var", "case", "of", "else", "discard", "import",
+        ]
+    }
+}
+
+// Crystal's keywords, fields named like one get an underscore after the name, so that they
+// don't hide methods like `class`
+static CRYSTAL_KEYWORDS: &[&str] = &[
+    "abstract",
+    "alias",
+    "as",
+    "asm",
+    "begin",
+    "break",
+    "case",
+    "class",
+    "def",
+    "do",
+    "else",
+    "elsif",
+    "end",
+    "ensure",
+    "enum",
+    "extend",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "include",
+    "instance_sizeof",
+    "lib",
+    "macro",
+    "module",
+    "next",
+    "nil",
+    "of",
+    "out",
+    "pointerof",
+    "private",
+    "protected",
+    "require",
+    "rescue",
+    "return",
+    "select",
+    "self",
+    "sizeof",
+    "struct",
+    "super",
+    "then",
+    "true",
+    "type",
+    "typeof",
+    "union",
+    "uninitialized",
+    "unless",
+    "until",
+    "verbatim",
+    "when",
+    "while",
+    "with",
+    "yield",
+];
+
+// `s` as a Crystal string literal, written the way Go's are, with interpolations escaped
+pub(crate) fn crystal_string(s: &str) -> String {
+    quote(s).replace("#{", "\\#{")
+}
+
+impl LanguageFormatter for Crystal {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let struct_name = self.struct_or_class_name(&raw);
+        let mut header = format!("struct {struct_name}\n");
+        if self.opts.annotations {
+            let indent = self.opts.indent_or(CRYSTAL_INDENT);
+            header.push_str(&format!("{indent}include JSON::Serializable\n\n"));
+        }
+        header
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::from("end")
+    }
+
+    // Files are only required at the top level, outside of the namespace
+    fn preamble(&self, schema: &Schema) -> Option<String> {
+        let uses_any = schema
+            .type_refs()
+            .any(|type_ref| type_ref.innermost() == &TypeRef::Primitive(LogicalType::Any));
+        (self.opts.annotations || uses_any).then(|| String::from("require \"json\"\n\n"))
+    }
+
+    // JSON::Serializable defines the constructor reading the fields, without it they're
+    // given in order
+    fn type_members(&self, _raw: String, fields: &[FieldContext]) -> String {
+        if self.opts.annotations || fields.is_empty() {
+            return String::new();
+        }
+        let indent = self.opts.indent_or(CRYSTAL_INDENT);
+        let parameters: Vec<String> = fields
+            .iter()
+            .map(|field| format!("@{}", self.field_name(field.name_key)))
+            .collect();
+        format!(
+            "\n{indent}def initialize({})\n{indent}end\n",
+            parameters.join(", ")
+        )
+    }
+
+    // Without fields, the include is the struct's last line
+    fn format_type(&self, rendered: String) -> String {
+        rendered.replacen("JSON::Serializable\n\nend", "JSON::Serializable\nend", 1)
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("alias {name} = {type_name}")
+    }
+
+    fn namespace(&self, name: &str) -> Namespace {
+        let name = name
+            .split(['.', ':'])
+            .filter(|part| !part.is_empty())
+            .map(|part| ascii_identifier(&first_char_upper(part)))
+            .collect::<Vec<_>>()
+            .join("::");
+        Namespace {
+            open: format!("module {name}\n"),
+            close: String::from("end"),
+            indent: self.opts.indent_or(CRYSTAL_INDENT).to_string(),
+        }
+    }
+
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| Case::Snake.apply(json_key));
+        let name = ascii_identifier(&name);
+        if CRYSTAL_KEYWORDS.contains(&name.as_str()) {
+            format!("{name}_")
+        } else {
+            name
+        }
+    }
+
+    // Fields named other than their key are annotated with it. Fields that may be null or
+    // missing are nilable, which JSON::Serializable leaves nil when they're missing.
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(CRYSTAL_INDENT);
+        let crystal_field_name = self.field_name(field.name_key);
+        let mut declaration = String::new();
+        if self.opts.annotations && crystal_field_name != field.json_key {
+            declaration.push_str(&format!(
+                "{indent}@[JSON::Field(key: {})]\n",
+                crystal_string(field.json_key)
+            ));
+        }
+        let mark = if field.inferred.may_be_missing() {
+            "?"
+        } else {
+            ""
+        };
+        declaration.push_str(&format!(
+            "{indent}getter {crystal_field_name} : {}{mark}\n",
+            field.type_name
+        ));
+        declaration
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        if optional && arr_type != CRYSTAL_ANY {
+            format!("Array({arr_type}?)")
+        } else {
+            format!("Array({arr_type})")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => CRYSTAL_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    CRYSTAL_FLOAT
+                } else {
+                    CRYSTAL_INT
+                }
+            }
+            Value::String(_) => CRYSTAL_STRING,
+            Value::Null => CRYSTAL_ANY,
+            // Non-primitives should not be passed to this function
+            _ => CRYSTAL_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&first_char_upper(&self.opts.type_name(key)))
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "#"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "cr"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        CRYSTAL_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "struct", "include", "getter", "def", "end", "require", "alias", "module",
+        ]
+    }
+}
+
+// Ruby's keywords, fields named like one get an underscore after the name, so that they
+// don't hide methods like `class`
+static RUBY_KEYWORDS: &[&str] = &[
+    "alias", "and", "begin", "break", "case", "class", "def", "defined?", "do", "else", "elsif",
+    "end", "ensure", "false", "for", "if", "in", "module", "next", "nil", "not", "or", "redo",
+    "rescue", "retry", "return", "self", "super", "then", "true", "undef", "unless", "until",
+    "when", "while", "yield",
+];
+
+impl Ruby {
+    fn dry(&self) -> bool {
+        self.opts.ruby_style == RubyStyle::DryStruct
+    }
+
+    // Dry::Struct reads attributes from symbol keys, named after the fields
+    fn transform_keys(&self, fields: &[FieldContext]) -> String {
+        let renamed: Vec<String> = fields
+            .iter()
+            .map(|field| (field.json_key, self.field_name(field.name_key)))
+            .filter(|(json_key, name)| json_key != name)
+            .map(|(json_key, name)| format!("{} => :{name}", quote(json_key)))
+            .collect();
+        if renamed.is_empty() {
+            return String::from("transform_keys(&:to_sym)");
+        }
+        format!(
+            "transform_keys {{ |key| {{ {} }}.fetch(key.to_s) {{ key.to_sym }} }}",
+            renamed.join(", ")
+        )
+    }
+}
+
+impl LanguageFormatter for Ruby {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let struct_name = self.struct_or_class_name(&raw);
+        if self.dry() {
+            format!("class {struct_name} < Dry::Struct\n")
+        } else {
+            format!("{struct_name} = Struct.new(\n")
+        }
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        if self.dry() {
+            String::from("end")
+        } else {
+            let indent = self.opts.indent_or(RUBY_INDENT);
+            format!("{indent}keyword_init: true\n)")
+        }
+    }
+
+    // Dry::Struct types come from a Types module of the application, defined the way its
+    // documentation has it
+    fn preamble(&self, _schema: &Schema) -> Option<String> {
+        let indent = self.opts.indent_or(RUBY_INDENT);
+        self.dry().then(|| {
+            format!("require \"dry-struct\"\n\nmodule Types\n{indent}include Dry.Types()\nend\n\n")
+        })
+    }
+
+    // With annotations, Dry::Structs read the keys of the JSON as they are
+    fn type_members(&self, _raw: String, fields: &[FieldContext]) -> String {
+        if !self.dry() || !self.opts.annotations || fields.is_empty() {
+            return String::new();
+        }
+        let indent = self.opts.indent_or(RUBY_INDENT);
+        format!("\n{indent}{}\n", self.transform_keys(fields))
+    }
+
+    // Struct.new needs members before Ruby 3.3, types without fields are plain classes
+    fn format_type(&self, rendered: String) -> String {
+        let indent = self.opts.indent_or(RUBY_INDENT);
+        let empty = format!("Struct.new(\n{indent}keyword_init: true\n)");
+        match rendered.strip_suffix(&empty) {
+            Some(header) => format!("{header}Class.new"),
+            None => rendered,
+        }
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        if self.dry() {
+            format!("{name} = {type_name}")
+        } else {
+            self.comment(&format!("{name} is an {type_name}"))
+        }
+    }
+
+    fn namespace(&self, name: &str) -> Namespace {
+        let indent = self.opts.indent_or(RUBY_INDENT);
+        let modules: Vec<String> = name
+            .split(['.', ':'])
+            .filter(|module| !module.is_empty())
+            .map(|module| ascii_identifier(&first_char_upper(module)))
+            .collect();
+        let open = modules
+            .iter()
+            .enumerate()
+            .map(|(depth, module)| format!("{}module {module}\n", indent.repeat(depth)))
+            .collect();
+        let close = (0..modules.len())
+            .rev()
+            .map(|depth| format!("{}end", indent.repeat(depth)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Namespace {
+            open,
+            close,
+            indent: indent.repeat(modules.len()),
+        }
+    }
+
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| Case::Snake.apply(json_key));
+        let name = ascii_identifier(&name);
+        if RUBY_KEYWORDS.contains(&name.as_str()) {
+            format!("{name}_")
+        } else {
+            name
+        }
+    }
+
+    // Dry::Struct attributes that may be missing are declared with attribute? and the ones
+    // that may be null are optional, see `Field::may_be_absent`
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(RUBY_INDENT);
+        let ruby_field_name = self.field_name(field.name_key);
+        let strict = self.opts.strict_null;
+        if !self.dry() {
+            let nil = if field.inferred.may_be_missing() {
+                ", nil"
+            } else {
+                ""
+            };
+            return format!(
+                "{indent}:{ruby_field_name}, {}\n",
+                self.comment(&format!("{}{nil}", field.type_name))
+            );
+        }
+        let attribute = if field.inferred.may_be_absent(strict) {
+            "attribute?"
+        } else {
+            "attribute"
+        };
+        let optional = if field.inferred.may_be_null(strict) {
+            ".optional"
+        } else {
+            ""
+        };
+        format!(
+            "{indent}{attribute} :{ruby_field_name}, {}{optional}\n",
+            field.type_name
+        )
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        match (self.dry(), optional) {
+            (true, true) if arr_type != DRY_ANY => format!("Types::Array.of({arr_type}.optional)"),
+            (true, _) => format!("Types::Array.of({arr_type})"),
+            (false, true) if arr_type != RUBY_ANY => format!("Array<{arr_type}, nil>"),
+            (false, _) => format!("Array<{arr_type}>"),
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        let (any, string, int, float, bool) = if self.dry() {
+            (DRY_ANY, DRY_STRING, DRY_INT, DRY_FLOAT, DRY_BOOL)
+        } else {
+            (RUBY_ANY, RUBY_STRING, RUBY_INT, RUBY_FLOAT, RUBY_BOOL)
+        };
+        match from {
+            Value::Bool(_) => bool,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    float
+                } else {
+                    int
+                }
+            }
+            Value::String(_) => string,
+            Value::Null => any,
+            // Non-primitives should not be passed to this function
+            _ => any,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&first_char_upper(&self.opts.type_name(key)))
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "#"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "rb"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        RUBY_AUTO_GENERATED
+    }
+
+    // Struct.new and Class.new assignments are the types' definitions
+    fn defined_type_name(&self, line: &str) -> Option<String> {
+        if let Some(rest) = line.strip_prefix("class ") {
+            return rest.split_whitespace().next().map(String::from);
+        }
+        let (name, value) = line.split_once(" = ")?;
+        (value.starts_with("Struct.new(") || value == "Class.new").then(|| name.to_string())
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "class",
+            "module",
+            "end",
+            "require",
+            "include",
+            "attribute",
+            "Struct",
+        ]
+    }
+}
+
+// Julia's keywords, fields named like one get an underscore after the name
+static JULIA_KEYWORDS: &[&str] = &[
+    "baremodule",
+    "begin",
+    "break",
+    "catch",
+    "const",
+    "continue",
+    "do",
+    "else",
+    "elseif",
+    "end",
+    "export",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "global",
+    "if",
+    "import",
+    "let",
+    "local",
+    "macro",
+    "module",
+    "quote",
+    "return",
+    "struct",
+    "true",
+    "try",
+    "using",
+    "while",
+];
+
+// `s` as a Julia string literal, written the way Go's are, with interpolations escaped
+pub(crate) fn julia_string(s: &str) -> String {
+    quote(s).replace('$', "\\$")
+}
+
+impl LanguageFormatter for Julia {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let struct_name = self.struct_or_class_name(&raw);
+        format!("struct {struct_name}\n")
+    }
+
+    // The struct is closed by `type_members`
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::new()
+    }
+
+    fn imports(&self, _schema: &Schema) -> Option<String> {
+        self.opts
+            .annotations
+            .then(|| String::from("using StructTypes\n\n"))
+    }
+
+    // Closes the struct, then with annotations tells StructTypes, which JSON3 reads types
+    // with, that it's a struct and the keys of fields named other than them
+    fn type_members(&self, raw: String, fields: &[FieldContext]) -> String {
+        let mut members = String::from("end\n");
+        if !self.opts.annotations {
+            return members;
+        }
+        let struct_name = self.struct_or_class_name(&raw);
+        members.push_str(&format!(
+            "\nStructTypes.StructType(::Type{{{struct_name}}}) = StructTypes.Struct()\n"
+        ));
+        let renamed: Vec<String> = fields
+            .iter()
+            .map(|field| (field.json_key, self.field_name(field.name_key)))
+            .filter(|(json_key, name)| json_key != name)
+            .map(|(json_key, name)| format!("(:{name}, Symbol({}))", julia_string(json_key)))
+            .collect();
+        if !renamed.is_empty() {
+            // A tuple of one needs a comma after its element
+            let comma = if renamed.len() == 1 { "," } else { "" };
+            members.push_str(&format!(
+                "StructTypes.names(::Type{{{struct_name}}}) = ({}{comma})\n",
+                renamed.join(", ")
+            ));
+        }
+        members
+    }
+
+    // Structs without fields are on one line, and the ones with fields defaulting to nothing
+    // get a keyword constructor
+    fn format_type(&self, rendered: String) -> String {
+        let rendered = match rendered.split_once('\n') {
+            Some((header, rest)) if rest.starts_with("end\n") => format!("{header} {rest}"),
+            _ => rendered,
+        };
+        let rendered = rendered.trim_end().to_string();
+        if rendered.contains(" = nothing\n") {
+            format!("Base.@kwdef {rendered}")
+        } else {
+            rendered
+        }
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("const {name} = {type_name}")
+    }
+
+    // Module contents aren't indented in Julia
+    fn namespace(&self, name: &str) -> Namespace {
+        let modules: Vec<String> = name
+            .split(['.', ':'])
+            .filter(|module| !module.is_empty())
+            .map(|module| ascii_identifier(&first_char_upper(module)))
+            .collect();
+        let open = modules
+            .iter()
+            .map(|module| format!("module {module}\n\n"))
+            .collect();
+        let close = modules
+            .iter()
+            .rev()
+            .map(|module| format!("end # module {module}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Namespace {
+            open,
+            close: format!("\n{close}"),
+            indent: String::new(),
+        }
+    }
+
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| Case::Snake.apply(json_key));
+        let name = ascii_identifier(&name);
+        if JULIA_KEYWORDS.contains(&name.as_str()) {
+            format!("{name}_")
+        } else {
+            name
+        }
+    }
+
+    // Fields that may be null or missing are Union{Nothing,T}, and the ones that may be
+    // missing default to nothing
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(JULIA_INDENT);
+        let julia_field_name = self.field_name(field.name_key);
+        if !field.inferred.may_be_missing() {
+            return format!("{indent}{julia_field_name}::{}\n", field.type_name);
+        }
+        let default = if field.inferred.may_be_absent(self.opts.strict_null) {
+            " = nothing"
+        } else {
+            ""
+        };
+        format!(
+            "{indent}{julia_field_name}::Union{{Nothing,{}}}{default}\n",
+            field.type_name
+        )
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        if optional && arr_type != JULIA_ANY {
+            format!("Vector{{Union{{Nothing,{arr_type}}}}}")
+        } else {
+            format!("Vector{{{arr_type}}}")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => JULIA_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    JULIA_FLOAT
+                } else {
+                    JULIA_INT
+                }
+            }
+            Value::String(_) => JULIA_STRING,
+            Value::Null => JULIA_ANY,
+            // Non-primitives should not be passed to this function
+            _ => JULIA_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&first_char_upper(&self.opts.type_name(key)))
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "#"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "jl"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        JULIA_AUTO_GENERATED
+    }
+
+    fn defined_type_name(&self, line: &str) -> Option<String> {
+        let rest = line.strip_prefix("Base.@kwdef ").unwrap_or(line);
+        let rest = rest
+            .strip_prefix("struct ")
+            .or_else(|| rest.strip_prefix("const "))?;
+        rest.split([' ', '\n']).next().map(String::from)
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &["struct", "end", "const", "module", "using", "nothing"]
+    }
+}
+
+// Groovy's keywords, fields named like one get an underscore after the name
+static GROOVY_KEYWORDS: &[&str] = &[
+    "abstract",
+    "as",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "def",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "threadsafe",
+    "throw",
+    "throws",
+    "trait",
+    "transient",
+    "true",
+    "try",
+    "var",
+    "void",
+    "volatile",
+    "while",
+];
+
+// `s` as a Groovy string literal, its double-quoted strings escape what Julia's do
+pub(crate) fn groovy_string(s: &str) -> String {
+    julia_string(s)
+}
+
+impl LanguageFormatter for Groovy {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let class_name = self.struct_or_class_name(&raw);
+        format!("@Canonical\nclass {class_name} ") + "{\n"
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::from("}")
+    }
+
+    // Jackson is told the keys of properties named other than them
+    fn imports(&self, schema: &Schema) -> Option<String> {
+        let renamed = schema.types.iter().any(|type_def| {
+            type_def
+                .fields
+                .iter()
+                .zip(unique_name_keys(type_def, self))
+                .any(|(field, name_key)| self.field_name(&name_key) != field.key)
+        });
+        let mut imports = vec![];
+        if self.opts.annotations && renamed {
+            imports.push("import com.fasterxml.jackson.annotation.JsonProperty\n");
+        }
+        imports.push("import groovy.transform.Canonical\n");
+        Some(imports.concat() + "\n")
+    }
+
+    fn namespace(&self, name: &str) -> Namespace {
+        Namespace {
+            open: format!("package {name}\n\n"),
+            close: String::new(),
+            indent: String::new(),
+        }
+    }
+
+    // Properties are in camelCase, as Jackson finds them by their getters
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| Case::Camel.apply(json_key));
+        let name = ascii_identifier(&name);
+        if GROOVY_KEYWORDS.contains(&name.as_str()) {
+            format!("{name}_")
+        } else {
+            name
+        }
+    }
+
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(GROOVY_INDENT);
+        let groovy_field_name = self.field_name(field.name_key);
+        let mut declaration = String::new();
+        if self.opts.annotations && groovy_field_name != field.json_key {
+            declaration.push_str(&format!(
+                "{indent}@JsonProperty({})\n",
+                groovy_string(field.json_key)
+            ));
+        }
+        declaration.push_str(&format!(
+            "{indent}{} {groovy_field_name}\n",
+            field.type_name
+        ));
+        declaration
+    }
+
+    fn format_arr_type(&self, arr_type: String, _optional: bool) -> String {
+        format!("List<{arr_type}>")
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => GROOVY_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    GROOVY_FLOAT
+                } else {
+                    GROOVY_INT
+                }
+            }
+            Value::String(_) => GROOVY_STRING,
+            Value::Null => GROOVY_ANY,
+            // Non-primitives should not be passed to this function
+            _ => GROOVY_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&first_char_upper(&self.opts.type_name(key)))
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "groovy"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        GROOVY_AUTO_GENERATED
+    }
+
+    fn defined_type_name(&self, line: &str) -> Option<String> {
+        let rest = line.strip_prefix("class ")?;
+        rest.split_whitespace().next().map(String::from)
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &["class", "package", "import"]
+    }
+}
+
+// Names properties can't have, C's keywords and the ones NSObject's methods and types have,
+// they get an underscore after the name
+static OBJC_RESERVED: &[&str] = &[
+    "class",
+    "copy",
+    "description",
+    "hash",
+    "id",
+    "init",
+    "new",
+    "self",
+    "super",
+    "superclass",
+];
+
+// Generated classes are referred to by pointers, their names are all type_name() gives
+fn objc_pointer(type_name: &str) -> String {
+    if type_name.ends_with('*') || type_name == OBJC_ANY {
+        type_name.to_string()
+    } else {
+        format!("{type_name} *")
+    }
+}
+
+impl LanguageFormatter for ObjC {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let class_name = self.struct_or_class_name(&raw);
+        format!("@interface {class_name} : NSObject\n")
+    }
+
+    // The interface is closed by `type_members`
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::new()
+    }
+
+    fn preamble(&self, _schema: &Schema) -> Option<String> {
+        Some(String::from("#import <Foundation/Foundation.h>\n\n"))
+    }
+
+    // Closes the interface, followed by an implementation for the properties to be
+    // synthesized in
+    fn type_members(&self, raw: String, _fields: &[FieldContext]) -> String {
+        let class_name = self.struct_or_class_name(&raw);
+        format!("@end\n\n@implementation {class_name}\n@end")
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("typedef {};", c_declaration(&objc_pointer(type_name), name))
+    }
+
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| Case::Camel.apply(json_key));
+        let name = ascii_identifier(&name);
+        if C_KEYWORDS.contains(&name.as_str()) || OBJC_RESERVED.contains(&name.as_str()) {
+            format!("{name}_")
+        } else {
+            name
+        }
+    }
+
+    // Strings and arrays are copied, so that mutable ones can't change under the object.
+    // Untyped properties may be nil, and so may the ones that may be null or missing.
+    fn format_field(&self, field: &FieldContext) -> String {
+        let objc_field_name = self.field_name(field.name_key);
+        let memory = if field.type_name == OBJC_STRING || field.array_depth() > 0 {
+            "copy"
+        } else {
+            "strong"
+        };
+        let nullable = field.type_name == OBJC_ANY || field.inferred.may_be_missing();
+        let nullability = if nullable { "nullable" } else { "nonnull" };
+        format!(
+            "@property (nonatomic, {memory}, {nullability}) {};\n",
+            c_declaration(&objc_pointer(field.type_name), &objc_field_name)
+        )
+    }
+
+    // Arrays can't hold nil, null elements are NSNulls
+    fn format_arr_type(&self, arr_type: String, _optional: bool) -> String {
+        format!("NSArray<{}> *", objc_pointer(&arr_type))
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) | Value::Number(_) => OBJC_NUMBER,
+            Value::String(_) => OBJC_STRING,
+            Value::Null => OBJC_ANY,
+            // Non-primitives should not be passed to this function
+            _ => OBJC_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&first_char_upper(&self.opts.type_name(key)))
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "h"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        OBJC_AUTO_GENERATED
+    }
+
+    fn defined_type_name(&self, line: &str) -> Option<String> {
+        if let Some(rest) = line.strip_prefix("@interface ") {
+            return rest.split_whitespace().next().map(String::from);
+        }
+        let rest = line.strip_prefix("typedef ")?.strip_suffix(';')?;
+        rest.rsplit([' ', '*']).next().map(String::from)
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "@interface",
+            "@implementation",
+            "@end",
+            "@property",
+            "nonatomic",
+            "copy",
+            "strong",
+            "nullable",
+            "nonnull",
+            "typedef",
+        ]
+    }
+}
+
+// Words the Thrift compiler doesn't allow as names, its own and those of the languages it
+// generates code in. Fields named like one get an underscore after the name.
+static THRIFT_RESERVED: &[&str] = &[
+    "abstract",
+    "and",
+    "args",
+    "as",
+    "assert",
+    "async",
+    "binary",
+    "bool",
+    "break",
+    "byte",
+    "case",
+    "class",
+    "const",
+    "continue",
+    "cpp_include",
+    "declare",
+    "def",
+    "default",
+    "del",
+    "delete",
+    "do",
+    "double",
+    "elif",
+    "else",
+    "elseif",
+    "end",
+    "enum",
+    "except",
+    "exception",
+    "exec",
+    "extends",
+    "finally",
+    "for",
+    "foreach",
+    "from",
+    "function",
+    "global",
+    "goto",
+    "i16",
+    "i32",
+    "i64",
+    "i8",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "include",
+    "inline",
+    "instanceof",
+    "interface",
+    "is",
+    "lambda",
+    "list",
+    "map",
+    "module",
+    "namespace",
+    "native",
+    "new",
+    "next",
+    "nil",
+    "not",
+    "oneway",
+    "optional",
+    "or",
+    "pass",
+    "print",
+    "private",
+    "protected",
+    "public",
+    "raise",
+    "redo",
+    "register",
+    "required",
+    "rescue",
+    "retry",
+    "return",
+    "self",
+    "senum",
+    "service",
+    "set",
+    "sizeof",
+    "slist",
+    "static",
+    "string",
+    "struct",
+    "super",
+    "switch",
+    "synchronized",
+    "then",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "try",
+    "typedef",
+    "undef",
+    "union",
+    "unless",
+    "unsigned",
+    "until",
+    "use",
+    "var",
+    "virtual",
+    "void",
+    "volatile",
+    "when",
+    "while",
+    "with",
+    "xor",
+    "yield",
+];
+
+impl LanguageFormatter for Thrift {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let struct_name = self.struct_or_class_name(&raw);
+        format!("struct {struct_name} ") + "{\n"
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::from("}")
+    }
+
+    fn imports(&self, schema: &Schema) -> Option<String> {
+        schema
+            .type_refs()
+            .any(|type_ref| type_ref.innermost() == &TypeRef::Primitive(LogicalType::Any))
+            .then(|| format!("typedef string {THRIFT_ANY}\n\n"))
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("typedef {type_name} {name}")
+    }
+
+    // Applies to the code generated in every language
+    fn namespace(&self, name: &str) -> Namespace {
+        Namespace {
+            open: format!("namespace * {name}\n\n"),
+            close: String::new(),
+            indent: String::new(),
+        }
+    }
+
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| Case::Snake.apply(json_key));
+        let name = ascii_identifier(&name);
+        if THRIFT_RESERVED.contains(&name.as_str()) {
+            format!("{name}_")
+        } else {
+            name
+        }
+    }
+
+    // Fields are numbered in the order they're declared, the way proto fields are. Fields
+    // that may be null or missing are optional.
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(THRIFT_INDENT);
+        let thrift_field_name = self.field_name(field.name_key);
+        let label = if field.inferred.may_be_missing() {
+            "optional "
+        } else {
+            ""
+        };
+        format!(
+            "{indent}{}: {label}{} {thrift_field_name},\n",
+            field.index + 1,
+            field.type_name
+        )
+    }
+
+    // Lists can't hold nulls
+    fn format_arr_type(&self, arr_type: String, _optional: bool) -> String {
+        format!("list<{arr_type}>")
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => THRIFT_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    THRIFT_FLOAT
+                } else {
+                    THRIFT_INT
+                }
+            }
+            Value::String(_) => THRIFT_STRING,
+            Value::Null => THRIFT_ANY,
+            // Non-primitives should not be passed to this function
+            _ => THRIFT_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&self.opts.type_name(key))
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "thrift"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        THRIFT_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &["struct", "typedef", "namespace", "optional", "list"]
+    }
+}
+
+// Words of the schema language flatc doesn't take as field names
+static FLATBUFFERS_RESERVED: &[&str] = &[
+    "attribute",
+    "bool",
+    "byte",
+    "double",
+    "enum",
+    "file_extension",
+    "file_identifier",
+    "float",
+    "include",
+    "int",
+    "long",
+    "namespace",
+    "root_type",
+    "rpc_service",
+    "short",
+    "string",
+    "struct",
+    "table",
+    "ubyte",
+    "uint",
+    "ulong",
+    "union",
+    "ushort",
+];
+
+impl LanguageFormatter for FlatBuffers {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let table_name = self.struct_or_class_name(&raw);
+        format!("table {table_name} ") + "{\n"
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::from("}")
+    }
+
+    // The root of a buffer is a table, so a root array is one holding the elements
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        let indent = self.opts.indent_or(FLATBUFFERS_INDENT);
+        format!("table {name} ") + &format!("{{\n{indent}items:{type_name};\n}}")
+    }
+
+    fn epilogue(&self, schema: &Schema) -> Option<String> {
+        let root = schema
+            .alias
+            .as_deref()
+            .or(schema.root_type().map(|root| root.key.as_str()))?;
+        Some(format!(
+            "\nroot_type {};\n",
+            self.struct_or_class_name(root)
+        ))
+    }
+
+    fn namespace(&self, name: &str) -> Namespace {
+        Namespace {
+            open: format!("namespace {name};\n\n"),
+            close: String::new(),
+            indent: String::new(),
+        }
+    }
+
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| Case::Snake.apply(json_key));
+        let name = ascii_identifier(&name);
+        if FLATBUFFERS_RESERVED.contains(&name.as_str()) {
+            format!("{name}_")
+        } else {
+            name
+        }
+    }
+
+    // Tables, strings and vectors may always be absent, scalars read as their default
+    // unless they're declared optional with a null default, as the ones that may be null
+    // or missing are
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(FLATBUFFERS_INDENT);
+        let fbs_field_name = self.field_name(field.name_key);
+        let scalar =
+            [FLATBUFFERS_INT, FLATBUFFERS_FLOAT, FLATBUFFERS_BOOL].contains(&field.type_name);
+        let default = if scalar && field.inferred.may_be_missing() {
+            " = null"
+        } else {
+            ""
+        };
+        format!("{indent}{fbs_field_name}:{}{default};\n", field.type_name)
+    }
+
+    // Vectors can't hold nulls or other vectors, vectors of vectors are FlexBuffers
+    fn format_arr_type(&self, arr_type: String, _optional: bool) -> String {
+        if arr_type.starts_with('[') {
+            String::from(FLATBUFFERS_ANY)
+        } else {
+            format!("[{arr_type}]")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => FLATBUFFERS_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    FLATBUFFERS_FLOAT
+                } else {
+                    FLATBUFFERS_INT
+                }
+            }
+            Value::String(_) => FLATBUFFERS_STRING,
+            Value::Null => FLATBUFFERS_ANY,
+            // Non-primitives should not be passed to this function
+            _ => FLATBUFFERS_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&self.opts.type_name(key))
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "fbs"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        FLATBUFFERS_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &["table", "namespace", "root_type", "flexbuffer"]
+    }
+}
+
+impl LanguageFormatter for CapnProto {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let struct_name = self.struct_or_class_name(&raw);
+        format!("struct {struct_name} ") + "{\n"
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::from("}")
+    }
+
+    // Every schema file needs a unique 64-bit ID with the high bit set. This one is made
+    // from the names of the types, for a schema to compile as is, and is meant to be
+    // replaced with one from `capnp id`.
+    fn preamble(&self, schema: &Schema) -> Option<String> {
+        let mut hasher = DefaultHasher::new();
+        schema
+            .types
+            .iter()
+            .for_each(|type_def| type_def.key.hash(&mut hasher));
+        let id = hasher.finish() | 1 << 63;
+        Some(format!(
+            "# Placeholder, replace it with a unique ID from `capnp id`\n@{id:#018x};\n\n"
+        ))
+    }
+
+    fn imports(&self, _schema: &Schema) -> Option<String> {
+        self.opts
+            .annotations
+            .then(|| String::from("using Json = import \"/capnp/compat/json.capnp\";\n\n"))
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("using {name} = {type_name};")
+    }
+
+    // Cap'n Proto has no packages, the closest is the namespace of the generated C++
+    fn namespace(&self, name: &str) -> Namespace {
+        let cxx_namespace = name.replace('.', "::");
+        Namespace {
+            open: format!(
+                "using Cxx = import \"/capnp/c++.capnp\";\n$Cxx.namespace({});\n\n",
+                quote(&cxx_namespace)
+            ),
+            close: String::new(),
+            indent: String::new(),
+        }
+    }
+
+    // Names can't have underscores in them
+    fn field_name(&self, json_key: &str) -> String {
+        let name = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| Case::Camel.apply(json_key));
+        let name = first_char_lower(&ascii_identifier(&name).replace('_', ""));
+        // Names start with a letter
+        if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            name
+        } else {
+            format!("field{name}")
+        }
+    }
+
+    // Fields are numbered from @0 in the order they're declared. Texts, lists and structs
+    // may be null, scalars can't be.
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(CAPNP_INDENT);
+        let capnp_field_name = self.field_name(field.name_key);
+        let json_name = if self.opts.annotations && capnp_field_name != field.json_key {
+            format!(" $Json.name({})", quote(field.json_key))
+        } else {
+            String::new()
+        };
+        format!(
+            "{indent}{capnp_field_name} @{} :{}{json_name};\n",
+            field.index, field.type_name
+        )
+    }
+
+    fn format_arr_type(&self, arr_type: String, _optional: bool) -> String {
+        format!("List({arr_type})")
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => CAPNP_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    CAPNP_FLOAT
+                } else {
+                    CAPNP_INT
+                }
+            }
+            Value::String(_) => CAPNP_STRING,
+            Value::Null => CAPNP_ANY,
+            // Non-primitives should not be passed to this function
+            _ => CAPNP_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        first_char_upper(&ascii_identifier(&self.opts.type_name(key)).replace('_', ""))
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "#"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "capnp"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        CAPNP_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &["struct", "using", "import"]
+    }
+}
+
+// Schemas are constants named like the types inferred from them, which TypeScript keeps
+// apart from the constants
+impl LanguageFormatter for Zod {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let schema_name = self.struct_or_class_name(&raw);
+        format!("export const {schema_name} = z.object(") + "{\n"
+    }
+
+    fn struct_or_class_footer(&self, struct_name: Option<String>) -> String {
+        let schema_name = self.struct_or_class_name(&struct_name.unwrap_or_default());
+        format!("}});\nexport type {schema_name} = z.infer<typeof {schema_name}>;")
+    }
+
+    // Before the namespace, imports can't be inside one
+    fn preamble(&self, _schema: &Schema) -> Option<String> {
+        Some(String::from("import { z } from \"zod\";\n\n"))
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("export const {name} = {type_name};\nexport type {name} = z.infer<typeof {name}>;")
+    }
+
+    fn namespace(&self, name: &str) -> Namespace {
+        Namespace {
+            open: format!("export namespace {name} ") + "{\n",
+            close: String::from("}"),
+            indent: self.opts.indent_or(ZOD_INDENT).to_string(),
+        }
+    }
+
+    // The schema checks the JSON as it is, so fields are named exactly like their key
+    fn field_name(&self, json_key: &str) -> String {
+        if is_identifier(json_key) {
+            json_key.to_string()
+        } else {
+            quote(json_key)
+        }
+    }
+
+    // Fields that may be null or missing are `.nullable()` and `.optional()`, see
+    // `Field::may_be_absent`
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(ZOD_INDENT);
+        let key = self.field_name(field.json_key);
+        let strict = self.opts.strict_null;
+        let nullable = if field.inferred.may_be_null(strict) {
+            ".nullable()"
+        } else {
+            ""
+        };
+        let optional = if field.inferred.may_be_absent(strict) {
+            ".optional()"
+        } else {
+            ""
+        };
+        format!("{indent}{key}: {}{nullable}{optional},\n", field.type_name)
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        if optional {
+            format!("{arr_type}.nullable().array()")
+        } else {
+            format!("{arr_type}.array()")
+        }
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => ZOD_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    ZOD_FLOAT
+                } else {
+                    ZOD_INT
+                }
+            }
+            Value::String(_) => ZOD_STRING,
+            Value::Null => ZOD_ANY,
+            // Non-primitives should not be passed to this function
+            _ => ZOD_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&self.opts.type_name(key))
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "ts"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        ZOD_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "import",
+            "from",
+            "export",
+            "const",
+            "type",
+            "typeof",
+            "namespace",
+        ]
+    }
+}
+
+// Codecs are constants named like the types they decode to, the way the schemas of Zod are
+impl LanguageFormatter for IoTs {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let codec_name = self.struct_or_class_name(&raw);
+        format!("export const {codec_name} = t.type(") + "{\n"
+    }
+
+    fn struct_or_class_footer(&self, struct_name: Option<String>) -> String {
+        let codec_name = self.struct_or_class_name(&struct_name.unwrap_or_default());
+        format!("}});\nexport type {codec_name} = t.TypeOf<typeof {codec_name}>;")
+    }
+
+    fn preamble(&self, _schema: &Schema) -> Option<String> {
+        Some(String::from("import * as t from \"io-ts\";\n\n"))
+    }
+
+    fn type_alias(&self, name: &str, type_name: &str) -> String {
+        format!("export const {name} = {type_name};\nexport type {name} = t.TypeOf<typeof {name}>;")
+    }
+
+    fn namespace(&self, name: &str) -> Namespace {
+        Namespace {
+            open: format!("export namespace {name} ") + "{\n",
+            close: String::from("}"),
+            indent: self.opts.indent_or(IO_TS_INDENT).to_string(),
+        }
+    }
+
+    fn field_name(&self, json_key: &str) -> String {
+        if is_identifier(json_key) {
+            json_key.to_string()
+        } else {
+            quote(json_key)
+        }
+    }
+
+    // Fields that may be null or missing are unions with t.null and t.undefined, which also
+    // lets the key be left out, see `Field::may_be_absent`
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(IO_TS_INDENT);
+        let key = self.field_name(field.json_key);
+        let strict = self.opts.strict_null;
+        let codec = if field.inferred.may_be_missing() {
+            let null = if field.inferred.may_be_null(strict) {
+                "t.null, "
+            } else {
+                ""
+            };
+            let undefined = if field.inferred.may_be_absent(strict) {
+                "t.undefined, "
+            } else {
+                ""
+            };
+            format!("t.union([{null}{undefined}{}])", field.type_name)
+        } else {
+            field.type_name.to_string()
+        };
+        format!("{indent}{key}: {codec},\n")
+    }
+
+    fn format_arr_type(&self, arr_type: String, optional: bool) -> String {
+        if optional {
+            format!("t.array(t.union([t.null, {arr_type}]))")
+        } else {
+            format!("t.array({arr_type})")
+        }
+    }
+
+    // t.Int is branded, so integers are plain numbers too
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => IO_TS_BOOL,
+            Value::Number(_) => IO_TS_NUMBER,
+            Value::String(_) => IO_TS_STRING,
+            Value::Null => IO_TS_ANY,
+            // Non-primitives should not be passed to this function
+            _ => IO_TS_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&self.opts.type_name(key))
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "ts"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        IO_TS_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "import",
+            "from",
+            "export",
+            "const",
+            "type",
+            "typeof",
+            "namespace",
+        ]
+    }
+}
+
+// Part of a YAML document. Maps keep their keys in the order they're written in.
+enum OpenApiYaml {
+    Scalar(String),
+    List(Vec<OpenApiYaml>),
+    Map(Vec<(String, OpenApiYaml)>),
+}
+
+impl OpenApiYaml {
+    fn text(text: &str) -> OpenApiYaml {
+        OpenApiYaml::Scalar(yaml_string(text))
+    }
+
+    fn entry(key: &str, value: OpenApiYaml) -> (String, OpenApiYaml) {
+        (yaml_string(key), value)
+    }
+
+    // Empty maps and lists, and lists of scalars, e.g. type unions, stay on one line
+    fn is_flow(&self) -> bool {
+        match self {
+            OpenApiYaml::Scalar(_) => true,
+            OpenApiYaml::Map(entries) => entries.is_empty(),
+            OpenApiYaml::List(items) => items
+                .iter()
+                .all(|item| matches!(item, OpenApiYaml::Scalar(_))),
+        }
+    }
+
+    // Writes the value from where its first line has already been started, with `pad`
+    // starting the lines after it
+    fn write(&self, indent: &str, pad: &str, out: &mut String) {
+        match self {
+            OpenApiYaml::Scalar(text) => out.push_str(text),
+            OpenApiYaml::Map(entries) if entries.is_empty() => out.push_str("{}"),
+            OpenApiYaml::List(items) if self.is_flow() => {
+                out.push('[');
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        out.push_str(", ");
+                    }
+                    item.write(indent, pad, out);
+                }
+                out.push(']');
+            }
+            OpenApiYaml::List(items) => {
+                let item_pad = format!("{pad}  ");
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        out.push_str(&format!("\n{pad}"));
+                    }
+                    out.push_str("- ");
+                    item.write(indent, &item_pad, out);
+                }
+            }
+            OpenApiYaml::Map(entries) => {
+                let inner = format!("{pad}{indent}");
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if index > 0 {
+                        out.push_str(&format!("\n{pad}"));
+                    }
+                    if value.is_flow() {
+                        out.push_str(&format!("{key}: "));
+                        value.write(indent, pad, out);
+                    } else {
+                        out.push_str(&format!("{key}:\n{inner}"));
+                        value.write(indent, &inner, out);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// `text` as a plain YAML scalar if it can't be read as anything else, double-quoted
+// otherwise
+fn yaml_string(text: &str) -> String {
+    let special = ["true", "false", "null", "yes", "no", "on", "off", "y", "n"];
+    let plain = text.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-./$".contains(c))
+        && !special.contains(&text.to_lowercase().as_str());
+    if plain {
+        text.to_string()
+    } else {
+        quote(text)
+    }
+}
+
+impl OpenApi {
+    // The schema of `type_ref`, objects are references to their own schema
+    fn openapi_schema(&self, type_ref: &TypeRef) -> OpenApiYaml {
+        let scalar = |type_name: &str| {
+            OpenApiYaml::Map(vec![OpenApiYaml::entry(
+                "type",
+                OpenApiYaml::text(type_name),
+            )])
+        };
+        match type_ref {
+            TypeRef::Primitive(LogicalType::Any) => OpenApiYaml::Map(vec![]),
+            TypeRef::Primitive(LogicalType::Uuid) => OpenApiYaml::Map(vec![
+                OpenApiYaml::entry("type", OpenApiYaml::text(OPENAPI_STRING)),
+                OpenApiYaml::entry("format", OpenApiYaml::text("uuid")),
+            ]),
+            TypeRef::Primitive(LogicalType::String | LogicalType::Date) => scalar(OPENAPI_STRING),
+            TypeRef::Primitive(LogicalType::Int | LogicalType::TimestampMillis) => {
+                scalar(OPENAPI_INT)
+            }
+            TypeRef::Primitive(LogicalType::Float | LogicalType::Decimal) => scalar(OPENAPI_FLOAT),
+            TypeRef::Primitive(LogicalType::Bool) => scalar(OPENAPI_BOOL),
+            TypeRef::Object(key) => {
+                let reference = format!("#/components/schemas/{}", self.struct_or_class_name(key));
+                OpenApiYaml::Map(vec![OpenApiYaml::entry(
+                    "$ref",
+                    OpenApiYaml::text(&reference),
+                )])
+            }
+            TypeRef::Array { element, optional } => {
+                let items = self.openapi_schema(element);
+                let items = if *optional { nullable(items) } else { items };
+                OpenApiYaml::Map(vec![
+                    OpenApiYaml::entry("type", OpenApiYaml::text("array")),
+                    OpenApiYaml::entry("items", items),
+                ])
+            }
+        }
+    }
+
+    fn object_schema(&self, type_def: &TypeDef) -> OpenApiYaml {
+        let strict = self.opts.strict_null;
+        let properties = type_def
+            .fields
+            .iter()
+            .map(|field| {
+                let schema = self.openapi_schema(&field.type_ref);
+                let schema = if field.may_be_null(strict) {
+                    nullable(schema)
+                } else {
+                    schema
+                };
+                OpenApiYaml::entry(&field.key, schema)
+            })
+            .collect();
+        let required: Vec<OpenApiYaml> = type_def
+            .fields
+            .iter()
+            .filter(|field| !field.may_be_absent(strict))
+            .map(|field| OpenApiYaml::text(&field.key))
+            .collect();
+        let mut schema = vec![
+            OpenApiYaml::entry("type", OpenApiYaml::text("object")),
+            OpenApiYaml::entry("properties", OpenApiYaml::Map(properties)),
+        ];
+        if !required.is_empty() {
+            schema.push(OpenApiYaml::entry("required", OpenApiYaml::List(required)));
+        }
+        OpenApiYaml::Map(schema)
+    }
+}
+
+// `schema`, also letting the value be null: a null type in the union of its types, or
+// any of the schema or null when it's a reference. Any value may already be null.
+fn nullable(schema: OpenApiYaml) -> OpenApiYaml {
+    let OpenApiYaml::Map(mut entries) = schema else {
+        return schema;
+    };
+    match entries.first_mut() {
+        Some((key, value)) if key == "type" => {
+            let type_name = std::mem::replace(value, OpenApiYaml::List(vec![]));
+            *value = OpenApiYaml::List(vec![type_name, OpenApiYaml::text("null")]);
+            OpenApiYaml::Map(entries)
+        }
+        Some(_) => {
+            let null =
+                OpenApiYaml::Map(vec![OpenApiYaml::entry("type", OpenApiYaml::text("null"))]);
+            OpenApiYaml::Map(vec![OpenApiYaml::entry(
+                "anyOf",
+                OpenApiYaml::List(vec![OpenApiYaml::Map(entries), null]),
+            )])
+        }
+        None => OpenApiYaml::Map(entries),
+    }
+}
+
+impl LanguageFormatter for OpenApi {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let schema_name = self.struct_or_class_name(&raw);
+        format!("{schema_name}:\n")
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::new()
+    }
+
+    // The components of an OpenAPI 3.1 document, to be pasted into one, with a schema
+    // for each type. Schemas of objects refer to the ones of the objects in them.
+    fn render_schema(&self, schema: &Schema) -> Option<String> {
+        let mut schemas: Vec<(String, OpenApiYaml)> = schema
+            .types
+            .iter()
+            .map(|type_def| {
+                let name = self.struct_or_class_name(&type_def.key);
+                OpenApiYaml::entry(&name, self.object_schema(type_def))
+            })
+            .collect();
+        if let Some(alias) = schema.alias.as_deref() {
+            let name = self.struct_or_class_name(alias);
+            schemas.push(OpenApiYaml::entry(&name, self.openapi_schema(&schema.root)));
+        }
+        let document = OpenApiYaml::Map(vec![OpenApiYaml::entry(
+            "components",
+            OpenApiYaml::Map(vec![OpenApiYaml::entry(
+                "schemas",
+                OpenApiYaml::Map(schemas),
+            )]),
+        )]);
+        let mut text = String::new();
+        document.write(self.opts.indent_or(OPENAPI_INDENT), "", &mut text);
+        Some(text)
+    }
+
+    fn field_name(&self, json_key: &str) -> String {
+        yaml_string(json_key)
+    }
+
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(OPENAPI_INDENT);
+        format!(
+            "{indent}{}:\n{indent}{indent}type: {}\n",
+            self.field_name(field.json_key),
+            field.type_name
+        )
+    }
+
+    fn format_arr_type(&self, _arr_type: String, _optional: bool) -> String {
+        String::from("array")
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => OPENAPI_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    OPENAPI_FLOAT
+                } else {
+                    OPENAPI_INT
+                }
+            }
+            Value::String(_) => OPENAPI_STRING,
+            Value::Null => OPENAPI_ANY,
+            // Non-primitives should not be passed to this function
+            _ => OPENAPI_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&self.opts.type_name(key))
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "#"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "yaml"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        OPENAPI_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &[
+            "components",
+            "schemas",
+            "type",
+            "properties",
+            "required",
+            "items",
+            "format",
+            "anyOf",
+            "$ref",
+        ]
+    }
+}
+
+// `text` as the value of an XML attribute, in double quotes
+fn xml_attribute(text: &str) -> String {
+    let escaped = text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('"', "&quot;");
+    format!("\"{escaped}\"")
+}
+
+impl Xsd {
+    // The type an element of `type_ref` is declared with. Arrays are repeated elements,
+    // arrays of arrays can't be told apart from them and are left untyped.
+    fn element_type(&self, type_ref: &TypeRef, type_name: &str) -> String {
+        let prefix = if self.opts.namespace.is_some() {
+            "tns:"
+        } else {
+            ""
+        };
+        match type_ref.innermost() {
+            _ if type_ref.array_depth() > 1 => String::from(XSD_ANY),
+            TypeRef::Object(_) => format!("{prefix}{type_name}"),
+            _ => type_name.to_string(),
+        }
+    }
+
+    // An element named `name`, repeated any number of times for arrays, which are nillable
+    // when they have nulls in them
+    fn element(
+        &self,
+        name: &str,
+        type_ref: &TypeRef,
+        type_name: &str,
+        nillable: bool,
+        optional: bool,
+    ) -> String {
+        let element_type = self.element_type(type_ref, type_name);
+        let nulls_in_array = match type_ref {
+            TypeRef::Array { element, optional } => *optional && element.array_depth() == 0,
+            _ => false,
+        };
+        let nillable = nillable || nulls_in_array;
+        let mut attributes = String::new();
+        if nillable {
+            attributes.push_str(" nillable=\"true\"");
+        }
+        if optional || type_ref.array_depth() > 0 {
+            attributes.push_str(" minOccurs=\"0\"");
+        }
+        if type_ref.array_depth() > 0 {
+            attributes.push_str(" maxOccurs=\"unbounded\"");
+        }
+        format!("<xs:element name=\"{name}\" type=\"{element_type}\"{attributes}/>")
+    }
+}
+
+impl LanguageFormatter for Xsd {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let indent = self.opts.indent_or(XSD_INDENT);
+        let type_name = self.struct_or_class_name(&raw);
+        format!("<xs:complexType name=\"{type_name}\">\n{indent}<xs:sequence>\n")
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        let indent = self.opts.indent_or(XSD_INDENT);
+        format!("{indent}</xs:sequence>\n</xs:complexType>")
+    }
+
+    // One schema, with the complex types first and an element for the document last. The
+    // namespace is its target namespace.
+    fn render_schema(&self, schema: &Schema) -> Option<String> {
+        let indent = self.opts.indent_or(XSD_INDENT);
+        let target = match self.opts.namespace.as_deref() {
+            Some(namespace) => format!(
+                " targetNamespace={0} xmlns:tns={0}",
+                xml_attribute(namespace)
+            ),
+            None => String::new(),
+        };
+        let mut types: Vec<String> = schema
+            .types
+            .iter()
+            .map(|type_def| render_type(type_def, self))
+            .collect();
+        let root_name = self.struct_or_class_name(
+            schema
+                .alias
+                .as_deref()
+                .or(schema.root_type().map(|root| root.key.as_str()))
+                .unwrap_or(XSD_AUTO_GENERATED),
+        );
+        // A root array is a type of its own, holding the elements
+        if schema.alias.is_some() {
+            let item_type = type_name(&schema.root, self);
+            let item = self.element("item", &schema.root, &item_type, false, false);
+            types.push(format!(
+                "<xs:complexType name=\"{root_name}\">\n{indent}<xs:sequence>\n\
+                 {indent}{indent}{item}\n{indent}</xs:sequence>\n</xs:complexType>"
+            ));
+        }
+        let prefix = if target.is_empty() { "" } else { "tns:" };
+        types.push(format!(
+            "<xs:element name=\"{root_name}\" type=\"{prefix}{root_name}\"/>"
+        ));
+        let body = types
+            .iter()
+            .flat_map(|text| text.split('\n'))
+            .map(|line| format!("{indent}{line}\n"))
+            .collect::<String>();
+        Some(format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <xs:schema xmlns:xs=\"http://www.w3.org/2001/XMLSchema\"{target} \
+             elementFormDefault=\"qualified\">\n{body}</xs:schema>"
+        ))
+    }
+
+    // Element names are the keys, with what XML names can't have replaced
+    fn field_name(&self, json_key: &str) -> String {
+        let name: String = self
+            .opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| String::from(json_key))
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || "_-.".contains(c) {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        if name.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+            name
+        } else {
+            format!("_{name}")
+        }
+    }
+
+    // Elements that may be null or missing are nillable and have a minOccurs of 0, see
+    // `Field::may_be_absent`
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(XSD_INDENT);
+        let strict = self.opts.strict_null;
+        let name = self.field_name(field.name_key);
+        let element = self.element(
+            &name,
+            field.type_ref,
+            field.type_name,
+            field.inferred.may_be_null(strict),
+            field.inferred.may_be_absent(strict),
+        );
+        format!("{indent}{indent}{element}\n")
+    }
+
+    fn format_arr_type(&self, arr_type: String, _optional: bool) -> String {
+        arr_type
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => XSD_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    XSD_FLOAT
+                } else {
+                    XSD_INT
+                }
+            }
+            Value::String(_) => XSD_STRING,
+            Value::Null => XSD_ANY,
+            // Non-primitives should not be passed to this function
+            _ => XSD_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&self.opts.type_name(key))
+    }
+
+    fn comment_prefix(&self) -> &'static str {
+        "<!--"
+    }
+
+    fn comment_suffix(&self) -> &'static str {
+        "-->"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "xsd"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        XSD_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &["xs:schema", "xs:complexType", "xs:sequence", "xs:element"]
+    }
+}
+
+impl Parquet {
+    // Declares the column `name` of `type_ref` at `depth`, objects are groups of their
+    // fields and arrays are LIST groups of their elements, as the format lays them out
+    fn write_column(
+        &self,
+        name: &str,
+        type_ref: &TypeRef,
+        optional: bool,
+        schema: &Schema,
+        depth: usize,
+        out: &mut String,
+    ) {
+        let indent = self.opts.indent_or(PARQUET_INDENT);
+        let pad = indent.repeat(depth);
+        // Untyped values may always be null
+        let repetition = if optional || type_ref == &TypeRef::Primitive(LogicalType::Any) {
+            "optional"
+        } else {
+            "required"
+        };
+        match type_ref {
+            TypeRef::Primitive(logical) => {
+                // The physical type, and how to read it
+                let (column_type, annotation) = match logical {
+                    LogicalType::String | LogicalType::Date | LogicalType::Uuid => {
+                        (PARQUET_STRING, " (STRING)")
+                    }
+                    LogicalType::Int => (PARQUET_INT, ""),
+                    LogicalType::TimestampMillis => (PARQUET_INT, " (TIMESTAMP(MILLIS,true))"),
+                    LogicalType::Float | LogicalType::Decimal => (PARQUET_FLOAT, ""),
+                    LogicalType::Bool => (PARQUET_BOOL, ""),
+                    LogicalType::Any => (PARQUET_ANY, " (JSON)"),
+                };
+                out.push_str(&format!(
+                    "{pad}{repetition} {column_type} {name}{annotation};\n"
+                ));
+            }
+            TypeRef::Object(key) => {
+                out.push_str(&format!("{pad}{repetition} group {name} {{\n"));
+                if let Some(type_def) = schema.types.iter().find(|type_def| &type_def.key == key) {
+                    self.write_fields(type_def, schema, depth + 1, out);
+                }
+                out.push_str(&format!("{pad}}}\n"));
+            }
+            TypeRef::Array { element, optional } => {
+                out.push_str(&format!("{pad}{repetition} group {name} (LIST) {{\n"));
+                out.push_str(&format!("{pad}{indent}repeated group list {{\n"));
+                self.write_column("element", element, *optional, schema, depth + 2, out);
+                out.push_str(&format!("{pad}{indent}}}\n{pad}}}\n"));
+            }
+        }
+    }
+
+    // Columns that may be null or missing are optional
+    fn write_fields(&self, type_def: &TypeDef, schema: &Schema, depth: usize, out: &mut String) {
+        let names = unique_name_keys(type_def, self);
+        for (field, name_key) in type_def.fields.iter().zip(&names) {
+            let optional = field.may_be_missing();
+            let name = self.field_name(name_key);
+            self.write_column(&name, &field.type_ref, optional, schema, depth, out);
+        }
+    }
+}
+
+impl LanguageFormatter for Parquet {
+    fn struct_or_class_header(&self, raw: String) -> String {
+        let message_name = self.struct_or_class_name(&raw);
+        format!("message {message_name} ") + "{\n"
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::from("}")
+    }
+
+    // A single message type, with nested objects as groups in it. The elements of a root
+    // array are in a list column.
+    fn render_schema(&self, schema: &Schema) -> Option<String> {
+        let mut text = String::new();
+        match (schema.alias.as_deref(), schema.root_type()) {
+            (None, Some(root)) => {
+                text.push_str(&self.struct_or_class_header(root.key.clone()));
+                self.write_fields(root, schema, 1, &mut text);
+            }
+            (alias, _) => {
+                let name = alias.unwrap_or(PARQUET_AUTO_GENERATED);
+                text.push_str(&self.struct_or_class_header(name.to_string()));
+                self.write_column("items", &schema.root, false, schema, 1, &mut text);
+            }
+        }
+        text.push_str(&self.struct_or_class_footer(None));
+        Some(text)
+    }
+
+    // Column names are the keys, with what the schema syntax uses itself replaced
+    fn field_name(&self, json_key: &str) -> String {
+        self.opts
+            .cased_field_name(json_key)
+            .unwrap_or_else(|| String::from(json_key))
+            .chars()
+            .map(|c| {
+                if c.is_whitespace() || c.is_control() || ";{}()=,".contains(c) {
+                    '_'
+                } else {
+                    c
+                }
+            })
+            .collect()
+    }
+
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(PARQUET_INDENT);
+        let name = self.field_name(field.name_key);
+        format!("{indent}required {} {name};\n", field.type_name)
+    }
+
+    fn format_arr_type(&self, arr_type: String, _optional: bool) -> String {
+        format!("group (LIST) {{ repeated group list {{ {arr_type} element; }} }}")
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => PARQUET_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    PARQUET_FLOAT
+                } else {
+                    PARQUET_INT
+                }
+            }
+            Value::String(_) => PARQUET_STRING,
+            Value::Null => PARQUET_ANY,
+            // Non-primitives should not be passed to this function
+            _ => PARQUET_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&self.opts.type_name(key))
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "schema"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        PARQUET_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &["message", "group", "required", "optional", "repeated"]
+    }
+}
+
+impl BigQuery {
+    // The columns of `type_def`, with the fields of nested records in them. Columns that
+    // may be null or missing are NULLABLE, the others REQUIRED.
+    fn columns(&self, type_def: &TypeDef, schema: &Schema) -> AvroJson {
+        let names = unique_name_keys(type_def, self);
+        let columns = type_def
+            .fields
+            .iter()
+            .zip(&names)
+            .map(|(field, name_key)| {
+                let nullable = field.may_be_missing();
+                self.column(
+                    &self.field_name(name_key),
+                    &field.type_ref,
+                    nullable,
+                    schema,
+                )
+            })
+            .collect();
+        AvroJson::Array(columns)
+    }
+
+    // Arrays are REPEATED columns of their elements. Arrays of arrays can't be, they're
+    // JSON columns, as are untyped values and empty objects, records need a field.
+    fn column(&self, name: &str, type_ref: &TypeRef, nullable: bool, schema: &Schema) -> AvroJson {
+        let record = match type_ref.innermost() {
+            TypeRef::Object(key) => schema.types.iter().find(|type_def| &type_def.key == key),
+            _ => None,
+        };
+        let untyped = record.is_some_and(|type_def| type_def.fields.is_empty());
+        let (element, mode) = match type_ref {
+            _ if untyped => (&TypeRef::Primitive(LogicalType::Any), "NULLABLE"),
+            TypeRef::Array { element, .. } if element.array_depth() == 0 => {
+                (element.as_ref(), "REPEATED")
+            }
+            TypeRef::Array { .. } | TypeRef::Primitive(LogicalType::Any) => {
+                (&TypeRef::Primitive(LogicalType::Any), "NULLABLE")
+            }
+            _ if nullable => (type_ref, "NULLABLE"),
+            _ => (type_ref, "REQUIRED"),
+        };
+        let column_type = match element {
+            TypeRef::Object(_) => "RECORD",
+            TypeRef::Primitive(LogicalType::TimestampMillis) => "TIMESTAMP",
+            element => &type_name(element, self),
+        };
+        let mut column = vec![
+            ("name", AvroJson::name(name)),
+            ("type", AvroJson::name(column_type)),
+            ("mode", AvroJson::name(mode)),
+        ];
+        if let Some(type_def) = record.filter(|_| column_type == "RECORD") {
+            column.push(("fields", self.columns(type_def, schema)));
+        }
+        AvroJson::Object(column)
+    }
+}
+
+impl LanguageFormatter for BigQuery {
+    fn struct_or_class_header(&self, _raw: String) -> String {
+        String::from("[\n")
+    }
+
+    fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+        String::from("]")
+    }
+
+    // The columns of a table with a row for the document, or for each of its elements
+    // when it's an array of objects
+    fn render_schema(&self, schema: &Schema) -> Option<String> {
+        let columns = match schema.root_type() {
+            Some(root) if schema.root.array_depth() <= 1 && !root.fields.is_empty() => {
+                self.columns(root, schema)
+            }
+            _ => AvroJson::Array(vec![self.column("items", &schema.root, false, schema)]),
+        };
+        let mut text = String::new();
+        columns.write(self.opts.indent_or(BIGQUERY_INDENT), 0, &mut text);
+        Some(text)
+    }
+
+    // Column names may only have letters, digits and underscores
+    fn field_name(&self, json_key: &str) -> String {
+        ascii_identifier(
+            &self
+                .opts
+                .cased_field_name(json_key)
+                .unwrap_or_else(|| String::from(json_key)),
+        )
+    }
+
+    fn format_field(&self, field: &FieldContext) -> String {
+        let indent = self.opts.indent_or(BIGQUERY_INDENT);
+        let name = quote(&self.field_name(field.name_key));
+        format!(
+            "{indent}{{\"name\": {name}, \"type\": {}}},\n",
+            quote(field.type_name)
+        )
+    }
+
+    fn format_arr_type(&self, arr_type: String, _optional: bool) -> String {
+        arr_type
+    }
+
+    fn premitive_type_name(&self, from: &Value) -> &'static str {
+        match from {
+            Value::Bool(_) => BIGQUERY_BOOL,
+            Value::Number(n) => {
+                if n.is_f64() {
+                    BIGQUERY_FLOAT
+                } else {
+                    BIGQUERY_INT
+                }
+            }
+            Value::String(_) => BIGQUERY_STRING,
+            Value::Null => BIGQUERY_ANY,
+            // Non-primitives should not be passed to this function
+            _ => BIGQUERY_ANY,
+        }
+    }
+
+    fn struct_or_class_name(&self, key: &str) -> String {
+        ascii_identifier(&self.opts.type_name(key))
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn default_type_name(&self) -> &'static str {
+        BIGQUERY_AUTO_GENERATED
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        &["name", "type", "mode", "fields"]
     }
 }
@@ -0,0 +1,43 @@
+// Generates types in several programming languages from sample JSON documents.
+// The `jsonc` binary is a command line interface over this, behind the default `cli` feature.
+
+pub mod constants;
+pub mod decompress;
+pub mod error;
+pub mod examples;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod generate;
+pub mod ignore;
+pub mod input;
+pub mod language;
+pub mod naming;
+pub mod options;
+pub mod plural;
+pub mod preprocess;
+pub mod registry;
+pub mod rename;
+pub mod sample;
+pub mod schema;
+pub mod select;
+pub mod shape;
+pub mod stats;
+pub mod streaming;
+#[cfg(feature = "template")]
+pub mod template;
+pub mod type_mapping;
+pub mod warnings;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use error::JsoncError;
+pub use generate::{
+    generate_code, generate_from_schema, generate_types, render, Generated, TypeOutput,
+};
+pub use language::{get_language_formatter, LanguageFormatter};
+pub use naming::{DefaultNaming, NamingStrategy};
+pub use options::{options_from_json, GeneratorOptions};
+pub use registry::register_language;
+pub use schema::{infer_schema, infer_schema_from, Schema};
+pub use shape::Shape;
+pub use warnings::{Warning, WarningCategory, Warnings};
@@ -1,183 +1,1020 @@
-pub mod constants;
-pub mod language;
+mod append;
+mod banner;
+mod cli;
+mod cli_error;
+mod config;
+mod diff;
+mod highlight;
+mod patterns;
+mod watch;
 
-use std::fs::File;
-use std::io::{self, BufRead, Error};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 use serde_json::Value;
 
-use constants::*;
-use language::*;
+use jsonc::constants::*;
+use jsonc::decompress::{decompress_reader, strip_compression_extension};
+use jsonc::generate::generate_from_schema;
+use jsonc::generate::write_types;
+use jsonc::input::*;
+use jsonc::language::*;
+use jsonc::preprocess::{replace_non_finite, NonFinite};
+use jsonc::schema::{check_root, schema_from_shape_named, shape_of, Schema};
+use jsonc::stats::Stats;
+use jsonc::streaming::parse_streaming;
+use jsonc::template::Template;
+use jsonc::warnings::{WarningCategory, Warnings};
+use jsonc::{GeneratorOptions, JsoncError, Shape};
 
-type StructValue = String;
-type ArrayType = String;
+use append::append_types;
+use banner::{banner, command_line, version};
+use cli::*;
+use cli_error::Error;
+use diff::unified_diff;
+use highlight::highlight;
+use patterns::expand_inputs;
+use watch::watch;
 
-fn infer_array(
-    key: Option<String>,
-    value: &Value,
-    structs_into: &mut Vec<StructValue>,
-    lang: Rc<dyn LanguageFormatter>,
-) -> ArrayType {
-    if let Value::Array(arr) = value {
-        let optional = arr.iter().any(Value::is_null);
+// A language as it was named on the command line, and its formatter
+type NamedFormatter = (String, Box<dyn LanguageFormatter>);
 
-        let non_null_values: Vec<&Value> = arr.iter().filter(|js| !js.is_null()).collect();
+// Generated code and the file it goes to, None for stdout
+type OutputFile = (Option<PathBuf>, String);
 
-        if non_null_values.is_empty() {
-            let null = Value::Null;
-            lang.format_arr_type(lang.premitive_type_name(&null).to_owned(), optional)
+fn usage(app: &str) {
+    eprintln!("usages of {app}:");
+    eprintln!("OPTIONS: \n\t[-l|--language]: Specify the output programming language, or several separated by commas");
+    eprintln!(
+        "\t--input-format:\tjson, jsonc or json5, guessed from the file extension by default"
+    );
+    eprintln!(
+        "\t--ndjson:\tread one JSON document per line and generate a type covering all of them"
+    );
+    eprintln!("\t--select:\tgenerate types for the value at this JSON pointer, e.g. /data/items");
+    eprintln!(
+        "\t--ignore-key KEY:\tleave out the fields under KEY wherever they are, can be repeated"
+    );
+    eprintln!("\t--ignore-path POINTER:\tleave out the field at this JSON pointer, * matching any key or element, e.g. /data/items/*/internal, can be repeated");
+    eprintln!("\t[-o|--output]:\twrite the generated code to this file instead of stdout");
+    eprintln!(
+        "\t--output-dir:\twrite the generated code to one file per language in this directory"
+    );
+    eprintln!("\t--append FILE:\tadd the types FILE doesn't define yet to the end of it, leaving the rest as is");
+    eprintln!("\t--template FILE:\tgenerate code with a Handlebars template, given the schema --emit-ir writes");
+    eprintln!("\t\t\t-l then picks the comment syntax and file extension of the output");
+    eprintln!("\t--watch:\tgenerate again whenever the input file changes, needs -o");
+    eprintln!("\t--with-examples:\tcomment every field with a value from the input, --with-examples=redact-strings only shows the length of strings");
+    eprintln!("\t--coerce-numeric-strings:\ttype strings that are numbers in every sample, e.g. \"19.99\", as numbers");
+    eprintln!("\t--flatten-wrappers[=keys=KEY,KEY]:\ttype fields holding an object with a single field, e.g. {{\"data\": [...]}}, as what it holds, only under the given keys if any");
+    eprintln!("\t--emit-sample:\tfollow the types with the input written as a value of them, e.g. for a first unit test");
+    eprintln!("\t--todo-comments:\tcomment fields whose type could not be inferred with a TODO");
+    eprintln!("\t--strict-null:\ttell fields missing from some array elements and fields null in some of them apart, typing each its own way, e.g. #[serde(default)] in Rust for missing ones");
+    eprintln!("\t--fail-on-any:\tfail when the type of some field could not be inferred, listing where they are, subtrees --best-effort typed as Any included");
+    eprintln!("\t--banner:\tstart the output with a comment saying it's generated, --no-banner to leave it out");
+    eprintln!("\t\t\tit says the version, the input and the options the code was generated with");
+    eprintln!("\t--reproducible:\tleave the timestamp out of the banner");
+    eprintln!("\t--separator:\ttext between type definitions, \\n and \\t are unescaped, a blank line by default");
+    eprintln!("\t--rename-map FILE:\tname fields after the names FILE gives their keys, a TOML table or JSON object like {{\"dob\": \"dateOfBirth\", \"/users/*/addr1\": \"addressLine1\"}}");
+    eprintln!("\t--root-name NAME:\tname the root type NAME, or its elements when it's an array, instead of e.g. Root, Model in Python or AutoGenerated in Go");
+    eprintln!("\t--type-prefix, --type-suffix:\tput around the name of every generated type, e.g. --type-prefix Api --type-suffix Dto");
+    eprintln!("\t--type-mapping FILE:\ttype fields as FILE says instead of as inferred, a TOML table or JSON object like {{\"id\": \"uuid\", \"/orders/*/amount\": \"decimal\"}}, out of string, int, float, bool, date, uuid, decimal, timestamp-millis and any");
+    eprintln!("\t--plural-map FILE:\tname the elements of arrays after the singulars FILE gives their keys, plural=singular lines like statuses=status");
+    eprintln!("\t--plural PLURAL=SINGULAR:\tlike a line of --plural-map, can be repeated");
+    eprintln!("\t--no-builtin-plurals:\tdon't name the elements of people, children and data arrays person, child and datum");
+    eprintln!("\t--ruby-style:\tstruct for Struct.new definitions, the default, or dry-struct for Dry::Struct classes with typed attributes");
+    eprintln!("\t--field-case, --type-case:\tname fields or types in snake, camel, pascal case or keep the keys as they are, fields named other than their key are annotated with it");
+    eprintln!("\t--namespace NAME:\tput every type in NAME, e.g. my.models, a module in Rust, an object in Scala and a package in Go and Java");
+    eprintln!("\t\t\t--wrap NAME does the same, --no-wrap-single leaves it out when there's a single type");
+    eprintln!("\t[-q|--quiet]:\tdon't print warnings, nor what was generated");
+    eprintln!("\t--warnings-as-errors:\tfail instead of generating when there are warnings, --warnings-as-errors=untyped,rename only when there are some of those, out of config, input, depth, skipped, untyped, rename, append and type-mapping");
+    eprintln!("\t--color:\tauto, always or never, colors are only used on terminals by default");
+    eprintln!("\t--config:\tread default options from this file instead of the nearest jsonc.toml");
+    eprintln!("\t--no-config:\tdon't read default options from a jsonc.toml file");
+    eprintln!("\t[-h|--help]:\tshow current window");
+    eprintln!("\t[-V|--version]:\tprint the version of {app}");
+    eprintln!("\t--list-languages:\tprint the supported languages and their aliases");
+    eprintln!(
+        "\t--max-depth N:\treject documents with arrays and objects nested more than N levels deep"
+    );
+    eprintln!("\t--jobs N:\tinfer types from long arrays on N threads, all cores by default");
+    eprintln!("\t--max-size BYTES:\trefuse to read inputs larger than BYTES");
+    eprintln!("\t--indent STRING:\tindent the generated code with STRING, e.g. \"  \"");
+    eprintln!("\t\t\tby default 4 spaces in Rust, Kotlin and Python, 2 in Dart, Protocol Buffers and GraphQL and tabs in other languages");
+    eprintln!("\t--indent-width N:\tindent the generated code with N spaces");
+    eprintln!("\t--indent-tabs:\tindent the generated code with tabs");
+    eprintln!("\t--no-annotations:\tleave out serialization annotations, e.g. struct tags in Go");
+    eprintln!("\t--stats:\tprint a summary of the inferred types to stderr");
+    eprintln!("\t--emit-ir:\twrite the inferred schema as JSON instead of code, its shape is described in src/schema.rs");
+    eprintln!("\t--graph:\twrite how the inferred types refer to each other as a Graphviz digraph instead of code");
+    eprintln!("\t--stats-json:\tprint the summary as a JSON object instead");
+    eprintln!("\t--streaming:\tparse the input as it's read instead of holding it in memory, for very large plain JSON documents");
+    eprintln!("\t--check:\tcompare the generated code with the -o or --output-dir files instead of writing them, printing a diff and failing if they differ");
+    eprintln!("\t\t\t--check=FILE compares with FILE instead, --check-loose ignores differences in whitespace");
+    eprintln!("\t--best-effort:\ttype subtrees deeper than --max-depth as Any and skip inputs --select finds nothing in,");
+    eprintln!("\t\t\tgenerating everything else and exiting with 4 instead of failing");
+    eprintln!("\t--lenient-numbers:\tread the NaN, Infinity and -Infinity some serializers write as floats, with a warning for each");
+    eprintln!("\t--merge:\tgenerate a single set of types covering all the input files");
+    eprintln!("\t--hidden:\tdon't skip hidden files and directories when expanding patterns");
+    eprintln!("\t{app} [FILE]...:\tread json files and convert to go structs, patterns like 'fixtures/**/*.json' are expanded");
+    eprintln!("\t\t\tgzip compressed input, e.g. dump.json.gz, is decompressed transparently");
+    eprintln!(
+        "\t[SOME_COMMAND] | {app}:\n\t\t\tpipe the result of the previous command into {app}"
+    );
+}
+
+fn list_languages() {
+    LANGUAGES.iter().for_each(|(id, aliases, _)| {
+        if aliases.is_empty() {
+            println!("{id}");
         } else {
-            let first_inferrable_value = non_null_values[0];
-            match first_inferrable_value {
-                Value::Array(_) => {
-                    let inner_arr_type =
-                        infer_array(key, first_inferrable_value, structs_into, Rc::clone(&lang));
-                    lang.format_arr_type(inner_arr_type, optional)
-                }
-                Value::Object(_) => {
-                    let struct_name = lang.struct_or_class_name(
-                        key.unwrap_or_else(|| String::from(GO_AUTO_GENERATED))
-                            .as_str(),
+            println!("{id} ({})", aliases.join(", "));
+        }
+    })
+}
+
+fn from_filepath(
+    filepath: &str,
+    args: &Args,
+    warnings: &mut Warnings,
+) -> Result<Parsed, JsoncError> {
+    let format = args
+        .input_format
+        .or_else(|| InputFormat::from_path(filepath))
+        .unwrap_or_default();
+    let file = fs::File::open(filepath).map_err(|e| JsoncError::io(Some(filepath), e))?;
+    if args.streaming {
+        return from_stream(BufReader::new(file), args, Some(filepath));
+    }
+    let bytes = read_input(file, args.max_size, Some(filepath))?;
+    let text = decode_input(bytes, Some(filepath))?;
+    parse_text(text, format, args, Some(filepath), warnings)
+}
+
+fn acquire_pipe(args: &Args, warnings: &mut Warnings) -> Result<Parsed, JsoncError> {
+    if args.streaming {
+        return from_stream(io::stdin().lock(), args, None);
+    }
+    let bytes = read_input(io::stdin().lock(), args.max_size, None)?;
+    let text = decode_input(bytes, None)?;
+    let format = args.input_format.unwrap_or_default();
+    parse_text(text, format, args, None, warnings)
+}
+
+// With --lenient-numbers, NaN and Infinity are read as floats first, with a warning for each
+fn parse_text(
+    text: String,
+    format: InputFormat,
+    args: &Args,
+    path: Option<&str>,
+    warnings: &mut Warnings,
+) -> Result<Parsed, JsoncError> {
+    if !args.lenient_numbers {
+        return parse_input(&text, format, args.ndjson, path);
+    }
+    let (text, found) = replace_non_finite(&text);
+    let mut found_warnings = Warnings::default();
+    for NonFinite {
+        literal,
+        line,
+        column,
+    } in found
+    {
+        found_warnings.push(
+            WarningCategory::Input,
+            format!("line {line}, column {column}: {literal} read as a float"),
+        );
+    }
+    warnings.extend(found_warnings.with_source(path.unwrap_or("stdin")));
+    parse_input(&text, format, args.ndjson, path)
+}
+
+// Parses the input as it's read, for documents too large to hold in memory.
+// Only plain JSON can be read this way.
+fn from_stream(
+    reader: impl BufRead,
+    args: &Args,
+    path: Option<&str>,
+) -> Result<Parsed, JsoncError> {
+    let reader = decompress_reader(reader, path)?;
+    parse_streaming(reader, args.ndjson, args.max_size, path).map(Parsed::Samples)
+}
+
+fn generator_options(args: &Args) -> GeneratorOptions {
+    let mut opts = GeneratorOptions::new().annotations(!args.no_annotations);
+    if let Some(indent) = &args.indent {
+        opts = opts.indent(indent);
+    }
+    if let Some(separator) = &args.separator {
+        opts = opts.separator(separator);
+    }
+    if let Some(jobs) = args.jobs {
+        opts = opts.jobs(jobs);
+    }
+    if let Some(namespace) = &args.namespace {
+        opts = opts.namespace(namespace);
+    }
+    if let Some(prefix) = &args.type_prefix {
+        opts = opts.type_prefix(prefix);
+    }
+    if let Some(suffix) = &args.type_suffix {
+        opts = opts.type_suffix(suffix);
+    }
+    if let Some(case) = args.field_case {
+        opts = opts.field_case(case);
+    }
+    if let Some(case) = args.type_case {
+        opts = opts.type_case(case);
+    }
+    if let Some(style) = args.ruby_style {
+        opts = opts.ruby_style(style);
+    }
+    if let Some(examples) = args.examples {
+        opts = opts.examples(examples);
+    }
+    if let Some(keys) = &args.flatten_wrappers {
+        opts = opts.flatten_wrappers(keys.clone());
+    }
+    opts.todo_comments(args.todo_comments)
+        .strict_null(args.strict_null)
+        .coerce_numeric_strings(args.coerce_numeric_strings)
+        .renames(args.renames.clone())
+        .type_mapping(args.type_mapping.clone())
+        .plurals(args.plurals.clone())
+        .emit_sample(args.emit_sample)
+        .wrap_single(!args.no_wrap_single)
+}
+
+// Each of the comma separated languages in `-l`, along with its formatter
+fn language_formatters(args: &Args) -> Result<Vec<NamedFormatter>, Error> {
+    let opts = generator_options(args);
+    args.language
+        .as_deref()
+        .unwrap_or(DEFAULT_LANG)
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| Ok((name.to_lowercase(), get_language_formatter(name, &opts)?)))
+        .collect()
+}
+
+// A parsed input document, along with the file it was read from, None for stdin
+struct Document {
+    source: Option<String>,
+    value: Value,
+    // What types are inferred from. Documents merged with --merge are merged shapes, and
+    // their value the one the shape stands for.
+    shape: Shape,
+    root_name: Option<String>,
+    // Output files in --output-dir are named after the input file
+    file_stem: String,
+}
+
+impl Document {
+    // The options the document's types are inferred with, its root named as selected
+    fn opts(&self, args: &Args) -> GeneratorOptions {
+        GeneratorOptions {
+            root_name: self.root_name.clone(),
+            ..generator_options(args)
+        }
+    }
+
+    // The types inferred from the document, the root named `default_name` unless it's
+    // named by the options
+    fn schema(&self, opts: &GeneratorOptions, default_name: &str) -> Result<Schema, JsoncError> {
+        schema_from_shape_named(&self.shape, Some(&self.value), opts, default_name)
+    }
+}
+
+fn file_stem(source: Option<&str>) -> String {
+    source
+        .map(strip_compression_extension)
+        .and_then(|source| Path::new(source).file_stem())
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("models")
+        .to_string()
+}
+
+// The code to generate from one document in one language
+struct Output<'a> {
+    document: &'a Document,
+    lang: &'a NamedFormatter,
+    // Generates the code instead of the language's formatter
+    template: Option<&'a Template>,
+    // Inferred once for every language the document is generated in
+    schema: Rc<Schema>,
+    opts: GeneratorOptions,
+    // Tells the output apart from the others when they're all written together
+    label: String,
+}
+
+// The documents read, and what went wrong reading them without stopping the run
+struct Documents {
+    documents: Vec<Document>,
+    warnings: Warnings,
+    // Subtrees typed as Any, and documents skipped, with --best-effort
+    degraded: usize,
+    // Fields left out with --ignore-key and --ignore-path
+    excluded: usize,
+}
+
+fn read_documents(args: &Args) -> Result<Documents, Error> {
+    let mut warnings = Warnings::default();
+    let mut degraded = 0;
+    let mut excluded = 0;
+    let inputs = expand_inputs(&args.inputs, args.hidden)?;
+    let values = if inputs.is_empty() {
+        vec![(None, acquire_pipe(args, &mut warnings)?)]
+    } else {
+        inputs
+            .into_iter()
+            .map(|path| {
+                let parsed = from_filepath(&path, args, &mut warnings)?;
+                Ok((Some(path), parsed))
+            })
+            .collect::<Result<Vec<_>, Error>>()?
+    };
+
+    let mut documents = vec![];
+    let mut skipped = None;
+    for (source, mut parsed) in values {
+        let display = source.as_deref().unwrap_or("stdin");
+        match args.max_depth {
+            Some(max_depth) if args.best_effort => {
+                let mut pruned = Warnings::default();
+                for pointer in parsed.prune_depth(max_depth) {
+                    degraded += 1;
+                    let message = format!(
+                        "{pointer} is nested more than {max_depth} levels deep, typed as Any"
                     );
-                    infer_struct(
-                        struct_name.clone(),
-                        first_inferrable_value,
-                        Rc::clone(&lang),
-                    )
-                    .iter()
-                    .for_each(|st| structs_into.push(st.to_owned()));
-                    lang.format_arr_type(struct_name, optional)
-                }
-                other => {
-                    lang.format_arr_type(lang.premitive_type_name(&other).to_owned(), optional)
+                    pruned.push_at(WarningCategory::Depth, pointer, message);
                 }
+                warnings.extend(pruned.with_source(display));
             }
+            Some(max_depth) => parsed.check_depth(max_depth, source.as_deref())?,
+            None => {}
         }
-    } else {
-        let null: Value = Value::Null;
-        lang.format_arr_type(lang.premitive_type_name(&null).to_string(), false)
-    }
-}
-
-fn infer_struct(
-    struct_name: String,
-    obj: &Value,
-    lang: Rc<dyn LanguageFormatter>,
-) -> Vec<StructValue> {
-    let mut result: Vec<StructValue> = vec![];
-    let mut struct_content: String = lang.struct_or_class_header(struct_name.clone());
-
-    if let Value::Object(o) = obj {
-        o.iter().for_each(|(json_key, json)| match json {
-            Value::Object(_) => {
-                let inner_struct = infer_struct(json_key.to_owned(), json, Rc::clone(&lang));
-                inner_struct.iter().for_each(|v| result.push(v.to_owned()));
-                struct_content.push_str(
-                    lang.format_field_type(
-                        &lang.struct_or_class_name(json_key),
-                        &lang.field_name(json_key),
-                    )
-                    .as_str(),
-                );
-            }
-            Value::Array(_) => {
-                let arr_type = infer_array(
-                    Some(json_key.to_owned()),
-                    json,
-                    &mut result,
-                    Rc::clone(&lang),
-                );
-                struct_content.push_str(lang.format_field_type(&arr_type, json_key).as_str());
+        excluded += parsed.ignore(&args.ignored);
+        let (parsed, root_name) = match &args.select {
+            Some(pointer) => match parsed.select(pointer) {
+                Ok(selected) => selected,
+                Err(e) if args.best_effort => {
+                    degraded += 1;
+                    let mut skip = Warnings::default();
+                    skip.push(WarningCategory::Skipped, format!("skipped, {e}"));
+                    warnings.extend(skip.with_source(display));
+                    skipped = Some(e);
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            },
+            None => (parsed, None),
+        };
+        // NDJSON lines and streamed input are already folded into a shape
+        let (shape, value) = match parsed {
+            Parsed::Document(value) => (shape_of(&value, &generator_options(args)), value),
+            Parsed::Samples(shape) => {
+                let value = shape.to_value();
+                (shape, value)
             }
-            other => struct_content.push_str(
-                lang.format_field_type(lang.premitive_type_name(other), json_key)
-                    .as_str(),
-            ),
+        };
+        documents.push(Document {
+            file_stem: file_stem(source.as_deref()),
+            source,
+            shape,
+            value,
+            root_name: args.root_name.clone().or(root_name),
         });
-        struct_content.push_str(
-            lang.struct_or_class_footer(Some(struct_name.clone()))
-                .as_str(),
-        );
     }
-    result.push(struct_content.to_owned());
-    result
+    // Nothing's left to generate from
+    if let (true, Some(e)) = (documents.is_empty(), skipped) {
+        return Err(e.into());
+    }
+
+    if args.merge && documents.len() > 1 {
+        documents = vec![merge_documents(documents)];
+    }
+    Ok(Documents {
+        documents,
+        warnings,
+        degraded,
+        excluded,
+    })
 }
 
-fn generate_types(value: Value, lang: Rc<dyn LanguageFormatter>) -> Vec<StructValue> {
-    let mut result: Vec<StructValue> = vec![];
-    match value {
-        Value::Array(_) => {
-            infer_array(None, &value, &mut result, lang);
-        }
-        Value::Object(_) => infer_struct(GO_AUTO_GENERATED.to_string(), &value, lang)
-            .iter()
-            .for_each(|s| result.push(s.to_owned())),
-        _ => {}
+// Every file is a sample of the same schema, so a single type covering all of them is
+// generated. Keys missing from some of the files are absent from the merged shape.
+fn merge_documents(documents: Vec<Document>) -> Document {
+    let sources: Vec<String> = documents.iter().filter_map(|d| d.source.clone()).collect();
+    let root_name = documents[0].root_name.clone();
+    let shape = documents
+        .into_iter()
+        .map(|d| d.shape)
+        .fold(Shape::Null, Shape::merge);
+    Document {
+        source: Some(sources.join(", ")),
+        value: shape.to_value(),
+        shape,
+        root_name,
+        file_stem: file_stem(None),
     }
-    result
 }
 
-fn usage(app: String) {
-    eprintln!("usages of {app}:");
-    eprintln!("OPTIONS: \n\t[-l|--language]: Specify the output programming language");
-    eprintln!("\t--help:\t\tshow current window");
-    eprintln!("\t{app} [FILE]:\tread json file and convert to go structs");
-    eprintln!(
-        "\t[SOME_COMMAND] | {app}:\n\t\t\tpipe the result of the previous command into {app}"
-    );
+// Every document is parsed once and its types inferred once, then code is generated from
+// them for every language. `schemas` are the ones inferred from each document with the
+// root named the default way. Also returns the number of types inferred from the documents.
+fn generate<'a>(
+    args: &Args,
+    documents: &'a [Document],
+    schemas: &[Rc<Schema>],
+    langs: &'a [NamedFormatter],
+    template: Option<&'a Template>,
+) -> Result<(Vec<Output<'a>>, usize), Error> {
+    let mut outputs = vec![];
+    let mut type_count = 0;
+    for (document, schema) in documents.iter().zip(schemas) {
+        let opts = document.opts(args);
+        type_count += schema.types.len() + usize::from(schema.alias.is_some());
+        // Languages naming the root alike share the schema, templates name it the default way
+        let mut schemas = vec![(DEFAULT_TYPE_NAME, Rc::clone(schema))];
+        for named in langs {
+            let name = &named.0;
+            let default_name = match template {
+                Some(_) => DEFAULT_TYPE_NAME,
+                None => named.1.default_type_name(),
+            };
+            let schema = match schemas.iter().find(|(named, _)| *named == default_name) {
+                Some((_, schema)) => Rc::clone(schema),
+                None => {
+                    let schema = Rc::new(document.schema(&opts, default_name)?);
+                    schemas.push((default_name, Rc::clone(&schema)));
+                    schema
+                }
+            };
+            let source = document.source.as_deref().unwrap_or("stdin");
+            let label = match (documents.len(), langs.len()) {
+                (1, _) => name.clone(),
+                (_, 1) => source.to_string(),
+                _ => format!("{source} ({name})"),
+            };
+            outputs.push(Output {
+                document,
+                lang: named,
+                template,
+                schema,
+                opts: opts.clone(),
+                label,
+            });
+        }
+    }
+    Ok((outputs, type_count))
 }
 
-fn from_filepath(
-    filepath: &str,
-    lang: Rc<dyn LanguageFormatter>,
-) -> Result<Vec<StructValue>, Error> {
-    let file = File::open(filepath)?;
-    let value: Value = serde_json::from_reader(file)?;
-    Ok(generate_types(value, lang))
+// Writes the code for `output` into `sink`
+fn render<W: Write + ?Sized>(output: &Output, args: &Args, sink: &mut W) -> Result<(), JsoncError> {
+    let lang = output.lang.1.as_ref();
+    if args.banner {
+        let source = output.document.source.as_deref().unwrap_or("stdin");
+        writeln!(
+            sink,
+            "{}",
+            banner(lang, source, &command_line(), args.reproducible)
+        )
+        .map_err(|source| JsoncError::Write { source })?;
+    }
+    let Some(template) = output.template else {
+        let value = &output.document.value;
+        return write_types(&output.schema, value, lang, &output.opts, sink).map(|_| ());
+    };
+    let (code, _) = template.render(&output.schema, &output.opts)?;
+    sink.write_all(code.as_bytes())
+        .map_err(|source| JsoncError::Write { source })
 }
 
-fn acquire_pipe(lang: Rc<dyn LanguageFormatter>) -> Vec<StructValue> {
-    let stdin = io::stdin().lock();
+fn render_to_string(output: &Output, args: &Args) -> Result<String, Error> {
+    let mut text = vec![];
+    render(output, args, &mut text)?;
+    Ok(String::from_utf8_lossy(&text).into_owned())
+}
 
-    let all_lines = stdin.lines().fold(String::new(), |mut buff, line| {
-        buff.push_str(line.unwrap().as_str());
-        buff
-    });
+// Streams the code for `output` into the file at `path`, or stdout if None
+fn write_rendered(output: &Output, args: &Args, path: Option<&Path>) -> Result<(), Error> {
+    let result = match path {
+        Some(path) => {
+            let file = File::create(path).map_err(|e| Error::write(path, e))?;
+            let mut sink = BufWriter::new(file);
+            render(output, args, &mut sink)
+                .and_then(|_| sink.flush().map_err(|source| JsoncError::Write { source }))
+        }
+        None => render(output, args, &mut io::stdout().lock()),
+    };
+    result.map_err(|e| match e {
+        JsoncError::Write { source } => Error::write(path.unwrap_or(Path::new("stdout")), source),
+        other => other.into(),
+    })
+}
 
-    let value: Value = serde_json::from_str(all_lines.as_str()).unwrap();
-    generate_types(value, lang)
+// Output files in --output-dir are named after the input file and the language
+fn output_path(dir: &str, output: &Output) -> PathBuf {
+    let file_name = format!(
+        "{}.{}",
+        output.document.file_stem,
+        output.lang.1.file_extension()
+    );
+    Path::new(dir).join(file_name)
 }
 
-fn main() {
-    // first argument is usually the application name
-    let result = if std::env::args().len() > 1 {
-        match std::env::args().nth(1).unwrap().as_str() {
-            "--help" => {
-                usage(std::env::args().nth(0).unwrap());
-                std::process::exit(0);
+// Writes the generated code, returning a short description of where it went and how many
+// types were inferred, `type_count`
+fn write_output(args: &Args, outputs: &[Output], type_count: usize) -> Result<String, Error> {
+    if args.check {
+        let files = output_files(args, outputs, false)?;
+        return check_output(args, &files);
+    }
+
+    // Files never get colors, only a terminal does
+    let color = args.output.is_none() && args.output_dir.is_none() && args.color.use_color();
+    match (&args.output_dir, outputs) {
+        (Some(dir), _) => {
+            fs::create_dir_all(dir).map_err(|e| Error::write(Path::new(dir), e))?;
+            for output in outputs {
+                write_rendered(output, args, Some(&output_path(dir, output)))?;
             }
-            "-l" | "--language" => {
-                let lang = std::env::args()
-                    .nth(2)
-                    .expect("Programming language not specified");
-                let lang_specifier = get_language_formatter(lang.as_str())
-                    .expect("Couldn't find the language specifier");
-
-                if let Some(filepath) = std::env::args().nth(3) {
-                    from_filepath(&filepath, lang_specifier).unwrap()
-                } else {
-                    acquire_pipe(lang_specifier)
+        }
+        // Nothing to do to the code after it's generated, it goes straight to where it's written
+        (None, [output]) if !color => {
+            write_rendered(output, args, args.output.as_deref().map(Path::new))?;
+        }
+        _ => {
+            for (path, text) in output_files(args, outputs, color)? {
+                match path {
+                    Some(path) => {
+                        fs::write(&path, text).map_err(|e| Error::write(&path, e))?;
+                    }
+                    None => print!("{text}"),
                 }
             }
-            filepath => {
-                from_filepath(filepath, get_language_formatter(DEFAULT_LANG).unwrap()).unwrap()
+        }
+    }
+
+    match args.output_dir.as_ref().or(args.output.as_ref()) {
+        Some(destination) => Ok(format!(
+            "generated {} into {destination}",
+            counted(type_count, "type")
+        )),
+        None => Ok(format!("generated {}", counted(type_count, "type"))),
+    }
+}
+
+// The files generated code goes to, one per output in --output-dir, otherwise a single
+// one with all the outputs, or None for stdout
+fn output_files(args: &Args, outputs: &[Output], color: bool) -> Result<Vec<OutputFile>, Error> {
+    if let Some(dir) = &args.output_dir {
+        let mut files = vec![];
+        for output in outputs {
+            files.push((
+                Some(output_path(dir, output)),
+                render_to_string(output, args)?,
+            ));
+        }
+        return Ok(files);
+    }
+
+    let paint = |text: String, lang: &dyn LanguageFormatter| {
+        if color {
+            highlight(&text, lang)
+        } else {
+            text
+        }
+    };
+
+    // Several outputs end up together, each section is labeled with a comment
+    let mut sections = vec![];
+    for output in outputs {
+        let lang = output.lang.1.as_ref();
+        let code = render_to_string(output, args)?;
+        let section = match outputs {
+            [_] => code,
+            _ => format!("{}\n\n{code}", lang.comment(&output.label)),
+        };
+        sections.push(paint(section, lang));
+    }
+    let text = sections.join("\n");
+    Ok(vec![(args.output.as_ref().map(PathBuf::from), text)])
+}
+
+// Writes the schema inferred from each document as JSON, several documents make an array
+// of schemas with the file each one came from
+fn write_schemas(
+    args: &Args,
+    documents: &[Document],
+    inferred: &[Rc<Schema>],
+) -> Result<String, Error> {
+    let mut schemas = vec![];
+    for (document, schema) in documents.iter().zip(inferred) {
+        let mut schema = schema.to_json();
+        if documents.len() > 1 {
+            schema["source"] = Value::from(document.source.as_deref());
+        }
+        schemas.push(schema);
+    }
+    let json = match schemas.len() {
+        1 => schemas.remove(0),
+        _ => Value::Array(schemas),
+    };
+    // Pretty printing a Value can't fail
+    let text = serde_json::to_string_pretty(&json).unwrap_or_default() + "\n";
+    write_text(args, &text, "the schema")
+}
+
+// Writes the type graph of each document in DOT, one digraph after the other
+fn write_graphs(
+    args: &Args,
+    documents: &[Document],
+    schemas: &[Rc<Schema>],
+) -> Result<String, Error> {
+    let mut text = String::new();
+    for (document, schema) in documents.iter().zip(schemas) {
+        text.push_str(&schema.to_dot(&document.file_stem));
+    }
+    write_text(args, &text, "the type graph")
+}
+
+// Writes `text`, which isn't code, to the -o file or stdout
+fn write_text(args: &Args, text: &str, what: &str) -> Result<String, Error> {
+    match &args.output {
+        Some(path) => {
+            fs::write(path, text).map_err(|e| Error::write(Path::new(path), e))?;
+            Ok(format!("wrote {what} to {path}"))
+        }
+        None => {
+            print!("{text}");
+            Ok(format!("wrote {what}"))
+        }
+    }
+}
+
+// Compares the generated code with the files it would be written to, printing a diff of
+// every file that's out of date
+fn check_output(args: &Args, files: &[OutputFile]) -> Result<String, Error> {
+    let mut out_of_date = 0;
+    for (path, generated) in files {
+        let path = match (&args.check_file, path) {
+            (Some(check_file), _) if args.output_dir.is_none() => PathBuf::from(check_file),
+            (_, Some(path)) => path.clone(),
+            _ => {
+                return Err(Error::Usage(String::from(
+                    "--check needs a file to compare with, pass --check=FILE or -o FILE",
+                )))
             }
+        };
+        let display = path.display().to_string();
+        // A file that doesn't exist yet is as out of date as it gets
+        let existing = match fs::read_to_string(&path) {
+            Ok(existing) => existing,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(JsoncError::io(Some(&display), e).into()),
+        };
+
+        let same = if args.check_loose {
+            existing.split_whitespace().eq(generated.split_whitespace())
+        } else {
+            existing == *generated
+        };
+        if !same {
+            out_of_date += 1;
+            print!(
+                "{}",
+                unified_diff(
+                    &existing,
+                    generated,
+                    &display,
+                    &format!("{display} (generated)")
+                )
+            );
         }
-    } else {
-        acquire_pipe(get_language_formatter(DEFAULT_LANG).unwrap())
+    }
+
+    if out_of_date > 0 {
+        return Err(Error::OutOfDate { files: out_of_date });
+    }
+    Ok(format!("{} up to date", counted(files.len(), "file")))
+}
+
+// Generates code for every input in every language and writes it out
+fn generate_and_write(
+    args: &Args,
+    langs: &[NamedFormatter],
+    template: Option<&Template>,
+) -> Result<String, Error> {
+    let Documents {
+        documents,
+        warnings: read_warnings,
+        degraded,
+        excluded,
+    } = read_documents(args)?;
+    let mut warnings = args.warnings.clone();
+    warnings.extend(read_warnings);
+    // Where the fields typed as Any are, in every document. Subtrees --best-effort typed
+    // as Any are among them.
+    let mut untyped = vec![];
+    // Renames and type mappings that match nothing in any of the documents
+    let mut unused_renames: Option<Vec<String>> = None;
+    let mut unused_mappings: Option<Vec<String>> = None;
+    let mut stats = Stats {
+        degraded,
+        excluded,
+        ..Stats::default()
     };
+    // Inferred once for every document, and shared by everything below. Unsupported roots
+    // are found out before anything is written.
+    let mut schemas = vec![];
+    for document in &documents {
+        check_root(&document.value)?;
+        schemas.push(Rc::new(
+            document.schema(&document.opts(args), DEFAULT_TYPE_NAME)?,
+        ));
+    }
+    for (document, schema) in documents.iter().zip(&schemas) {
+        let source = document.source.as_deref().unwrap_or("stdin");
+        stats.collect(schema);
+        for (path, _) in schema.untyped_fields() {
+            let what = if path.is_empty() {
+                "the elements of the document"
+            } else {
+                path.as_str()
+            };
+            untyped.push(format!("{source}: {what}"));
+        }
+        warnings.extend(schema.warnings().with_source(source));
+        unused_renames = Some(still_unused(unused_renames, args.renames.unused(schema)));
+        unused_mappings = Some(still_unused(
+            unused_mappings,
+            args.type_mapping.unused(schema),
+        ));
+    }
+    for entry in unused_renames.unwrap_or_default() {
+        let message = format!("--rename-map: '{entry}' doesn't match any key in the input");
+        if entry.starts_with('/') {
+            warnings.push_at(WarningCategory::Rename, entry.clone(), message);
+        } else {
+            warnings.push(WarningCategory::Rename, message);
+        }
+    }
+    for entry in unused_mappings.unwrap_or_default() {
+        let message = format!("--type-mapping: '{entry}' doesn't match any key in the input");
+        if entry.starts_with('/') {
+            warnings.push_at(WarningCategory::TypeMapping, entry.clone(), message);
+        } else {
+            warnings.push(WarningCategory::TypeMapping, message);
+        }
+    }
+    if args.emit_ir || args.graph {
+        print_stats(args, stats, &warnings);
+        report(args, &warnings)?;
+        fail_on_any(args, untyped)?;
+        let summary = if args.emit_ir {
+            write_schemas(args, &documents, &schemas)?
+        } else {
+            write_graphs(args, &documents, &schemas)?
+        };
+        return partial_success(with_warnings(summary, &warnings), degraded);
+    }
+    let (outputs, type_count) = generate(args, &documents, &schemas, langs, template)?;
+    let appended = match &args.append {
+        Some(path) => Some(append_output(&outputs, path, &mut warnings)?),
+        None => None,
+    };
+    print_stats(args, stats, &warnings);
+    report(args, &warnings)?;
+    fail_on_any(args, untyped)?;
+    let summary = match appended {
+        Some(appended) => write_appended(args, appended)?,
+        None => write_output(args, &outputs, type_count)?,
+    };
+    partial_success(with_warnings(summary, &warnings), degraded)
+}
 
-    println!("{}", &result[0]);
-    result[1..].iter().for_each(|s| {println!("\n{s}");})
+// With --stats, prints what was inferred from the documents and what was warned about
+fn print_stats(args: &Args, mut stats: Stats, warnings: &Warnings) {
+    if let Some(format) = args.stats {
+        stats.warnings = warnings.iter().map(|warning| warning.to_string()).collect();
+        stats.print(format);
+    }
+}
+
+// Entries unused in every document so far, `unused` being the ones unused in the last one
+fn still_unused(before: Option<Vec<String>>, unused: Vec<String>) -> Vec<String> {
+    match before {
+        Some(before) => before.into_iter().filter(|e| unused.contains(e)).collect(),
+        None => unused,
+    }
+}
+
+// With --fail-on-any, fails the run if there's any field typed as Any, before anything
+// is written
+fn fail_on_any(args: &Args, untyped: Vec<String>) -> Result<(), Error> {
+    if args.fail_on_any && !untyped.is_empty() {
+        return Err(Error::UntypedFields { fields: untyped });
+    }
+    Ok(())
+}
+
+// `count` of `noun`, e.g. 1 type or 2 types
+fn counted(count: usize, noun: &str) -> String {
+    match count {
+        1 => format!("1 {noun}"),
+        _ => format!("{count} {noun}s"),
+    }
+}
+
+// The summary of what was written, along with how many warnings there were if any
+fn with_warnings(summary: String, warnings: &Warnings) -> String {
+    match warnings.len() {
+        0 => summary,
+        count => format!("{summary}, {}", counted(count, "warning")),
+    }
+}
+
+// Everything was written, but with --best-effort some of it may have been typed as Any
+fn partial_success(summary: String, degraded: usize) -> Result<String, Error> {
+    match degraded {
+        0 => Ok(summary),
+        _ => Err(Error::Degraded { parts: degraded }),
+    }
+}
+
+// The --append file with the types it doesn't define yet added, warning about the ones
+// it defines differently. Returns the file, its new text and the number of types added.
+fn append_output(
+    outputs: &[Output],
+    path: &str,
+    warnings: &mut Warnings,
+) -> Result<(String, String, usize), Error> {
+    let [Output {
+        lang: (name, lang),
+        schema,
+        opts,
+        ..
+    }] = outputs
+    else {
+        return Err(Error::Usage(String::from(
+            "--append needs a single input, or --merge, and a single language",
+        )));
+    };
+    // A file that doesn't exist yet gets every type
+    let existing = match fs::read_to_string(path) {
+        Ok(existing) => existing,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(JsoncError::io(Some(path), e).into()),
+    };
+    if lang.render_schema(schema).is_some() {
+        return Err(Error::Usage(format!(
+            "--append can't add types to a single {name} document"
+        )));
+    }
+    let generated = generate_from_schema(schema, lang.as_ref());
+    let appended = append_types(&existing, &generated, lang.as_ref(), &opts.separator);
+    for name in appended.conflicts {
+        warnings.push(
+            WarningCategory::Append,
+            format!("{path}: {name} is already defined differently, leaving it as it is"),
+        );
+    }
+    Ok((path.to_string(), appended.text, appended.added))
+}
+
+// Writes the --append file, or compares it with what it would be with --check
+fn write_appended(
+    args: &Args,
+    (path, text, added): (String, String, usize),
+) -> Result<String, Error> {
+    if args.check {
+        return check_output(args, &[(Some(PathBuf::from(path)), text)]);
+    }
+    if added == 0 {
+        return Ok(format!("{path} already has every type"));
+    }
+    fs::write(&path, text).map_err(|e| Error::write(Path::new(&path), e))?;
+    Ok(format!("appended {} to {path}", counted(added, "type")))
+}
+
+// Prints warnings to stderr, failing the run instead if any of them are to be treated as
+// errors, which are printed even with --quiet
+fn report(args: &Args, warnings: &Warnings) -> Result<(), Error> {
+    if warnings.is_empty() {
+        return Ok(());
+    }
+    let errors = match &args.warnings_as_errors {
+        Some(categories) => warnings.count_in(categories),
+        None => 0,
+    };
+    if !args.quiet || errors > 0 {
+        warnings.print();
+    }
+    if errors > 0 {
+        return Err(Error::Warnings { warnings: errors });
+    }
+    Ok(())
+}
+
+fn run(app: &str) -> Result<(), Error> {
+    let args = match parse_args(std::env::args().skip(1))? {
+        Command::Help => {
+            usage(app);
+            return Ok(());
+        }
+        Command::Version => {
+            println!("{}", version());
+            return Ok(());
+        }
+        Command::ListLanguages => {
+            list_languages();
+            return Ok(());
+        }
+        Command::Generate(args) => args,
+    };
+
+    // Nothing is being piped in, waiting on stdin would just look like a hang
+    if args.inputs.is_empty() && io::stdin().is_terminal() {
+        usage(app);
+        std::process::exit(2);
+    }
+
+    if args.streaming && args.lenient_numbers {
+        return Err(Error::Usage(String::from(
+            "--lenient-numbers can't be used with --streaming",
+        )));
+    }
+
+    if args.streaming && args.input_format == Some(InputFormat::Json5) {
+        return Err(Error::Usage(String::from(
+            "--streaming only supports JSON input, not JSON5",
+        )));
+    }
+
+    if args.template.is_some() && args.namespace.is_some() {
+        return Err(Error::Usage(String::from(
+            "--namespace can't be used with --template, the template can put the types in one",
+        )));
+    }
+
+    if args.append.is_some() && args.namespace.is_some() {
+        return Err(Error::Usage(String::from(
+            "--append can't add types inside a --namespace",
+        )));
+    }
+
+    if args.append.is_some()
+        && (args.output.is_some() || args.output_dir.is_some() || args.template.is_some())
+    {
+        return Err(Error::Usage(String::from(
+            "--append writes to its own file, it can't be used with -o, --output-dir or --template",
+        )));
+    }
+
+    let langs = language_formatters(&args)?;
+    let template = match &args.template {
+        Some(path) => Some(Template::from_file(path)?),
+        None => None,
+    };
+
+    if args.watch {
+        let [input] = args.inputs.as_slice() else {
+            return Err(Error::Usage(String::from(
+                "--watch needs exactly one input file",
+            )));
+        };
+        if args.output.is_none() && args.output_dir.is_none() && args.append.is_none() {
+            return Err(Error::Usage(String::from(
+                "--watch needs an output file (-o, --append) or directory (--output-dir)",
+            )));
+        }
+        return watch(Path::new(input), || {
+            generate_and_write(&args, &langs, template.as_ref())
+        });
+    }
+
+    let summary = generate_and_write(&args, &langs, template.as_ref())?;
+    if !args.quiet {
+        eprintln!("{summary}");
+    }
+    Ok(())
+}
+
+fn main() {
+    // first argument is usually the application name
+    let app = std::env::args()
+        .next()
+        .unwrap_or_else(|| String::from("jsonc"));
+
+    if let Err(e) = run(&app) {
+        eprintln!("{app}: {e}");
+        if e.is_usage() {
+            eprintln!("Try '{app} --help' for more information.");
+        }
+        std::process::exit(e.exit_code());
+    }
 }
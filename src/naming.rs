@@ -0,0 +1,152 @@
+use std::fmt::Debug;
+
+// How generated types and fields are named after JSON keys. Formatters ask the strategy in
+// their options instead of deciding for themselves, so names can be changed, e.g. prefixed
+// or with abbreviations spelled out, without writing a formatter.
+// Every method defaults to the way the built-in languages name things.
+pub trait NamingStrategy: Debug + Send + Sync {
+    // Name of the type generated for an object under `key`
+    fn type_name(&self, key: &str) -> String {
+        pascal_case(key)
+    }
+
+    // Name of a field in languages whose fields start with a lowercase letter
+    fn field_name(&self, key: &str) -> String {
+        camelcase(key)
+    }
+
+    // Name of a field in languages where it has to start with an uppercase letter to be
+    // visible, e.g. Go
+    fn exported_field_name(&self, key: &str) -> String {
+        pascal_case(key)
+    }
+
+    // What an array under `key` holds one of, element types of a root array are named after it
+    fn singular(&self, key: &str) -> String {
+        if let Some(stripped) = key.strip_suffix("ies") {
+            format!("{stripped}y")
+        } else {
+            key.strip_suffix('s').unwrap_or(key).to_string()
+        }
+    }
+}
+
+// snake_case keys in PascalCase for types, camelCase for fields
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultNaming;
+
+impl NamingStrategy for DefaultNaming {}
+
+// How names are written whatever the language usually does, for --field-case and --type-case
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Case {
+    Snake,
+    Camel,
+    Pascal,
+    // The key as it is
+    Keep,
+}
+
+impl Case {
+    pub fn from_name(name: &str) -> Option<Case> {
+        match name.to_lowercase().as_str() {
+            "snake" => Some(Case::Snake),
+            "camel" => Some(Case::Camel),
+            "pascal" => Some(Case::Pascal),
+            "keep" => Some(Case::Keep),
+            _ => None,
+        }
+    }
+
+    // `key` written in this case. Acronyms are words like any other, e.g. userID is user_id
+    // in snake case and UserId in Pascal case.
+    pub fn apply(self, key: &str) -> String {
+        let words = words(key);
+        match self {
+            Case::Keep => key.to_string(),
+            Case::Snake => words
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Case::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(index, word)| match index {
+                    0 => word.to_lowercase(),
+                    _ => first_char_upper(&word.to_lowercase()),
+                })
+                .collect(),
+            Case::Pascal => words
+                .iter()
+                .map(|word| first_char_upper(&word.to_lowercase()))
+                .collect(),
+        }
+    }
+}
+
+// The words of a key, split at anything but letters and digits and where the case changes.
+// A run of capitals is a word of its own, but for the last one when it starts the next
+// word, e.g. HTTPServer is HTTP and Server.
+fn words(key: &str) -> Vec<String> {
+    let chars: Vec<char> = key.chars().collect();
+    let mut words = vec![];
+    let mut word = String::new();
+    for (index, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        let previous = index.checked_sub(1).map(|i| chars[i]);
+        let next = chars.get(index + 1);
+        let starts_word = c.is_uppercase()
+            && match previous {
+                Some(previous) if previous.is_lowercase() || previous.is_ascii_digit() => true,
+                Some(previous) if previous.is_uppercase() => next.is_some_and(|n| n.is_lowercase()),
+                _ => false,
+            };
+        if starts_word && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+pub(crate) fn first_char_upper(s: &str) -> String {
+    let mut c = s.chars();
+    match c.next() {
+        None => String::new(),
+        Some(ch) => c.fold(ch.to_uppercase().to_string(), |mut buff, ch| {
+            buff.push(ch);
+            buff
+        }),
+    }
+}
+
+pub(crate) fn first_char_lower(s: &str) -> String {
+    let mut c = s.chars();
+    match c.next() {
+        None => String::new(),
+        Some(ch) => ch.to_lowercase().chain(c).collect(),
+    }
+}
+
+// snake_case keys in PascalCase, the way most languages name types
+pub fn pascal_case(snake_case: &str) -> String {
+    snake_case.split('_').map(first_char_upper).collect()
+}
+
+pub fn camelcase(snake_case: &str) -> String {
+    let mut split = snake_case.split('_');
+    let mut first = String::from(split.next().unwrap_or("Unknown"));
+    for w in split {
+        first.push_str(first_char_upper(w).as_str());
+    }
+    first
+}
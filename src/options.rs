@@ -0,0 +1,401 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::error::JsoncError;
+use crate::examples::ExampleMode;
+use crate::naming::{Case, DefaultNaming, NamingStrategy};
+use crate::plural::PluralMap;
+use crate::rename::RenameMap;
+use crate::shape::Shape;
+use crate::type_mapping::TypeMapping;
+
+// Generated code is indented with tabs unless some other `indent` is given, or the
+// language is usually indented some other way
+pub static DEFAULT_INDENT: &str = "\t";
+
+// Type definitions are separated by a blank line unless some other `separator` is given
+pub static DEFAULT_SEPARATOR: &str = "\n\n";
+
+// What Ruby types are generated as
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RubyStyle {
+    // Struct.new with keyword arguments, typed in comments
+    #[default]
+    Struct,
+    // Dry::Struct classes with typed attributes
+    DryStruct,
+}
+
+impl RubyStyle {
+    pub fn from_name(name: &str) -> Option<RubyStyle> {
+        match name.to_lowercase().as_str() {
+            "struct" => Some(RubyStyle::Struct),
+            "dry-struct" | "dry_struct" => Some(RubyStyle::DryStruct),
+            _ => None,
+        }
+    }
+}
+
+// Settings for generating types, shared by the traversal of the document and the
+// formatters, which get their own copy from `get_language_formatter`.
+// The defaults generate types named the way each language usually has them, with the root
+// named after what the language calls it, e.g. Root, or Model in Python.
+#[derive(Clone, Debug)]
+pub struct GeneratorOptions {
+    // Name of the root type, or of its elements if the root is an array
+    pub root_name: Option<String>,
+    // One level of indentation, None for the one usual in the language
+    pub indent: Option<String>,
+    // Serialization annotations, e.g. struct tags in Go
+    pub annotations: bool,
+    // Comment every field with a sample value from the input
+    pub examples: Option<ExampleMode>,
+    // Strings that are numbers in every sample, e.g. "19.99", are typed as numbers, with a
+    // note that they're sent as strings
+    pub coerce_numeric_strings: bool,
+    // Comment fields whose type couldn't be inferred with a TODO
+    pub todo_comments: bool,
+    // Fields missing from some samples and fields null in some of them are told apart, each
+    // typed the way the language does it, e.g. with a serde default in Rust. Without it a
+    // field that may be either is typed as both.
+    pub strict_null: bool,
+    // Follow the types with the input written as a value of them
+    pub emit_sample: bool,
+    // Text between type definitions when they're rendered together
+    pub separator: String,
+    // Every type is put in this namespace, e.g. a module in Rust or a package in Go
+    pub namespace: Option<String>,
+    // Whether the namespace is still used when there's only one type to put in it
+    pub wrap_single: bool,
+    // Threads inferring types from long arrays, all cores if None. Only used with the
+    // `parallel` feature.
+    pub jobs: Option<usize>,
+    // How types and fields are named after JSON keys
+    pub naming: Arc<dyn NamingStrategy>,
+    // Put around the name of every generated type, e.g. Api and Dto for ApiUserDto
+    pub type_prefix: String,
+    pub type_suffix: String,
+    // Cases fields and types are named in instead of the ones `naming` and the language
+    // would use. Fields named other than their key are annotated with it.
+    pub field_case: Option<Case>,
+    pub type_case: Option<Case>,
+    // Names given to keys instead of the ones made from them
+    pub renames: RenameMap,
+    // Types given to fields instead of the ones inferred for them
+    pub type_mapping: TypeMapping,
+    // Singulars of plural keys, the elements of arrays under them are named after those
+    pub plurals: PluralMap,
+    // Fields whose value is an object with a single field, e.g. {"data": [...]}, are typed
+    // as what that field holds. Only objects whose key is one of these are, or any if
+    // there are none.
+    pub flatten_wrappers: Option<Vec<String>>,
+    // What Ruby types are generated as
+    pub ruby_style: RubyStyle,
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        GeneratorOptions {
+            root_name: None,
+            indent: None,
+            annotations: true,
+            examples: None,
+            coerce_numeric_strings: false,
+            todo_comments: false,
+            strict_null: false,
+            emit_sample: false,
+            separator: String::from(DEFAULT_SEPARATOR),
+            namespace: None,
+            wrap_single: true,
+            jobs: None,
+            naming: Arc::new(DefaultNaming),
+            type_prefix: String::new(),
+            type_suffix: String::new(),
+            field_case: None,
+            type_case: None,
+            renames: RenameMap::default(),
+            type_mapping: TypeMapping::default(),
+            plurals: PluralMap::default(),
+            flatten_wrappers: None,
+            ruby_style: RubyStyle::default(),
+        }
+    }
+}
+
+impl GeneratorOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn root_name(mut self, root_name: impl Into<String>) -> Self {
+        self.root_name = Some(root_name.into());
+        self
+    }
+
+    pub fn indent(mut self, indent: impl Into<String>) -> Self {
+        self.indent = Some(indent.into());
+        self
+    }
+
+    pub fn annotations(mut self, annotations: bool) -> Self {
+        self.annotations = annotations;
+        self
+    }
+
+    pub fn examples(mut self, examples: ExampleMode) -> Self {
+        self.examples = Some(examples);
+        self
+    }
+
+    pub fn coerce_numeric_strings(mut self, coerce_numeric_strings: bool) -> Self {
+        self.coerce_numeric_strings = coerce_numeric_strings;
+        self
+    }
+
+    pub fn todo_comments(mut self, todo_comments: bool) -> Self {
+        self.todo_comments = todo_comments;
+        self
+    }
+
+    pub fn strict_null(mut self, strict_null: bool) -> Self {
+        self.strict_null = strict_null;
+        self
+    }
+
+    pub fn emit_sample(mut self, emit_sample: bool) -> Self {
+        self.emit_sample = emit_sample;
+        self
+    }
+
+    pub fn separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    pub fn wrap_single(mut self, wrap_single: bool) -> Self {
+        self.wrap_single = wrap_single;
+        self
+    }
+
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.jobs = Some(jobs);
+        self
+    }
+
+    // The indentation asked for, or `default` if none was
+    pub fn indent_or<'a>(&'a self, default: &'a str) -> &'a str {
+        self.indent.as_deref().unwrap_or(default)
+    }
+
+    pub fn naming(mut self, naming: impl NamingStrategy + 'static) -> Self {
+        self.naming = Arc::new(naming);
+        self
+    }
+
+    pub fn type_prefix(mut self, type_prefix: impl Into<String>) -> Self {
+        self.type_prefix = type_prefix.into();
+        self
+    }
+
+    pub fn type_suffix(mut self, type_suffix: impl Into<String>) -> Self {
+        self.type_suffix = type_suffix.into();
+        self
+    }
+
+    pub fn field_case(mut self, field_case: Case) -> Self {
+        self.field_case = Some(field_case);
+        self
+    }
+
+    pub fn type_case(mut self, type_case: Case) -> Self {
+        self.type_case = Some(type_case);
+        self
+    }
+
+    // Name of the field under `key` in the case asked for, None to name it the language's way
+    pub fn cased_field_name(&self, key: &str) -> Option<String> {
+        self.field_case.map(|case| case.apply(key))
+    }
+
+    pub fn renames(mut self, renames: RenameMap) -> Self {
+        self.renames = renames;
+        self
+    }
+
+    pub fn type_mapping(mut self, type_mapping: TypeMapping) -> Self {
+        self.type_mapping = type_mapping;
+        self
+    }
+
+    pub fn plurals(mut self, plurals: PluralMap) -> Self {
+        self.plurals = plurals;
+        self
+    }
+
+    // What an array under `key` holds one of, from `plurals` if it's there
+    pub fn singular(&self, key: &str) -> String {
+        match self.plurals.lookup(key) {
+            Some(singular) => singular.to_string(),
+            None => self.naming.singular(key),
+        }
+    }
+
+    // The key the elements of an array under `key` are named after, the key itself unless
+    // `plurals` has its singular
+    pub(crate) fn element_key(&self, key: &str) -> String {
+        self.plurals.lookup(key).unwrap_or(key).to_string()
+    }
+
+    pub fn ruby_style(mut self, ruby_style: RubyStyle) -> Self {
+        self.ruby_style = ruby_style;
+        self
+    }
+
+    pub fn flatten_wrappers(mut self, keys: Vec<String>) -> Self {
+        self.flatten_wrappers = Some(keys);
+        self
+    }
+
+    // The key and value of the single field of `shape`, if it's a wrapper to flatten
+    pub(crate) fn wrapped<'a>(&self, shape: &'a Shape) -> Option<(&'a str, &'a Shape)> {
+        let keys = self.flatten_wrappers.as_ref()?;
+        let Shape::Object { fields, .. } = shape else {
+            return None;
+        };
+        match fields.iter().next() {
+            Some((key, inner)) if fields.len() == 1 && (keys.is_empty() || keys.contains(key)) => {
+                Some((key, inner))
+            }
+            _ => None,
+        }
+    }
+
+    // Name of the type generated for an object under `key`, with the prefix and suffix
+    pub fn type_name(&self, key: &str) -> String {
+        let name = match self.type_case {
+            Some(case) => case.apply(key),
+            None => self.naming.type_name(key),
+        };
+        format!("{}{name}{}", self.type_prefix, self.type_suffix)
+    }
+}
+
+// Reads options from a JSON object whose keys are the field names, e.g.
+// {"root_name": "Package", "annotations": false, "examples": "redact-strings"}.
+// Keys left out keep their defaults, and so does an empty text.
+pub fn options_from_json(text: &str) -> Result<GeneratorOptions, JsoncError> {
+    let mut opts = GeneratorOptions::default();
+    if text.trim().is_empty() {
+        return Ok(opts);
+    }
+    let invalid = |message: String| JsoncError::Options { message };
+    let value: Value = serde_json::from_str(text).map_err(|e| invalid(e.to_string()))?;
+    let Value::Object(fields) = value else {
+        return Err(invalid(String::from("expected a JSON object")));
+    };
+
+    for (key, value) in fields {
+        let expected = |tpe: &str| invalid(format!("'{key}' should be a {tpe}"));
+        match key.as_str() {
+            "root_name" => {
+                opts.root_name = Some(value.as_str().ok_or_else(|| expected("string"))?.into())
+            }
+            "indent" => {
+                opts.indent = Some(value.as_str().ok_or_else(|| expected("string"))?.into())
+            }
+            "type_prefix" => {
+                opts.type_prefix = value.as_str().ok_or_else(|| expected("string"))?.into()
+            }
+            "type_suffix" => {
+                opts.type_suffix = value.as_str().ok_or_else(|| expected("string"))?.into()
+            }
+            "separator" => {
+                opts.separator = value.as_str().ok_or_else(|| expected("string"))?.into()
+            }
+            "namespace" => {
+                opts.namespace = Some(value.as_str().ok_or_else(|| expected("string"))?.into())
+            }
+            "wrap_single" => {
+                opts.wrap_single = value.as_bool().ok_or_else(|| expected("boolean"))?
+            }
+            "jobs" => {
+                let jobs = value.as_u64().ok_or_else(|| expected("number"))?;
+                opts.jobs = Some(jobs as usize);
+            }
+            "annotations" => {
+                opts.annotations = value.as_bool().ok_or_else(|| expected("boolean"))?
+            }
+            "coerce_numeric_strings" => {
+                opts.coerce_numeric_strings = value.as_bool().ok_or_else(|| expected("boolean"))?
+            }
+            "todo_comments" => {
+                opts.todo_comments = value.as_bool().ok_or_else(|| expected("boolean"))?
+            }
+            "strict_null" => {
+                opts.strict_null = value.as_bool().ok_or_else(|| expected("boolean"))?
+            }
+            "emit_sample" => {
+                opts.emit_sample = value.as_bool().ok_or_else(|| expected("boolean"))?
+            }
+            "renames" => opts.renames = RenameMap::from_json(&value.to_string())?,
+            "type_mapping" => opts.type_mapping = TypeMapping::from_json(&value.to_string())?,
+            // An object from plurals to singulars
+            "plurals" => {
+                let Value::Object(entries) = value else {
+                    return Err(expected("object"));
+                };
+                for (plural, singular) in entries {
+                    let singular = singular.as_str().ok_or_else(|| {
+                        invalid(format!("the singular of '{plural}' should be a string"))
+                    })?;
+                    opts.plurals.insert(plural, singular);
+                }
+            }
+            "builtin_plurals" => opts
+                .plurals
+                .builtins(value.as_bool().ok_or_else(|| expected("boolean"))?),
+            // true for any key, or the keys that may be flattened
+            "flatten_wrappers" => {
+                opts.flatten_wrappers = match value {
+                    Value::Bool(flatten) => flatten.then(Vec::new),
+                    Value::Array(keys) => Some(
+                        keys.iter()
+                            .map(|key| key.as_str().map(String::from))
+                            .collect::<Option<_>>()
+                            .ok_or_else(|| expected("boolean or an array of strings"))?,
+                    ),
+                    _ => return Err(expected("boolean or an array of strings")),
+                }
+            }
+            "ruby_style" => {
+                let name = value.as_str().ok_or_else(|| expected("string"))?;
+                opts.ruby_style = RubyStyle::from_name(name)
+                    .ok_or_else(|| invalid(format!("unknown Ruby style '{name}'")))?;
+            }
+            "field_case" | "type_case" => {
+                let name = value.as_str().ok_or_else(|| expected("string"))?;
+                let case = Case::from_name(name)
+                    .ok_or_else(|| invalid(format!("unknown case '{name}'")))?;
+                if key == "field_case" {
+                    opts.field_case = Some(case);
+                } else {
+                    opts.type_case = Some(case);
+                }
+            }
+            "examples" => {
+                let name = value.as_str().ok_or_else(|| expected("string"))?;
+                let mode = ExampleMode::from_name(name)
+                    .ok_or_else(|| invalid(format!("unknown examples mode '{name}'")))?;
+                opts.examples = Some(mode);
+            }
+            _ => return Err(invalid(format!("unknown option '{key}'"))),
+        }
+    }
+    Ok(opts)
+}
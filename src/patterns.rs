@@ -0,0 +1,39 @@
+use glob::MatchOptions;
+
+use crate::cli_error::Error;
+
+fn is_pattern(input: &str) -> bool {
+    input.contains(['*', '?', '['])
+}
+
+// Expands glob patterns among the inputs into the files they match, in sorted order.
+// This is done here rather than by the shell, which doesn't happen on Windows.
+// Hidden files and directories are only matched if `hidden` is set.
+pub fn expand_inputs(inputs: &[String], hidden: bool) -> Result<Vec<String>, Error> {
+    let options = MatchOptions {
+        require_literal_leading_dot: !hidden,
+        ..MatchOptions::new()
+    };
+
+    let mut expanded = vec![];
+    for input in inputs {
+        if !is_pattern(input) {
+            expanded.push(input.clone());
+            continue;
+        }
+
+        let paths = glob::glob_with(input, options)
+            .map_err(|e| Error::Usage(format!("invalid pattern '{input}': {e}")))?;
+        let mut matches = paths
+            .filter_map(|path| path.ok())
+            .filter(|path| path.is_file())
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>();
+        if matches.is_empty() {
+            return Err(Error::NoMatch(input.clone()));
+        }
+        matches.sort();
+        expanded.extend(matches);
+    }
+    Ok(expanded)
+}
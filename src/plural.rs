@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+
+use crate::error::JsoncError;
+
+// Irregular plurals known without being given, unless builtins are turned off
+static BUILTIN_PLURALS: &[(&str, &str)] = &[
+    ("people", "person"),
+    ("children", "child"),
+    ("data", "datum"),
+];
+
+// Singulars of the plurals that `NamingStrategy::singular` gets wrong, e.g. statuses to
+// status. The elements of arrays under one of these keys are named after the singular,
+// keys that aren't in it are named the way they'd be without it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PluralMap {
+    entries: BTreeMap<String, String>,
+    builtins: bool,
+}
+
+impl Default for PluralMap {
+    fn default() -> Self {
+        PluralMap {
+            entries: BTreeMap::new(),
+            builtins: true,
+        }
+    }
+}
+
+impl PluralMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Reads `plural=singular` lines, which a TOML table of strings is too. Blank lines,
+    // comments after # and [headers] are skipped.
+    pub fn from_text(text: &str) -> Result<PluralMap, JsoncError> {
+        let unquote = |s: &str| s.trim().trim_matches('"').trim_matches('\'').to_string();
+        let mut plurals = PluralMap::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() || line.starts_with('[') {
+                continue;
+            }
+            let Some((plural, singular)) = line.split_once('=') else {
+                return Err(JsoncError::Options {
+                    message: format!("expected plural=singular on line {}", index + 1),
+                });
+            };
+            plurals.insert(unquote(plural), unquote(singular));
+        }
+        Ok(plurals)
+    }
+
+    pub fn insert(&mut self, plural: impl Into<String>, singular: impl Into<String>) {
+        self.entries.insert(plural.into(), singular.into());
+    }
+
+    // Adds the entries of `other`, which win over the ones with the same plural
+    pub fn extend(&mut self, other: PluralMap) {
+        self.entries.extend(other.entries);
+    }
+
+    pub fn builtins(&mut self, builtins: bool) {
+        self.builtins = builtins;
+    }
+
+    // The singular of `plural`, if it's given or a known irregular
+    pub fn lookup(&self, plural: &str) -> Option<&str> {
+        self.entries.get(plural).map(String::as_str).or_else(|| {
+            BUILTIN_PLURALS
+                .iter()
+                .find(|(known, _)| self.builtins && *known == plural)
+                .map(|(_, singular)| *singular)
+        })
+    }
+}
@@ -0,0 +1,162 @@
+// Turns a JSONC document (JSON with `//` and `/* */` comments and trailing commas)
+// into plain JSON. Comments and trailing commas are replaced with whitespace rather
+// than removed, so line and column numbers in parse errors still match the input.
+pub fn strip_jsonc(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut out: Vec<u8> = Vec::with_capacity(len);
+    // Index in `out` of a comma that hasn't been followed by a value yet
+    let mut pending_comma: Option<usize> = None;
+    let mut i = 0;
+
+    while i < len {
+        let next = bytes.get(i + 1).copied();
+        match bytes[i] {
+            b'"' => {
+                pending_comma = None;
+                let start = i;
+                i += 1;
+                while i < len {
+                    match bytes[i] {
+                        b'\\' => i += 2,
+                        b'"' => {
+                            i += 1;
+                            break;
+                        }
+                        _ => i += 1,
+                    }
+                }
+                out.extend_from_slice(&bytes[start..i.min(len)]);
+                continue;
+            }
+            b'/' if next == Some(b'/') => {
+                while i < len && bytes[i] != b'\n' {
+                    out.push(b' ');
+                    i += 1;
+                }
+                continue;
+            }
+            b'/' if next == Some(b'*') => {
+                out.extend_from_slice(b"  ");
+                i += 2;
+                while i < len && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
+                    out.push(if bytes[i] == b'\n' { b'\n' } else { b' ' });
+                    i += 1;
+                }
+                if i < len {
+                    out.extend_from_slice(b"  ");
+                    i += 2;
+                }
+                continue;
+            }
+            b',' => {
+                pending_comma = Some(out.len());
+                out.push(b',');
+            }
+            closing @ (b'}' | b']') => {
+                if let Some(comma) = pending_comma.take() {
+                    out[comma] = b' ';
+                }
+                out.push(closing);
+            }
+            whitespace if whitespace.is_ascii_whitespace() => out.push(whitespace),
+            other => {
+                pending_comma = None;
+                out.push(other);
+            }
+        }
+        i += 1;
+    }
+
+    // Only whole characters are ever blanked out, so the result is still valid UTF-8
+    String::from_utf8(out).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
+
+// A NaN or Infinity literal found by `replace_non_finite`, at a 1-based line and column
+#[derive(Clone, Debug, PartialEq)]
+pub struct NonFinite {
+    pub literal: &'static str,
+    pub line: usize,
+    pub column: usize,
+}
+
+static NON_FINITE: [(&str, &str); 3] = [
+    ("-Infinity", "-0.0     "),
+    ("Infinity", "0.0     "),
+    ("NaN", "0.0"),
+];
+
+// Replaces the bare NaN, Infinity and -Infinity some serializers write, but JSON doesn't
+// allow, with 0.0 so the values they stand in for are typed as floats. Strings and
+// comments are left alone, and the replacement is padded to the length of the literal so
+// positions in parse errors still match the input.
+pub fn replace_non_finite(input: &str) -> (String, Vec<NonFinite>) {
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut out = String::with_capacity(len);
+    let mut found = vec![];
+    let mut line = 1;
+    let mut line_start = 0;
+    // Start of the text not copied to `out` yet
+    let mut copied = 0;
+    let mut i = 0;
+
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b == b'.';
+    while i < len {
+        let next = bytes.get(i + 1).copied();
+        match bytes[i] {
+            b'\n' => {
+                line += 1;
+                line_start = i + 1;
+            }
+            b'"' => {
+                i += 1;
+                while i < len && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+            }
+            b'/' if next == Some(b'/') => {
+                while i + 1 < len && bytes[i + 1] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if next == Some(b'*') => {
+                i += 2;
+                while i < len && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
+                    if bytes[i] == b'\n' {
+                        line += 1;
+                        line_start = i + 1;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'-' | b'I' | b'N' if i == 0 || !is_word(bytes[i - 1]) => {
+                let rest = &input[i..];
+                let literal = NON_FINITE.iter().find(|(literal, _)| {
+                    rest.starts_with(literal)
+                        && !bytes.get(i + literal.len()).is_some_and(|&b| is_word(b))
+                        // An unquoted JSON5 key
+                        && !rest[literal.len()..].trim_start().starts_with(':')
+                });
+                if let Some((literal, replacement)) = literal {
+                    out.push_str(&input[copied..i]);
+                    out.push_str(replacement);
+                    found.push(NonFinite {
+                        literal,
+                        line,
+                        column: i - line_start + 1,
+                    });
+                    i += literal.len();
+                    copied = i;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    out.push_str(&input[copied.min(len)..]);
+    (out, found)
+}
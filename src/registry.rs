@@ -0,0 +1,99 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use crate::language::LanguageFormatter;
+use crate::options::GeneratorOptions;
+
+// Builds a formatter that generates code the way `GeneratorOptions` ask for
+pub type FormatterFactory = Box<dyn Fn(&GeneratorOptions) -> Box<dyn LanguageFormatter>>;
+
+thread_local! {
+    // Formatters aren't Send, so languages are registered per thread
+    static REGISTRY: RefCell<BTreeMap<String, FormatterFactory>> = RefCell::new(BTreeMap::new());
+}
+
+/// Makes a custom formatter available under `name`, e.g. for an in-house language.
+/// It takes precedence over a built-in language with the same name.
+///
+/// The registry is thread-local, since formatters aren't `Send`: a language registered on
+/// one thread can't be used on another, each thread generating code registers it itself.
+///
+/// ```
+/// use jsonc::language::FieldContext;
+/// use jsonc::naming::pascal_case;
+/// use jsonc::{generate_code, register_language, GeneratorOptions, LanguageFormatter};
+/// use serde_json::Value;
+///
+/// struct Idl;
+///
+/// impl LanguageFormatter for Idl {
+///     fn struct_or_class_header(&self, raw: String) -> String {
+///         format!("record {} {{\n", self.struct_or_class_name(&raw))
+///     }
+///
+///     fn struct_or_class_footer(&self, _struct_name: Option<String>) -> String {
+///         String::from("}")
+///     }
+///
+///     fn field_name(&self, json_key: &str) -> String {
+///         json_key.to_string()
+///     }
+///
+///     fn format_field(&self, field: &FieldContext) -> String {
+///         format!("  {}: {};\n", self.field_name(field.name_key), field.type_name)
+///     }
+///
+///     fn format_arr_type(&self, arr_type: String, _optional: bool) -> String {
+///         format!("list<{arr_type}>")
+///     }
+///
+///     fn premitive_type_name(&self, from: &Value) -> &'static str {
+///         match from {
+///             Value::Bool(_) => "bool",
+///             Value::Number(_) => "number",
+///             Value::String(_) => "string",
+///             _ => "any",
+///         }
+///     }
+///
+///     fn struct_or_class_name(&self, key: &str) -> String {
+///         pascal_case(key)
+///     }
+/// }
+///
+/// register_language("idl", |_opts| Box::new(Idl));
+/// let sample = r#"{"name": "jsonc", "tags": ["cli"]}"#;
+/// let opts = GeneratorOptions::default();
+/// let code = generate_code(sample, "idl", &opts).unwrap();
+/// assert!(code.contains("record Root {\n  name: string;\n  tags: list<string>;\n}"));
+///
+/// // Other threads don't have it
+/// let elsewhere = std::thread::spawn(move || generate_code(sample, "idl", &opts).is_err());
+/// assert!(elsewhere.join().unwrap());
+/// ```
+pub fn register_language(
+    name: &str,
+    factory: impl Fn(&GeneratorOptions) -> Box<dyn LanguageFormatter> + 'static,
+) {
+    REGISTRY.with(|registry| {
+        registry
+            .borrow_mut()
+            .insert(name.to_lowercase(), Box::new(factory))
+    });
+}
+
+pub fn registered_formatter(
+    name: &str,
+    opts: &GeneratorOptions,
+) -> Option<Box<dyn LanguageFormatter>> {
+    REGISTRY.with(|registry| {
+        let registry = registry.borrow();
+        registry
+            .get(&name.to_lowercase())
+            .map(|factory| factory(opts))
+    })
+}
+
+pub fn registered_languages() -> Vec<String> {
+    REGISTRY.with(|registry| registry.borrow().keys().cloned().collect())
+}
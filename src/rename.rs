@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::error::JsoncError;
+use crate::schema::Schema;
+
+// Names given to JSON keys instead of the ones made from the keys, e.g. dob to dateOfBirth.
+// An entry is either a bare key, renamed wherever it is, or a JSON pointer to a single
+// field, e.g. /users/*/addr1, where * or an index stands for every element of an array.
+// Pointers win over bare keys. The name is cased like a key would be.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RenameMap {
+    // By the entry as it's matched, to the name and the entry as it was written
+    entries: BTreeMap<String, (String, String)>,
+}
+
+// A pointer written the way schema paths are, with arrays as [] and keys unescaped
+pub(crate) fn normalize_pointer(pointer: &str) -> String {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|segment| match segment {
+            "*" | "-" => String::from("[]"),
+            index if !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()) => {
+                String::from("[]")
+            }
+            key => key.replace("~1", "/").replace("~0", "~"),
+        })
+        .map(|segment| format!("/{segment}"))
+        .collect()
+}
+
+impl RenameMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Reads a JSON object from entries to names, e.g. {"dob": "dateOfBirth"}
+    pub fn from_json(text: &str) -> Result<RenameMap, JsoncError> {
+        let invalid = |message: String| JsoncError::Options { message };
+        let value: Value = serde_json::from_str(text).map_err(|e| invalid(e.to_string()))?;
+        let Value::Object(entries) = value else {
+            return Err(invalid(String::from("expected a JSON object of renames")));
+        };
+        let mut renames = RenameMap::new();
+        for (entry, name) in entries {
+            let name = name
+                .as_str()
+                .ok_or_else(|| invalid(format!("the rename of '{entry}' should be a string")))?;
+            renames.insert(&entry, name);
+        }
+        Ok(renames)
+    }
+
+    pub fn insert(&mut self, entry: &str, name: impl Into<String>) {
+        let matched = if entry.starts_with('/') {
+            normalize_pointer(entry)
+        } else {
+            entry.to_string()
+        };
+        self.entries
+            .insert(matched, (name.into(), entry.to_string()));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // The name for the field under `key` at `path`, e.g. /users/[]/dob, if it's renamed
+    pub fn lookup(&self, key: &str, path: &str) -> Option<&str> {
+        self.entries
+            .get(path)
+            .or_else(|| self.entries.get(key))
+            .map(|(name, _)| name.as_str())
+    }
+
+    // Entries that don't match any field of `schema`, they're likely typos
+    pub fn unused(&self, schema: &Schema) -> Vec<String> {
+        let fields: Vec<(&str, String)> = schema
+            .types
+            .iter()
+            .flat_map(|type_def| {
+                type_def.fields.iter().map(|field| {
+                    (
+                        field.key.as_str(),
+                        format!("{}/{}", type_def.path, field.key),
+                    )
+                })
+            })
+            .collect();
+        self.entries
+            .iter()
+            .filter(|(entry, _)| {
+                !fields
+                    .iter()
+                    .any(|(key, path)| key == entry || path == *entry)
+            })
+            .map(|(_, (_, written))| written.clone())
+            .collect()
+    }
+}
@@ -0,0 +1,615 @@
+use serde_json::{Number, Value};
+
+use crate::constants::{GO_BOOL, GO_FLOAT, GO_INT, GO_STRING};
+use crate::generate::{type_name, unique_name_keys};
+use crate::language::{
+    dart_string, elm_string, haskell_string, kotlin_string, ocaml_string, quote, zig_string,
+    LanguageFormatter,
+};
+use crate::schema::{LogicalType, Schema, TypeRef};
+
+// Arrays in a sample are cut off after this many elements, with a comment saying how
+// many were left out
+pub static MAX_SAMPLE_ELEMENTS: usize = 3;
+
+// A value of the input as a value of the generated types, written out by
+// `LanguageFormatter::sample_declaration`. Fields missing from the input get the zero value
+// of their type, e.g. 0 or "".
+#[derive(Clone, PartialEq, Debug)]
+pub enum Literal {
+    // A field of the language's Any type
+    Null,
+    Bool(bool),
+    // As it was written in the input
+    Int(String),
+    Float(f64),
+    String(String),
+    // An element of an array whose elements may be null, or a field that may be missing
+    // or null
+    Optional(Option<Box<Literal>>),
+    Array {
+        // The array's type and its elements', as the formatter names them
+        type_name: String,
+        element_type: String,
+        items: Vec<Literal>,
+        // Elements left out after the first MAX_SAMPLE_ELEMENTS
+        omitted: usize,
+    },
+    Object {
+        type_name: String,
+        // By the name the formatter gives the field
+        fields: Vec<(String, Literal)>,
+    },
+}
+
+// The document `value` as a literal of the types in `schema`, which was inferred from it
+pub fn sample_literal(value: &Value, schema: &Schema, lang: &dyn LanguageFormatter) -> Literal {
+    let context = Context { schema, lang };
+    literal(&schema.root, Some(value), "", &context)
+}
+
+// What every literal of a document is made with
+struct Context<'a> {
+    schema: &'a Schema,
+    lang: &'a dyn LanguageFormatter,
+}
+
+fn literal(type_ref: &TypeRef, value: Option<&Value>, path: &str, context: &Context) -> Literal {
+    let Context { schema, lang, .. } = *context;
+    // Numbers sent as strings are still numbers in the generated types
+    let number = |value: Option<&Value>| match value {
+        Some(Value::String(s)) => serde_json::from_str::<Number>(s).ok(),
+        Some(Value::Number(number)) => Some(number.clone()),
+        _ => None,
+    };
+    match type_ref {
+        TypeRef::Primitive(LogicalType::Any) => Literal::Null,
+        TypeRef::Primitive(LogicalType::Bool) => {
+            Literal::Bool(value.and_then(Value::as_bool).unwrap_or_default())
+        }
+        TypeRef::Primitive(LogicalType::Int | LogicalType::TimestampMillis) => Literal::Int(
+            number(value).map_or_else(|| String::from("0"), |number| number.to_string()),
+        ),
+        TypeRef::Primitive(LogicalType::Float | LogicalType::Decimal) => Literal::Float(
+            number(value)
+                .and_then(|number| number.as_f64())
+                .unwrap_or_default(),
+        ),
+        TypeRef::Primitive(LogicalType::String | LogicalType::Date | LogicalType::Uuid) => {
+            Literal::String(value.and_then(Value::as_str).unwrap_or_default().into())
+        }
+        TypeRef::Array { element, optional } => {
+            let all = value
+                .and_then(Value::as_array)
+                .map_or(&[][..], Vec::as_slice);
+            let element_path = format!("{path}/[]");
+            let items = all
+                .iter()
+                .take(MAX_SAMPLE_ELEMENTS)
+                .map(|item| {
+                    let item_literal = || literal(element, Some(item), &element_path, context);
+                    match (optional, item) {
+                        (true, Value::Null) => Literal::Optional(None),
+                        (true, _) => Literal::Optional(Some(Box::new(item_literal()))),
+                        (false, _) => item_literal(),
+                    }
+                })
+                .collect();
+            Literal::Array {
+                type_name: type_name(type_ref, lang),
+                element_type: type_name(element, lang),
+                items,
+                omitted: all.len().saturating_sub(MAX_SAMPLE_ELEMENTS),
+            }
+        }
+        TypeRef::Object(key) => {
+            let type_def = schema
+                .types
+                .iter()
+                .find(|type_def| type_def.path == path)
+                .or_else(|| schema.types.iter().find(|type_def| type_def.key == *key));
+            let fields = type_def
+                .map(|type_def| {
+                    type_def
+                        .fields
+                        .iter()
+                        .zip(unique_name_keys(type_def, lang))
+                        .map(|(field, name_key)| {
+                            let field_value = value.and_then(|value| {
+                                std::iter::once(&field.key)
+                                    .chain(&field.flattened)
+                                    .try_fold(value, |value, key| value.get(key))
+                            });
+                            let field_path = format!("{path}{}", field.pointer());
+                            let field_literal =
+                                || literal(&field.type_ref, field_value, &field_path, context);
+                            let field_literal = if field.may_be_missing() {
+                                match field_value {
+                                    None | Some(Value::Null) => Literal::Optional(None),
+                                    Some(_) => Literal::Optional(Some(Box::new(field_literal()))),
+                                }
+                            } else {
+                                field_literal()
+                            };
+                            (lang.field_name(&name_key), field_literal)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Literal::Object {
+                type_name: lang.struct_or_class_name(key),
+                fields,
+            }
+        }
+    }
+}
+
+// How literals are laid out: one element or field per line, each nested one level deeper
+pub struct Layout<'a> {
+    pub indent: &'a str,
+    pub comment_prefix: &'a str,
+}
+
+impl Layout<'_> {
+    // `open`, then every item on a line of its own, one level deeper than `depth`, and
+    // `close`. Every item but the last is followed by `separator`, and so is the last
+    // one if `trailing` is set.
+    pub fn block(
+        &self,
+        open: &str,
+        items: Vec<String>,
+        (separator, trailing): (&str, bool),
+        omitted: usize,
+        close: &str,
+        depth: usize,
+    ) -> String {
+        if items.is_empty() && omitted == 0 {
+            return format!("{open}{close}");
+        }
+        let inner = self.indent.repeat(depth + 1);
+        let count = items.len();
+        let mut text = format!("{open}\n");
+        for (index, item) in items.into_iter().enumerate() {
+            let end = if index + 1 < count || trailing {
+                separator
+            } else {
+                ""
+            };
+            text.push_str(&format!("{inner}{item}{end}\n"));
+        }
+        if omitted > 0 {
+            text.push_str(&format!("{inner}{} {omitted} more\n", self.comment_prefix));
+        }
+        text.push_str(&self.indent.repeat(depth));
+        text.push_str(close);
+        text
+    }
+}
+
+// Floats always have a fraction or an exponent, so they aren't read as integers
+pub fn float_literal(value: f64) -> String {
+    format!("{value:?}")
+}
+
+// Strings are written the way Rust's Debug has them, which is valid Rust
+pub(crate) fn rust(literal: &Literal, layout: &Layout, depth: usize) -> String {
+    let nested = |literal: &Literal| rust(literal, layout, depth + 1);
+    match literal {
+        Literal::Null | Literal::Optional(None) => String::from("None"),
+        Literal::Bool(b) => b.to_string(),
+        Literal::Int(n) => n.clone(),
+        Literal::Float(f) => float_literal(*f),
+        Literal::String(s) => format!("{s:?}.into()"),
+        Literal::Optional(Some(inner)) => format!("Some({})", rust(inner, layout, depth)),
+        Literal::Array { items, omitted, .. } => {
+            let items = items.iter().map(nested).collect();
+            layout.block("vec![", items, (",", true), *omitted, "]", depth)
+        }
+        Literal::Object { type_name, fields } => {
+            let fields = fields
+                .iter()
+                .map(|(name, value)| format!("{name}: {}", nested(value)))
+                .collect();
+            layout.block(
+                &format!("{type_name} {{"),
+                fields,
+                (",", true),
+                0,
+                "}",
+                depth,
+            )
+        }
+    }
+}
+
+// Go's name for the type of a scalar literal
+fn go_scalar_type(literal: &Literal) -> &'static str {
+    match literal {
+        Literal::Bool(_) => GO_BOOL,
+        Literal::Int(_) => GO_INT,
+        Literal::Float(_) => GO_FLOAT,
+        _ => GO_STRING,
+    }
+}
+
+// Composite literals. Go can't take the address of a constant, so optional elements and
+// fields that aren't structs are pointed to from a function. Optional slices can be nil,
+// so they aren't pointed to.
+pub(crate) fn go(literal: &Literal, layout: &Layout, depth: usize) -> String {
+    let nested = |literal: &Literal| go(literal, layout, depth + 1);
+    match literal {
+        Literal::Null | Literal::Optional(None) => String::from("nil"),
+        Literal::Bool(b) => b.to_string(),
+        Literal::Int(n) => n.clone(),
+        Literal::Float(f) => float_literal(*f),
+        Literal::String(s) => quote(s),
+        Literal::Optional(Some(inner)) => go(inner, layout, depth),
+        Literal::Array {
+            type_name,
+            element_type,
+            items,
+            omitted,
+        } => {
+            let items = items
+                .iter()
+                .map(|item| match item {
+                    Literal::Optional(Some(inner)) if matches!(**inner, Literal::Object { .. }) => {
+                        format!("&{}", nested(inner))
+                    }
+                    Literal::Optional(Some(inner)) => format!(
+                        "func() *{element_type} {{ v := {element_type}({}); return &v }}()",
+                        nested(inner)
+                    ),
+                    item => nested(item),
+                })
+                .collect();
+            layout.block(
+                &format!("{type_name}{{"),
+                items,
+                (",", true),
+                *omitted,
+                "}",
+                depth,
+            )
+        }
+        Literal::Object { type_name, fields } => {
+            let fields = fields
+                .iter()
+                .map(|(name, value)| match value {
+                    Literal::Optional(Some(inner)) => match **inner {
+                        Literal::Object { .. } => format!("{name}: &{}", nested(inner)),
+                        Literal::Array { .. } => format!("{name}: {}", nested(inner)),
+                        _ => {
+                            let tpe = go_scalar_type(inner);
+                            format!(
+                                "{name}: func() *{tpe} {{ v := {tpe}({}); return &v }}()",
+                                nested(inner)
+                            )
+                        }
+                    },
+                    value => format!("{name}: {}", nested(value)),
+                })
+                .collect();
+            layout.block(
+                &format!("{type_name}{{"),
+                fields,
+                (",", true),
+                0,
+                "}",
+                depth,
+            )
+        }
+    }
+}
+
+// Fields are public, so objects are filled in by an initializer block of an anonymous
+// subclass, and lists are made with Arrays.asList. Optional fields without a value keep
+// the one they start out with.
+pub(crate) fn java(literal: &Literal, layout: &Layout, depth: usize) -> String {
+    let nested = |literal: &Literal| java(literal, layout, depth + 1);
+    match literal {
+        Literal::Null | Literal::Optional(None) => String::from("null"),
+        Literal::Bool(b) => b.to_string(),
+        Literal::Int(n) => n.clone(),
+        Literal::Float(f) => format!("{}f", float_literal(*f)),
+        Literal::String(s) => quote(s),
+        Literal::Optional(Some(inner)) => java(inner, layout, depth),
+        Literal::Array { items, omitted, .. } => {
+            let items = items.iter().map(nested).collect();
+            layout.block("Arrays.asList(", items, (",", false), *omitted, ")", depth)
+        }
+        Literal::Object { type_name, fields } => {
+            let fields = fields
+                .iter()
+                .filter(|(_, value)| *value != Literal::Optional(None))
+                .map(|(name, value)| format!("{name} = {}", nested(value)))
+                .collect();
+            let open = format!("new {type_name}() {{{{");
+            layout.block(&open, fields, (";", true), 0, "}}", depth)
+        }
+    }
+}
+
+// Case classes are made with named arguments
+pub(crate) fn scala(literal: &Literal, layout: &Layout, depth: usize) -> String {
+    let nested = |literal: &Literal| scala(literal, layout, depth + 1);
+    match literal {
+        Literal::Null => String::from("null"),
+        Literal::Optional(None) => String::from("None"),
+        Literal::Bool(b) => b.to_string(),
+        Literal::Int(n) => n.clone(),
+        Literal::Float(f) => format!("{}f", float_literal(*f)),
+        Literal::String(s) => quote(s),
+        Literal::Optional(Some(inner)) => format!("Some({})", scala(inner, layout, depth)),
+        Literal::Array { items, omitted, .. } => {
+            let items = items.iter().map(nested).collect();
+            layout.block("Seq(", items, (",", true), *omitted, ")", depth)
+        }
+        Literal::Object { type_name, fields } => {
+            let fields = fields
+                .iter()
+                .map(|(name, value)| format!("{name} = {}", nested(value)))
+                .collect();
+            layout.block(&format!("{type_name}("), fields, (",", true), 0, ")", depth)
+        }
+    }
+}
+
+// Data classes are made with named arguments, and lists with listOf
+pub(crate) fn kotlin(literal: &Literal, layout: &Layout, depth: usize) -> String {
+    let nested = |literal: &Literal| kotlin(literal, layout, depth + 1);
+    match literal {
+        Literal::Null | Literal::Optional(None) => String::from("null"),
+        Literal::Bool(b) => b.to_string(),
+        Literal::Int(n) => n.clone(),
+        Literal::Float(f) => float_literal(*f),
+        Literal::String(s) => kotlin_string(s),
+        Literal::Optional(Some(inner)) => kotlin(inner, layout, depth),
+        Literal::Array { items, omitted, .. } => {
+            let items = items.iter().map(nested).collect();
+            layout.block("listOf(", items, (",", true), *omitted, ")", depth)
+        }
+        Literal::Object { type_name, fields } => {
+            let fields = fields
+                .iter()
+                .map(|(name, value)| format!("{name} = {}", nested(value)))
+                .collect();
+            layout.block(&format!("{type_name}("), fields, (",", true), 0, ")", depth)
+        }
+    }
+}
+
+// Dataclasses are made with keyword arguments
+pub(crate) fn python(literal: &Literal, layout: &Layout, depth: usize) -> String {
+    let nested = |literal: &Literal| python(literal, layout, depth + 1);
+    match literal {
+        Literal::Null | Literal::Optional(None) => String::from("None"),
+        Literal::Bool(true) => String::from("True"),
+        Literal::Bool(false) => String::from("False"),
+        Literal::Int(n) => n.clone(),
+        Literal::Float(f) => float_literal(*f),
+        Literal::String(s) => quote(s),
+        Literal::Optional(Some(inner)) => python(inner, layout, depth),
+        Literal::Array { items, omitted, .. } => {
+            let items = items.iter().map(nested).collect();
+            layout.block("[", items, (",", true), *omitted, "]", depth)
+        }
+        Literal::Object { type_name, fields } => {
+            let fields = fields
+                .iter()
+                .map(|(name, value)| format!("{name}={}", nested(value)))
+                .collect();
+            layout.block(&format!("{type_name}("), fields, (",", true), 0, ")", depth)
+        }
+    }
+}
+
+// Classes are made with named arguments
+pub(crate) fn dart(literal: &Literal, layout: &Layout, depth: usize) -> String {
+    let nested = |literal: &Literal| dart(literal, layout, depth + 1);
+    match literal {
+        Literal::Null | Literal::Optional(None) => String::from("null"),
+        Literal::Bool(b) => b.to_string(),
+        Literal::Int(n) => n.clone(),
+        Literal::Float(f) => float_literal(*f),
+        Literal::String(s) => dart_string(s),
+        Literal::Optional(Some(inner)) => dart(inner, layout, depth),
+        Literal::Array { items, omitted, .. } => {
+            let items = items.iter().map(nested).collect();
+            layout.block("[", items, (",", true), *omitted, "]", depth)
+        }
+        Literal::Object { type_name, fields } => {
+            let fields = fields
+                .iter()
+                .map(|(name, value)| format!("{name}: {}", nested(value)))
+                .collect();
+            layout.block(&format!("{type_name}("), fields, (",", true), 0, ")", depth)
+        }
+    }
+}
+
+// Records are made with record syntax, negative numbers are in parentheses
+pub(crate) fn haskell(literal: &Literal, layout: &Layout, depth: usize) -> String {
+    let nested = |literal: &Literal| haskell(literal, layout, depth + 1);
+    let signed = |number: String| {
+        if number.starts_with('-') {
+            format!("({number})")
+        } else {
+            number
+        }
+    };
+    match literal {
+        Literal::Null => String::from("Null"),
+        Literal::Optional(None) => String::from("Nothing"),
+        Literal::Bool(true) => String::from("True"),
+        Literal::Bool(false) => String::from("False"),
+        Literal::Int(n) => signed(n.clone()),
+        Literal::Float(f) => signed(float_literal(*f)),
+        Literal::String(s) => haskell_string(s),
+        Literal::Optional(Some(inner)) => match haskell(inner, layout, depth) {
+            text if text.contains([' ', '\n']) && !text.starts_with(['(', '[', '"']) => {
+                format!("Just ({text})")
+            }
+            text => format!("Just {text}"),
+        },
+        Literal::Array { items, omitted, .. } => {
+            let items = items.iter().map(nested).collect();
+            layout.block("[", items, (",", false), *omitted, "]", depth)
+        }
+        Literal::Object { type_name, fields } => {
+            let fields = fields
+                .iter()
+                .map(|(name, value)| format!("{name} = {}", nested(value)))
+                .collect();
+            layout.block(
+                &format!("{type_name} {{"),
+                fields,
+                (",", false),
+                0,
+                "}",
+                depth,
+            )
+        }
+    }
+}
+
+// Records are made with record syntax, untyped values are encoded nulls
+pub(crate) fn elm(literal: &Literal, layout: &Layout, depth: usize) -> String {
+    let nested = |literal: &Literal| elm(literal, layout, depth + 1);
+    match literal {
+        Literal::Null => String::from("Encode.null"),
+        Literal::Optional(None) => String::from("Nothing"),
+        Literal::Bool(true) => String::from("True"),
+        Literal::Bool(false) => String::from("False"),
+        Literal::Int(n) => n.clone(),
+        Literal::Float(f) => float_literal(*f),
+        Literal::String(s) => elm_string(s),
+        Literal::Optional(Some(inner)) => match elm(inner, layout, depth) {
+            text if text.contains([' ', '\n']) || text.starts_with('-') => {
+                if text.starts_with(['[', '{', '"']) {
+                    format!("Just {text}")
+                } else {
+                    format!("Just ({text})")
+                }
+            }
+            text => format!("Just {text}"),
+        },
+        Literal::Array { items, omitted, .. } => {
+            let items = items.iter().map(nested).collect();
+            layout.block("[", items, (",", false), *omitted, "]", depth)
+        }
+        Literal::Object { fields, .. } => {
+            let fields = fields
+                .iter()
+                .map(|(name, value)| format!("{name} = {}", nested(value)))
+                .collect();
+            layout.block("{", fields, (",", false), 0, "}", depth)
+        }
+    }
+}
+
+// Records and lists separate their items with ;, negative numbers are in parentheses
+pub(crate) fn ocaml(literal: &Literal, layout: &Layout, depth: usize) -> String {
+    let nested = |literal: &Literal| ocaml(literal, layout, depth + 1);
+    let signed = |number: String| {
+        if number.starts_with('-') {
+            format!("({number})")
+        } else {
+            number
+        }
+    };
+    match literal {
+        Literal::Null => String::from("`Null"),
+        Literal::Optional(None) => String::from("None"),
+        Literal::Bool(b) => b.to_string(),
+        Literal::Int(n) => signed(n.clone()),
+        Literal::Float(f) => signed(float_literal(*f)),
+        Literal::String(s) => ocaml_string(s),
+        Literal::Optional(Some(inner)) => match ocaml(inner, layout, depth) {
+            text if text.starts_with('-') => format!("Some ({text})"),
+            text => format!("Some {text}"),
+        },
+        Literal::Array { items, omitted, .. } => {
+            let items = items.iter().map(nested).collect();
+            layout.block("[", items, (";", false), *omitted, "]", depth)
+        }
+        // Records without fields are JSON, see `OCaml::format_type`
+        Literal::Object { fields, .. } if fields.is_empty() => String::from("`Assoc []"),
+        Literal::Object { fields, .. } => {
+            let fields = fields
+                .iter()
+                .map(|(name, value)| format!("{name} = {}", nested(value)))
+                .collect();
+            layout.block("{", fields, (";", false), 0, "}", depth)
+        }
+    }
+}
+
+// Records have a field on each line, integers are int64 literals
+pub(crate) fn fsharp(literal: &Literal, layout: &Layout, depth: usize) -> String {
+    let nested = |literal: &Literal| fsharp(literal, layout, depth + 1);
+    match literal {
+        Literal::Null => String::from("Unchecked.defaultof<JsonElement>"),
+        Literal::Optional(None) => String::from("None"),
+        Literal::Bool(b) => b.to_string(),
+        Literal::Int(n) => format!("{n}L"),
+        Literal::Float(f) => float_literal(*f),
+        Literal::String(s) => quote(s),
+        Literal::Optional(Some(inner)) => format!("Some({})", fsharp(inner, layout, depth)),
+        Literal::Array { items, omitted, .. } => {
+            let items = items.iter().map(nested).collect();
+            layout.block("[", items, ("", false), *omitted, "]", depth)
+        }
+        // Types without fields are classes, see `FSharp::format_type`
+        Literal::Object { type_name, fields } if fields.is_empty() => format!("{type_name}()"),
+        Literal::Object { fields, .. } => {
+            let fields = fields
+                .iter()
+                .map(|(name, value)| format!("{name} = {}", nested(value)))
+                .collect();
+            layout.block("{", fields, ("", false), 0, "}", depth)
+        }
+    }
+}
+
+// Structs are typed literals with .field = value, arrays are slices of arrays
+pub(crate) fn zig(literal: &Literal, layout: &Layout, depth: usize) -> String {
+    let nested = |literal: &Literal| zig(literal, layout, depth + 1);
+    match literal {
+        Literal::Null => String::from(".null"),
+        Literal::Optional(None) => String::from("null"),
+        Literal::Bool(b) => b.to_string(),
+        Literal::Int(n) => n.clone(),
+        Literal::Float(f) => float_literal(*f),
+        Literal::String(s) => zig_string(s),
+        Literal::Optional(Some(inner)) => zig(inner, layout, depth),
+        Literal::Array {
+            element_type,
+            items,
+            omitted,
+            ..
+        } => {
+            let optional = items
+                .iter()
+                .any(|item| matches!(item, Literal::Optional(_)));
+            let mark = if optional { "?" } else { "" };
+            let items = items.iter().map(nested).collect();
+            let open = format!("&[_]{mark}{element_type}{{");
+            layout.block(&open, items, (",", true), *omitted, "}", depth)
+        }
+        Literal::Object { type_name, fields } => {
+            let fields = fields
+                .iter()
+                .map(|(name, value)| format!(".{name} = {}", nested(value)))
+                .collect();
+            layout.block(
+                &format!("{type_name}{{"),
+                fields,
+                (",", true),
+                0,
+                "}",
+                depth,
+            )
+        }
+    }
+}
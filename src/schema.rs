@@ -0,0 +1,766 @@
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Value};
+
+use crate::constants::DEFAULT_TYPE_NAME;
+use crate::error::JsoncError;
+use crate::examples::example;
+use crate::naming::pascal_case;
+use crate::options::GeneratorOptions;
+use crate::select::kind;
+use crate::shape::Shape;
+use crate::stats::{is_date, is_uuid};
+use crate::warnings::{WarningCategory, Warnings};
+
+// Version of the JSON written by `Schema::to_json`, raised on changes that could break
+// the scripts reading it. Adding keys is not one of them.
+pub static SCHEMA_VERSION: u64 = 1;
+
+// The type of a value as far as the sample tells, strings are dates or UUIDs when they look like one
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LogicalType {
+    String,
+    Int,
+    Float,
+    Bool,
+    Date,
+    Uuid,
+    // Only ever given by `type_mapping`: a decimal number, typed as floats are, and a
+    // number of milliseconds since the epoch, typed as integers are
+    Decimal,
+    TimestampMillis,
+    // Nothing could be inferred, the value was null
+    Any,
+}
+
+impl LogicalType {
+    pub fn of(value: &Value) -> LogicalType {
+        match value {
+            Value::Bool(_) => LogicalType::Bool,
+            Value::Number(n) if n.is_f64() => LogicalType::Float,
+            Value::Number(_) => LogicalType::Int,
+            Value::String(s) if is_date(s) => LogicalType::Date,
+            Value::String(s) if is_uuid(s) => LogicalType::Uuid,
+            Value::String(_) => LogicalType::String,
+            _ => LogicalType::Any,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LogicalType::String => "string",
+            LogicalType::Int => "int",
+            LogicalType::Float => "float",
+            LogicalType::Bool => "bool",
+            LogicalType::Date => "date",
+            LogicalType::Uuid => "uuid",
+            LogicalType::Decimal => "decimal",
+            LogicalType::TimestampMillis => "timestamp-millis",
+            LogicalType::Any => "any",
+        }
+    }
+
+    pub const NAMES: [&'static str; 9] = [
+        "string",
+        "int",
+        "float",
+        "bool",
+        "date",
+        "uuid",
+        "decimal",
+        "timestamp-millis",
+        "any",
+    ];
+
+    pub fn from_name(name: &str) -> Option<LogicalType> {
+        match name.to_lowercase().as_str() {
+            "string" => Some(LogicalType::String),
+            "int" => Some(LogicalType::Int),
+            "float" => Some(LogicalType::Float),
+            "bool" => Some(LogicalType::Bool),
+            "date" => Some(LogicalType::Date),
+            "uuid" => Some(LogicalType::Uuid),
+            "decimal" => Some(LogicalType::Decimal),
+            "timestamp-millis" => Some(LogicalType::TimestampMillis),
+            "any" => Some(LogicalType::Any),
+            _ => None,
+        }
+    }
+
+    // Whether values of this type can be read from samples of type `observed`
+    fn reads(self, observed: LogicalType) -> bool {
+        let string = |logical| {
+            matches!(
+                logical,
+                LogicalType::String | LogicalType::Date | LogicalType::Uuid
+            )
+        };
+        match self {
+            LogicalType::Any => true,
+            _ if observed == LogicalType::Any => true,
+            LogicalType::String | LogicalType::Date | LogicalType::Uuid => string(observed),
+            LogicalType::Float | LogicalType::Decimal => {
+                matches!(observed, LogicalType::Int | LogicalType::Float)
+            }
+            LogicalType::Int | LogicalType::TimestampMillis => observed == LogicalType::Int,
+            LogicalType::Bool => observed == LogicalType::Bool,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TypeRef {
+    Primitive(LogicalType),
+    // One of the schema's types, by its key
+    Object(String),
+    // Elements may be null if `optional` is set
+    Array {
+        element: Box<TypeRef>,
+        optional: bool,
+    },
+}
+
+impl TypeRef {
+    // How many arrays the innermost element type is nested in
+    pub fn array_depth(&self) -> usize {
+        match self {
+            TypeRef::Array { element, .. } => 1 + element.array_depth(),
+            _ => 0,
+        }
+    }
+
+    // The type of the elements of the innermost array, or this type if it's not an array
+    pub fn innermost(&self) -> &TypeRef {
+        match self {
+            TypeRef::Array { element, .. } => element.innermost(),
+            other => other,
+        }
+    }
+
+    // Why nothing better than Any could be inferred, None if something could
+    pub fn untyped_reason(&self) -> Option<&'static str> {
+        match (self, self.innermost()) {
+            (TypeRef::Primitive(LogicalType::Any), _) => Some("all samples null"),
+            (TypeRef::Array { .. }, TypeRef::Primitive(LogicalType::Any)) => {
+                Some("all arrays empty or of nulls")
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Field {
+    // The JSON key, as it was in the input
+    pub key: String,
+    // What the field is named after instead of the key, from the rename map
+    pub name: Option<String>,
+    pub type_ref: TypeRef,
+    // The sample value was null
+    pub optional: bool,
+    // The key was missing from some of the samples
+    pub absent: bool,
+    // The value was null in some of the samples, but not in all of them
+    pub nullable: bool,
+    // A short rendition of the sample value, when examples are asked for
+    pub example: Option<String>,
+    // Why the type couldn't be inferred, when TODO comments are asked for
+    pub todo: Option<&'static str>,
+    // Numbers in the type are sent as strings, e.g. "19.99", see `coerce_numeric_strings`
+    pub string_encoded: bool,
+    // Keys of the single-field objects the value was wrapped in, outermost first, when
+    // they're flattened, see `flatten_wrappers`
+    pub flattened: Vec<String>,
+    // What the samples are, when `type_mapping` gave the field a type they can't be read as
+    pub mapping_conflict: Option<&'static str>,
+}
+
+impl Field {
+    // Whether the field may be missing or null, and its type doesn't say so already the
+    // way Any does. Such fields are typed as optional.
+    pub fn may_be_missing(&self) -> bool {
+        (self.absent || self.nullable)
+            && !matches!(self.type_ref, TypeRef::Primitive(LogicalType::Any))
+    }
+
+    // Whether the field is typed as one whose key may be missing. Only `strict_null` tells
+    // missing keys and nulls apart, without it a field that may be either is typed as both.
+    pub fn may_be_absent(&self, strict_null: bool) -> bool {
+        self.may_be_missing() && (self.absent || !strict_null)
+    }
+
+    // Whether the field is typed as one whose value may be null, see `may_be_absent`
+    pub fn may_be_null(&self, strict_null: bool) -> bool {
+        self.may_be_missing() && (self.nullable || !strict_null)
+    }
+
+    // Where the value of the field is in the object holding it, e.g. /data/value for a
+    // field under data whose wrapper was flattened
+    pub fn pointer(&self) -> String {
+        std::iter::once(&self.key)
+            .chain(&self.flattened)
+            .map(|key| format!("/{key}"))
+            .collect()
+    }
+}
+
+// A type generated for a JSON object
+#[derive(Clone, PartialEq, Debug)]
+pub struct TypeDef {
+    // What the type is named after, the JSON key of the object or the root name
+    pub key: String,
+    // Where the object is in the document, e.g. /users/[] for the elements of an array,
+    // empty for the root
+    pub path: String,
+    pub fields: Vec<Field>,
+}
+
+// Everything inferred from a document, before it's rendered in any language
+#[derive(Clone, PartialEq, Debug)]
+pub struct Schema {
+    pub root: TypeRef,
+    // What the alias for a root array is named after, None if the root is an object
+    pub alias: Option<String>,
+    // In the order they should be written in, nested types come before the ones using them
+    pub types: Vec<TypeDef>,
+    // Where the samples were of different types, so the field was typed as Any, by the
+    // pointer of the field as in `untyped_fields`, with the types
+    pub conflicts: Vec<(String, Vec<&'static str>)>,
+}
+
+// Types can only be generated from objects and arrays
+pub fn check_root(value: &Value) -> Result<(), JsoncError> {
+    match value {
+        Value::Array(_) | Value::Object(_) => Ok(()),
+        scalar => Err(JsoncError::UnsupportedRoot { kind: kind(scalar) }),
+    }
+}
+
+// Infers the types of a document, arrays are inferred from all of their elements merged
+// into one, the way NDJSON documents are
+pub fn infer_schema(value: &Value, opts: &GeneratorOptions) -> Result<Schema, JsoncError> {
+    infer_schema_named(value, opts, DEFAULT_TYPE_NAME)
+}
+
+// Like `infer_schema`, with the root named `default_name` unless the options name it
+pub fn infer_schema_named(
+    value: &Value,
+    opts: &GeneratorOptions,
+    default_name: &str,
+) -> Result<Schema, JsoncError> {
+    check_root(value)?;
+    infer(&shape_of(value, opts), Some(value), opts, default_name)
+}
+
+// The shape types are inferred from. Long arrays are folded on a pool of its own when the
+// number of threads is limited, if one can't be started they're folded on the global pool.
+pub fn shape_of(value: &Value, opts: &GeneratorOptions) -> Shape {
+    #[cfg(feature = "parallel")]
+    if let Some(jobs) = opts.jobs {
+        if let Ok(pool) = rayon::ThreadPoolBuilder::new().num_threads(jobs).build() {
+            return pool.install(|| Shape::of(value));
+        }
+    }
+    #[cfg(not(feature = "parallel"))]
+    let _ = opts;
+    Shape::of(value)
+}
+
+// Infers the types of a document in any serde data format, e.g. YAML or MessagePack,
+// without building the document in memory. Arrays are folded as they're read.
+pub fn infer_schema_from<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    opts: &GeneratorOptions,
+) -> Result<Schema, JsoncError> {
+    let shape = Shape::deserialize(deserializer).map_err(|e| JsoncError::Parse {
+        path: None,
+        line: 0,
+        column: 0,
+        message: e.to_string(),
+    })?;
+    schema_from_shape(&shape, opts)
+}
+
+// Infers the types of everything folded into a shape
+pub fn schema_from_shape(shape: &Shape, opts: &GeneratorOptions) -> Result<Schema, JsoncError> {
+    infer(shape, None, opts, DEFAULT_TYPE_NAME)
+}
+
+// Like `schema_from_shape`, with the root named `default_name` unless the options name it.
+// `value`, the document folded into `shape` if it's at hand, is where examples come from.
+pub fn schema_from_shape_named(
+    shape: &Shape,
+    value: Option<&Value>,
+    opts: &GeneratorOptions,
+    default_name: &str,
+) -> Result<Schema, JsoncError> {
+    infer(shape, value, opts, default_name)
+}
+
+// Examples are taken from `value` when the document is at hand, so that arrays outside
+// of other arrays show their actual elements rather than the folded one
+fn infer(
+    shape: &Shape,
+    value: Option<&Value>,
+    opts: &GeneratorOptions,
+    default_name: &str,
+) -> Result<Schema, JsoncError> {
+    let key = match (shape, opts.root_name.as_deref()) {
+        (Shape::Array { .. }, Some(root_name)) => opts.singular(root_name),
+        (Shape::Object { .. } | Shape::Array { .. }, root_name) => {
+            root_name.unwrap_or(default_name).to_string()
+        }
+        (other, _) => return Err(JsoncError::UnsupportedRoot { kind: other.kind() }),
+    };
+    let mut types = vec![];
+    let root = infer_type(&key, "", shape, value, opts, &mut types);
+    // Named like the elements would be if there was no root name, the alias gets a suffix.
+    // Names are compared as they're generated, with the type prefix and suffix.
+    let alias = matches!(root, TypeRef::Array { .. }).then(|| {
+        let name = opts.root_name.as_deref().unwrap_or(default_name);
+        match root.innermost() {
+            TypeRef::Object(element) if opts.type_name(element) == opts.type_name(name) => {
+                format!("{name}List")
+            }
+            _ => name.to_string(),
+        }
+    });
+    let mut conflicts = vec![];
+    find_conflicts(shape, "", &mut conflicts);
+    Ok(Schema {
+        root,
+        alias,
+        types,
+        conflicts,
+    })
+}
+
+// The type `type_mapping` gives a field of `shape`, arrays of it if it holds arrays
+fn mapped_type(shape: &Shape, logical: LogicalType) -> TypeRef {
+    match shape {
+        Shape::Array { element, has_null } => TypeRef::Array {
+            element: Box::new(mapped_type(element, logical)),
+            optional: *has_null,
+        },
+        _ => TypeRef::Primitive(logical),
+    }
+}
+
+const CONFLICT_REASON: &str = "samples of different types";
+
+// Why nothing better than Any could be inferred for the samples folded into `shape`
+fn untyped_reason(shape: &Shape, type_ref: &TypeRef) -> Option<&'static str> {
+    let reason = type_ref.untyped_reason()?;
+    match shape.innermost() {
+        Shape::Conflict(_) => Some(CONFLICT_REASON),
+        _ => Some(reason),
+    }
+}
+
+// What the samples of `shape` are, if they can't be read as `logical`
+fn mapping_conflict(shape: &Shape, logical: LogicalType) -> Option<&'static str> {
+    let observed = match shape.innermost() {
+        Shape::Object { .. } => return Some("objects"),
+        Shape::Scalar(scalar) => LogicalType::of(scalar),
+        Shape::NumericString { .. } => LogicalType::String,
+        _ => LogicalType::Any,
+    };
+    (!logical.reads(observed)).then_some(observed.name())
+}
+
+fn infer_type(
+    key: &str,
+    path: &str,
+    shape: &Shape,
+    value: Option<&Value>,
+    opts: &GeneratorOptions,
+    types: &mut Vec<TypeDef>,
+) -> TypeRef {
+    match shape {
+        Shape::Object {
+            fields,
+            absent,
+            nullable,
+        } => {
+            let fields = fields
+                .iter()
+                .map(|(field_key, field)| {
+                    let field_nullable = nullable.contains(field_key) && !field.is_null();
+                    let mut field_value = value.and_then(|value| value.get(field_key));
+                    let mut field_path = format!("{path}/{field_key}");
+                    let name = opts.renames.lookup(field_key, &field_path);
+                    // The field is typed as what its wrappers hold
+                    let mut field = field;
+                    let mut flattened = vec![];
+                    while let Some((inner_key, inner)) = opts.wrapped(field) {
+                        field = inner;
+                        field_value = field_value.and_then(|value| value.get(inner_key));
+                        field_path = format!("{field_path}/{inner_key}");
+                        flattened.push(inner_key.to_string());
+                    }
+                    let example = opts.examples.and_then(|mode| match field_value {
+                        Some(field_value) => example(field_value, mode),
+                        None => example(&field.to_value(), mode),
+                    });
+                    // Types of renamed fields are named after the new name
+                    let type_key = name.unwrap_or(field_key);
+                    let mapped = opts.type_mapping.lookup(field_key, &field_path);
+                    let type_ref = match mapped {
+                        Some(logical) => mapped_type(field, logical),
+                        None => infer_type(type_key, &field_path, field, field_value, opts, types),
+                    };
+                    Field {
+                        key: field_key.to_string(),
+                        name: name.map(String::from),
+                        todo: untyped_reason(field, &type_ref).filter(|_| opts.todo_comments),
+                        type_ref,
+                        optional: field.is_null(),
+                        absent: absent.contains(field_key),
+                        nullable: field_nullable,
+                        example,
+                        string_encoded: opts.coerce_numeric_strings
+                            && mapped.is_none()
+                            && matches!(field.innermost(), Shape::NumericString { .. }),
+                        flattened,
+                        mapping_conflict: mapped
+                            .and_then(|logical| mapping_conflict(field, logical)),
+                    }
+                })
+                .collect();
+            let type_def = TypeDef {
+                key: key.to_string(),
+                path: path.to_string(),
+                fields,
+            };
+            TypeRef::Object(add_type(type_def, opts, types))
+        }
+        Shape::Array { element, has_null } => {
+            let element_path = format!("{path}/[]");
+            let element = match element.as_ref() {
+                Shape::Null => TypeRef::Primitive(LogicalType::Any),
+                // Elements of arrays in arrays have no key of their own, they're named
+                // after the array's, e.g. GridItem for the objects in {"grid": [[{...}]]}
+                element @ Shape::Array { .. } => {
+                    let element_key = format!("{key}_item");
+                    infer_type(&element_key, &element_path, element, None, opts, types)
+                }
+                element => {
+                    let element_key = opts.element_key(key);
+                    infer_type(&element_key, &element_path, element, None, opts, types)
+                }
+            };
+            TypeRef::Array {
+                element: Box::new(element),
+                optional: *has_null,
+            }
+        }
+        Shape::Scalar(scalar) => TypeRef::Primitive(LogicalType::of(scalar)),
+        Shape::NumericString { float, .. } if opts.coerce_numeric_strings => {
+            TypeRef::Primitive(if *float {
+                LogicalType::Float
+            } else {
+                LogicalType::Int
+            })
+        }
+        Shape::NumericString { .. } => TypeRef::Primitive(LogicalType::String),
+        Shape::Null | Shape::Conflict(_) => TypeRef::Primitive(LogicalType::Any),
+    }
+}
+
+// Where in `shape` the samples were of different types, arrays of them as the field
+// holding the array
+fn find_conflicts(shape: &Shape, path: &str, conflicts: &mut Vec<(String, Vec<&'static str>)>) {
+    match shape {
+        Shape::Object { fields, .. } => fields
+            .iter()
+            .for_each(|(key, field)| find_conflicts(field, &format!("{path}/{key}"), conflicts)),
+        Shape::Array { element, .. } => find_conflicts(element, &format!("{path}/[]"), conflicts),
+        Shape::Conflict(kinds) => {
+            let pointer = path.trim_end_matches("/[]").to_string();
+            conflicts.push((pointer, kinds.iter().copied().collect()))
+        }
+        _ => {}
+    }
+}
+
+// Adds `type_def` to `types` and returns its key, or the key of a type that's already there
+// with the same name and fields. If there's one with the same name but other fields, the
+// type is named after its path instead, e.g. company_address for /company/address, and
+// failing that, numbered. The root keeps its name, the other type is renamed.
+fn add_type(mut type_def: TypeDef, opts: &GeneratorOptions, types: &mut Vec<TypeDef>) -> String {
+    if let Some(index) = position_named(types, &type_def.key, opts) {
+        if type_def.path.is_empty() {
+            let existing = types[index].clone();
+            let key = path_key(&existing, types, opts);
+            rename_refs(types, &existing.key, &key);
+            rename_refs(std::slice::from_mut(&mut type_def), &existing.key, &key);
+            types[index].key = key;
+        } else if same_fields(&types[index].fields, &type_def.fields) {
+            return types[index].key.clone();
+        } else {
+            type_def.key = path_key(&type_def, types, opts);
+        }
+    }
+    let key = type_def.key.clone();
+    types.push(type_def);
+    key
+}
+
+// Where the type among `types` named like `key` is
+fn position_named(types: &[TypeDef], key: &str, opts: &GeneratorOptions) -> Option<usize> {
+    let name = opts.type_name(key);
+    types
+        .iter()
+        .position(|existing| opts.type_name(&existing.key) == name)
+}
+
+// A key for `type_def` made of its path, with array elements as item, e.g. users_item_address
+// for /users/[]/address, numbered if one of `types` is already named like it
+fn path_key(type_def: &TypeDef, types: &[TypeDef], opts: &GeneratorOptions) -> String {
+    let base = type_def
+        .path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| if segment == "[]" { "item" } else { segment })
+        .collect::<Vec<_>>()
+        .join("_");
+    let mut key = base.clone();
+    let mut number = 1;
+    while position_named(types, &key, opts).is_some() {
+        number += 1;
+        key = format!("{base}_{number}");
+    }
+    key
+}
+
+// Points every field referring to the type under `from` to the one under `to`
+fn rename_refs(types: &mut [TypeDef], from: &str, to: &str) {
+    fn rename(type_ref: &mut TypeRef, from: &str, to: &str) {
+        match type_ref {
+            TypeRef::Object(key) if key == from => *key = to.to_string(),
+            TypeRef::Array { element, .. } => rename(element, from, to),
+            _ => {}
+        }
+    }
+    for field in types.iter_mut().flat_map(|type_def| &mut type_def.fields) {
+        rename(&mut field.type_ref, from, to);
+    }
+}
+
+// Whether two types declare the same fields, examples and comments aside
+fn same_fields(a: &[Field], b: &[Field]) -> bool {
+    let declared = |field: &Field| {
+        (
+            field.key.clone(),
+            field.name.clone(),
+            field.type_ref.clone(),
+            (field.optional, field.absent, field.nullable),
+            field.string_encoded,
+            field.flattened.clone(),
+        )
+    };
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| declared(a) == declared(b))
+}
+
+// Types are named in PascalCase here, each language names them its own way in generated code
+fn type_json(type_ref: &TypeRef) -> Value {
+    match type_ref {
+        TypeRef::Primitive(logical) => json!({ "kind": logical.name() }),
+        TypeRef::Object(key) => json!({ "kind": "object", "ref": pascal_case(key) }),
+        TypeRef::Array { element, optional } => json!({
+            "kind": "array",
+            "element": type_json(element),
+            "optional_elements": optional,
+        }),
+    }
+}
+
+impl Schema {
+    // Fields nothing better than Any could be inferred for, by their JSON pointer, and why.
+    // A root array of nothing but nulls or empty arrays is there too, as the empty pointer.
+    pub fn untyped_fields(&self) -> Vec<(String, &'static str)> {
+        let root = self
+            .root
+            .untyped_reason()
+            .map(|reason| (String::new(), reason));
+        root.into_iter()
+            .chain(self.types.iter().flat_map(|type_def| {
+                type_def.fields.iter().filter_map(|field| {
+                    let reason = field.type_ref.untyped_reason()?;
+                    Some((format!("{}{}", type_def.path, field.pointer()), reason))
+                })
+            }))
+            .map(|(pointer, reason)| match self.conflict_at(&pointer) {
+                Some(_) => (pointer, CONFLICT_REASON),
+                None => (pointer, reason),
+            })
+            .collect()
+    }
+
+    // The types of the samples at `pointer`, if they were different
+    fn conflict_at(&self, pointer: &str) -> Option<&[&'static str]> {
+        self.conflicts
+            .iter()
+            .find(|(at, _)| at == pointer)
+            .map(|(_, kinds)| kinds.as_slice())
+    }
+
+    // What's worth warning about in the schema, by where it is in the document
+    pub fn warnings(&self) -> Warnings {
+        let mut warnings = Warnings::default();
+        for (pointer, reason) in self.untyped_fields() {
+            let what = if pointer.is_empty() {
+                "the elements of the document"
+            } else {
+                pointer.as_str()
+            };
+            match self.conflict_at(&pointer) {
+                Some(kinds) => {
+                    let kinds = kinds.join(", ");
+                    let message =
+                        format!("{what}: samples of different types ({kinds}), typed as Any");
+                    warnings.push_at(WarningCategory::Conflict, pointer.clone(), message);
+                }
+                None => {
+                    let message = format!("the type of {what} could not be inferred ({reason})");
+                    warnings.push_at(WarningCategory::Untyped, pointer.clone(), message);
+                }
+            }
+        }
+        for type_def in &self.types {
+            for field in &type_def.fields {
+                let (Some(observed), TypeRef::Primitive(logical)) =
+                    (field.mapping_conflict, field.type_ref.innermost())
+                else {
+                    continue;
+                };
+                let pointer = format!("{}{}", type_def.path, field.pointer());
+                let message = format!(
+                    "--type-mapping types {pointer} as {}, but the samples are {observed}",
+                    logical.name()
+                );
+                warnings.push_at(WarningCategory::TypeMapping, pointer, message);
+            }
+        }
+        warnings
+    }
+
+    // The type generated for the document itself, or for the elements of a root array.
+    // It's the last one, since every type comes after the ones nested in it.
+    pub fn root_type(&self) -> Option<&TypeDef> {
+        match self.root.innermost() {
+            TypeRef::Object(_) => self.types.last(),
+            _ => None,
+        }
+    }
+
+    // The type of the document, then those of every field
+    pub fn type_refs(&self) -> impl Iterator<Item = &TypeRef> {
+        let fields = self.types.iter().flat_map(|type_def| &type_def.fields);
+        std::iter::once(&self.root).chain(fields.map(|field| &field.type_ref))
+    }
+
+    // The schema as data for other tools, this shape is kept stable:
+    //
+    // version: SCHEMA_VERSION
+    // root: TYPE of the document
+    // alias: name of the alias for the document if it's an array, in PascalCase, or null
+    // types: in the order they should be written in, each with
+    //   name: the type's name, the key it's named after in PascalCase
+    //   key: the JSON key it's named after, or the root name
+    //   fields: each with
+    //     key: the JSON key, as it was in the input
+    //     type: TYPE of the field
+    //     optional: whether the sample value was null
+    //     absent: whether the key was missing from some of the samples
+    //     nullable: whether the value was null in some of the samples, but not all of them
+    //     array_depth: how many arrays the innermost element type is nested in
+    //     flattened: keys of the single-field objects the value was wrapped in, outermost
+    //       first, empty unless wrappers are flattened
+    //
+    // TYPE is an object with a `kind` of string, int, float, bool, date, uuid, decimal,
+    // timestamp-millis, any, object or array. Objects refer to one of the types by name in `ref`, arrays have the TYPE of
+    // their `element`, and whether elements may be null in `optional_elements`.
+    pub fn to_json(&self) -> Value {
+        let types: Vec<Value> = self
+            .types
+            .iter()
+            .map(|type_def| {
+                let fields: Vec<Value> = type_def
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        json!({
+                            "key": field.key,
+                            "type": type_json(&field.type_ref),
+                            "optional": field.optional,
+                            "absent": field.absent,
+                            "nullable": field.nullable,
+                            "array_depth": field.type_ref.array_depth(),
+                            "flattened": field.flattened,
+                        })
+                    })
+                    .collect();
+                json!({
+                    "name": pascal_case(&type_def.key),
+                    "key": type_def.key,
+                    "fields": fields,
+                })
+            })
+            .collect();
+        json!({
+            "version": SCHEMA_VERSION,
+            "root": type_json(&self.root),
+            "alias": self.alias.as_deref().map(pascal_case),
+            "types": types,
+        })
+    }
+
+    // The types as a Graphviz digraph named `name`: a node per type, labeled with its name
+    // and number of fields, and an edge from every type to each type its fields refer to,
+    // dashed if they're arrays of it
+    pub fn to_dot(&self, name: &str) -> String {
+        let mut dot = format!(
+            "digraph \"{}\" {{\n    node [shape=box];\n",
+            dot_escape(name)
+        );
+        for (index, type_def) in self.types.iter().enumerate() {
+            let count = type_def.fields.len();
+            let fields = if count == 1 { "field" } else { "fields" };
+            dot.push_str(&format!(
+                "    t{index} [label=\"{}\\n{count} {fields}\"];\n",
+                dot_escape(&pascal_case(&type_def.key)),
+            ));
+        }
+        for (index, type_def) in self.types.iter().enumerate() {
+            for field in &type_def.fields {
+                if !matches!(field.type_ref.innermost(), TypeRef::Object(_)) {
+                    continue;
+                }
+                let depth = field.type_ref.array_depth();
+                let path = format!(
+                    "{}{}{}",
+                    type_def.path,
+                    field.pointer(),
+                    "/[]".repeat(depth)
+                );
+                let Some(target) = self.types.iter().position(|t| t.path == path) else {
+                    continue;
+                };
+                let style = if depth > 0 { ", style=dashed" } else { "" };
+                dot.push_str(&format!(
+                    "    t{index} -> t{target} [label=\"{}\"{style}];\n",
+                    dot_escape(&field.key)
+                ));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+// `text` escaped to go in a quoted DOT string
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,151 @@
+use serde_json::Value;
+
+use crate::error::JsoncError;
+use crate::shape::Shape;
+
+fn unescape(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+// How values are called in messages
+pub fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+// Why there's nothing at a segment of a pointer
+enum Missing {
+    // The keys the object has instead
+    Key(Vec<String>),
+    // The length of the array
+    Index(usize),
+    NotAnIndex,
+    // The kind of the scalar
+    Scalar(&'static str),
+}
+
+// What a JSON pointer can navigate: documents, and the shapes of documents, where any
+// index of an array is the elements folded into one
+trait Pointee: Sized {
+    fn child(self, segment: &str) -> Result<Self, Missing>;
+
+    // Whether it's an object, whose children are named by their key
+    fn has_keys(&self) -> bool;
+}
+
+impl Pointee for Value {
+    fn child(self, segment: &str) -> Result<Value, Missing> {
+        match self {
+            Value::Object(mut map) => map
+                .remove(segment)
+                .ok_or_else(|| Missing::Key(map.keys().cloned().collect())),
+            Value::Array(mut arr) => match segment.parse::<usize>() {
+                Ok(index) if index < arr.len() => Ok(arr.swap_remove(index)),
+                Ok(_) => Err(Missing::Index(arr.len())),
+                Err(_) => Err(Missing::NotAnIndex),
+            },
+            other => Err(Missing::Scalar(kind(&other))),
+        }
+    }
+
+    fn has_keys(&self) -> bool {
+        self.is_object()
+    }
+}
+
+impl Pointee for Shape {
+    fn child(self, segment: &str) -> Result<Shape, Missing> {
+        match self {
+            Shape::Object { mut fields, .. } => fields
+                .remove(segment)
+                .ok_or_else(|| Missing::Key(fields.into_keys().collect())),
+            Shape::Array { element, .. } => match segment.parse::<usize>() {
+                Ok(_) => Ok(*element),
+                Err(_) => Err(Missing::NotAnIndex),
+            },
+            other => Err(Missing::Scalar(other.kind())),
+        }
+    }
+
+    fn has_keys(&self) -> bool {
+        matches!(self, Shape::Object { .. })
+    }
+}
+
+// Navigates to the value at `pointer` (RFC 6901), returning it along with the last
+// object key on the way, which makes for a good name for the selected value.
+pub fn select(value: Value, pointer: &str) -> Result<(Value, Option<String>), JsoncError> {
+    select_in(value, pointer)
+}
+
+// Like `select`, in the shape of a document. Every index of an array selects its elements
+// folded into one, so none is out of bounds.
+pub fn select_shape(shape: Shape, pointer: &str) -> Result<(Shape, Option<String>), JsoncError> {
+    select_in(shape, pointer)
+}
+
+fn select_in<T: Pointee>(value: T, pointer: &str) -> Result<(T, Option<String>), JsoncError> {
+    if pointer.is_empty() {
+        return Ok((value, None));
+    }
+    let select_error = |message: String| JsoncError::Select {
+        pointer: pointer.to_string(),
+        message,
+    };
+    let Some(segments) = pointer.strip_prefix('/') else {
+        return Err(select_error(String::from(
+            "a JSON pointer must start with '/'",
+        )));
+    };
+
+    let mut current = value;
+    let mut name = None;
+    let mut location = String::new();
+
+    for raw_segment in segments.split('/') {
+        let segment = unescape(raw_segment);
+        let at = if location.is_empty() {
+            "/"
+        } else {
+            location.as_str()
+        };
+        let has_keys = current.has_keys();
+        current = match current.child(&segment) {
+            Ok(inner) => inner,
+            Err(Missing::Key(keys)) => {
+                return Err(select_error(format!(
+                    "no key '{segment}' at {at}, available keys are: {}",
+                    keys.join(", ")
+                )))
+            }
+            Err(Missing::Index(len)) => {
+                return Err(select_error(format!(
+                    "index {segment} is out of bounds at {at}, the array has {len} elements"
+                )))
+            }
+            Err(Missing::NotAnIndex) => {
+                return Err(select_error(format!(
+                    "'{segment}' is not an array index at {at}"
+                )))
+            }
+            Err(Missing::Scalar(kind)) => {
+                return Err(select_error(format!(
+                    "cannot select '{segment}' at {at}, it is a {kind}"
+                )))
+            }
+        };
+        if has_keys {
+            name = Some(segment);
+        }
+        location.push('/');
+        location.push_str(raw_segment);
+    }
+
+    Ok((current, name))
+}
@@ -0,0 +1,353 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Number, Value};
+
+use crate::select::kind;
+
+// Arrays at least this long are folded on several threads, shorter ones aren't worth it
+#[cfg(feature = "parallel")]
+static PARALLEL_THRESHOLD: usize = 4096;
+
+// What's known about the values seen at one place in a document: a partial schema.
+// Shapes of several samples merge into one covering all of them, so folding every value
+// into one is all types are inferred from, and memory depends on the schema rather than
+// on the size of the input.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    Null,
+    // Numbers, strings and booleans keep the first value seen, a float over an integer
+    Scalar(Value),
+    // A string every sample of was a number, e.g. "19.99". Keeps the first one seen, and
+    // whether any of them was a float.
+    NumericString {
+        value: String,
+        float: bool,
+    },
+    // Along with the keys missing from some of the objects folded in, and the ones whose
+    // value was null in some of them
+    Object {
+        fields: BTreeMap<String, Shape>,
+        absent: BTreeSet<String>,
+        nullable: BTreeSet<String>,
+    },
+    // The elements folded into one, and whether any of them was null
+    Array {
+        element: Box<Shape>,
+        has_null: bool,
+    },
+    // Samples of different JSON types, e.g. a number in one and a string in another, by
+    // the name of each. Nothing but Any covers them all.
+    Conflict(BTreeSet<&'static str>),
+}
+
+impl Shape {
+    pub fn of(value: &Value) -> Shape {
+        match value {
+            Value::Null => Shape::Null,
+            Value::Object(obj) => Shape::object(
+                obj.iter()
+                    .map(|(key, value)| (key.clone(), Shape::of(value)))
+                    .collect(),
+            ),
+            Value::Array(items) => Shape::Array {
+                element: Box::new(fold_elements(items)),
+                has_null: items.iter().any(Value::is_null),
+            },
+            Value::String(s) => Shape::string(s.clone()),
+            scalar => Shape::Scalar(scalar.clone()),
+        }
+    }
+
+    // An object seen once, so no key is missing from it yet
+    pub fn object(fields: BTreeMap<String, Shape>) -> Shape {
+        let nullable = fields
+            .iter()
+            .filter(|(_, shape)| shape.is_null())
+            .map(|(key, _)| key.clone())
+            .collect();
+        Shape::Object {
+            fields,
+            absent: BTreeSet::new(),
+            nullable,
+        }
+    }
+
+    fn string(s: String) -> Shape {
+        match s.parse::<Number>() {
+            Ok(n) => Shape::NumericString {
+                float: n.is_f64(),
+                value: s,
+            },
+            Err(_) => Shape::Scalar(Value::String(s)),
+        }
+    }
+
+    pub fn merge(self, other: Shape) -> Shape {
+        match (self, other) {
+            (Shape::Null, other) | (other, Shape::Null) => other,
+            (
+                Shape::Object {
+                    fields: mut a,
+                    absent: mut a_absent,
+                    nullable: mut a_nullable,
+                },
+                Shape::Object {
+                    fields: b,
+                    absent: b_absent,
+                    nullable: b_nullable,
+                },
+            ) => {
+                a_absent.extend(b_absent);
+                a_nullable.extend(b_nullable);
+                a_absent.extend(a.keys().filter(|key| !b.contains_key(*key)).cloned());
+                for (key, shape) in b {
+                    let merged = match a.remove(&key) {
+                        Some(existing) => existing.merge(shape),
+                        None => {
+                            a_absent.insert(key.clone());
+                            shape
+                        }
+                    };
+                    a.insert(key, merged);
+                }
+                Shape::Object {
+                    fields: a,
+                    absent: a_absent,
+                    nullable: a_nullable,
+                }
+            }
+            (
+                Shape::Array {
+                    element: a,
+                    has_null: a_null,
+                },
+                Shape::Array {
+                    element: b,
+                    has_null: b_null,
+                },
+            ) => Shape::Array {
+                element: Box::new(a.merge(*b)),
+                has_null: a_null || b_null,
+            },
+            (Shape::NumericString { value, float: a }, Shape::NumericString { float: b, .. }) => {
+                Shape::NumericString {
+                    value,
+                    float: a || b,
+                }
+            }
+            // Some samples aren't numbers after all
+            (Shape::NumericString { value, .. }, Shape::Scalar(Value::String(_))) => {
+                Shape::Scalar(Value::String(value))
+            }
+            // A field that is an integer in one sample and a float in another is a float
+            (Shape::Scalar(Value::Number(a)), Shape::Scalar(Value::Number(b)))
+                if !a.is_f64() && b.is_f64() =>
+            {
+                Shape::Scalar(Value::Number(b))
+            }
+            (Shape::Conflict(mut a), Shape::Conflict(b)) => {
+                a.extend(b);
+                Shape::Conflict(a)
+            }
+            (Shape::Conflict(mut kinds), other) | (other, Shape::Conflict(mut kinds)) => {
+                kinds.insert(other.kind());
+                Shape::Conflict(kinds)
+            }
+            (a, b) if a.kind() == b.kind() => a,
+            (a, b) => Shape::Conflict(BTreeSet::from([a.kind(), b.kind()])),
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Shape::Null)
+    }
+
+    // How many arrays and objects deep the shape is nested
+    pub fn depth(&self) -> usize {
+        match self {
+            Shape::Object { fields, .. } => {
+                1 + fields.values().map(Shape::depth).max().unwrap_or(0)
+            }
+            Shape::Array { element, .. } => 1 + element.depth(),
+            _ => 0,
+        }
+    }
+
+    // The shape of the elements of the innermost array, or this shape if it's not an array
+    pub fn innermost(&self) -> &Shape {
+        match self {
+            Shape::Array { element, .. } => element.innermost(),
+            other => other,
+        }
+    }
+
+    // Name of the JSON type, as in error messages
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Shape::Null => "null",
+            Shape::Scalar(value) => kind(value),
+            Shape::NumericString { .. } => "string",
+            Shape::Object { .. } => "object",
+            Shape::Array { .. } => "array",
+            Shape::Conflict(_) => "value of several types",
+        }
+    }
+
+    // A document standing for every value folded into the shape, arrays hold their folded
+    // element followed by a null if any of the elements was null. Samples of different
+    // types stand as null, which is typed as Any too.
+    pub fn to_value(&self) -> Value {
+        match self {
+            Shape::Null | Shape::Conflict(_) => Value::Null,
+            Shape::Scalar(value) => value.clone(),
+            Shape::NumericString { value, .. } => Value::String(value.clone()),
+            Shape::Object { fields, .. } => Value::Object(
+                fields
+                    .iter()
+                    .map(|(key, shape)| (key.clone(), shape.to_value()))
+                    .collect::<Map<String, Value>>(),
+            ),
+            Shape::Array { element, has_null } => {
+                let element = (!element.is_null()).then(|| element.to_value());
+                let null = has_null.then_some(Value::Null);
+                Value::Array(element.into_iter().chain(null).collect())
+            }
+        }
+    }
+}
+
+// Whether folding `value` in would leave the shape as it is, in which case it's skipped
+// without building a shape for it, which is most elements in long arrays of similar
+// objects. Never true for a value that would change the shape.
+fn absorbs(shape: &Shape, value: &Value) -> bool {
+    match (shape, value) {
+        (_, Value::Null) => true,
+        (Shape::Conflict(kinds), value) => kinds.contains(kind(value)),
+        (Shape::Scalar(Value::Number(a)), Value::Number(b)) => a.is_f64() || !b.is_f64(),
+        (Shape::Scalar(Value::String(_)), Value::String(_)) => true,
+        (Shape::Scalar(Value::Bool(_)), Value::Bool(_)) => true,
+        (Shape::NumericString { float, .. }, Value::String(s)) => match s.parse::<Number>() {
+            Ok(n) => *float || !n.is_f64(),
+            Err(_) => false,
+        },
+        (
+            Shape::Object {
+                fields,
+                absent,
+                nullable,
+            },
+            Value::Object(obj),
+        ) => {
+            obj.iter().all(|(key, value)| {
+                let known = fields.get(key).is_some_and(|field| absorbs(field, value));
+                known && (!value.is_null() || nullable.contains(key))
+            }) && fields
+                .keys()
+                .all(|key| obj.contains_key(key) || absent.contains(key))
+        }
+        (Shape::Array { element, has_null }, Value::Array(items)) => items
+            .iter()
+            .all(|item| absorbs(element, item) && (*has_null || !item.is_null())),
+        _ => false,
+    }
+}
+
+fn fold_element(shape: Shape, item: &Value) -> Shape {
+    match absorbs(&shape, item) {
+        true => shape,
+        false => shape.merge(Shape::of(item)),
+    }
+}
+
+// The shapes of the elements folded into one. Folding is associative, so long arrays are
+// split between threads with the same result.
+fn fold_elements(items: &[Value]) -> Shape {
+    #[cfg(feature = "parallel")]
+    if items.len() >= PARALLEL_THRESHOLD {
+        return items
+            .par_iter()
+            .fold(|| Shape::Null, fold_element)
+            .reduce(|| Shape::Null, Shape::merge);
+    }
+    items.iter().fold(Shape::Null, fold_element)
+}
+
+// Shapes can be read from any serde data format, folding arrays as they're parsed rather
+// than building the whole document first
+impl<'de> Deserialize<'de> for Shape {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ShapeVisitor)
+    }
+}
+
+struct ShapeVisitor;
+
+impl<'de> Visitor<'de> for ShapeVisitor {
+    type Value = Shape;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Shape, E> {
+        Ok(Shape::Scalar(Value::Bool(v)))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Shape, E> {
+        Ok(Shape::Scalar(Value::from(v)))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Shape, E> {
+        Ok(Shape::Scalar(Value::from(v)))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Shape, E> {
+        Ok(Number::from_f64(v).map_or(Shape::Null, |n| Shape::Scalar(Value::Number(n))))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Shape, E> {
+        Ok(Shape::string(v.to_string()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Shape, E> {
+        Ok(Shape::string(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<Shape, E> {
+        Ok(Shape::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Shape, E> {
+        Ok(Shape::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Shape, D::Error> {
+        Shape::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Shape, A::Error> {
+        // Every element is folded in as soon as it's parsed
+        let mut element = Shape::Null;
+        let mut has_null = false;
+        while let Some(shape) = seq.next_element::<Shape>()? {
+            has_null |= shape.is_null();
+            element = element.merge(shape);
+        }
+        Ok(Shape::Array {
+            element: Box::new(element),
+            has_null,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Shape, A::Error> {
+        let mut fields = BTreeMap::new();
+        while let Some((key, shape)) = map.next_entry::<String, Shape>()? {
+            fields.insert(key, shape);
+        }
+        Ok(Shape::object(fields))
+    }
+}
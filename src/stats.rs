@@ -0,0 +1,128 @@
+use serde_json::{json, Value};
+
+use crate::schema::{LogicalType, Schema, TypeRef};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StatsFormat {
+    Text,
+    Json,
+}
+
+// Numbers describing what was inferred from the input, to judge whether a sample
+// was good enough to generate from
+#[derive(Default, Debug)]
+pub struct Stats {
+    pub types: usize,
+    pub fields: usize,
+    // Fields that were null or empty arrays, or of different types in different samples, so
+    // nothing better than Any could be inferred
+    pub any_fields: usize,
+    // Where those fields are, as JSON pointers
+    pub any_paths: Vec<String>,
+    // Fields that may be null or missing, typed as optional
+    pub optional_fields: usize,
+    pub max_depth: usize,
+    // Strings that look like dates or UUIDs
+    pub dates: usize,
+    pub uuids: usize,
+    // Subtrees typed as Any, and inputs skipped, with --best-effort
+    pub degraded: usize,
+    // Fields left out of the input before it was generated from
+    pub excluded: usize,
+    // What was warned about, as printed at the end of the run
+    pub warnings: Vec<String>,
+}
+
+pub(crate) fn is_date(s: &str) -> bool {
+    // YYYY-MM-DD, optionally followed by a time as in RFC 3339
+    let bytes = s.as_bytes();
+    bytes.len() >= 10
+        && bytes[..10].iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        })
+        && (bytes.len() == 10 || bytes[10] == b'T' || bytes[10] == b' ')
+}
+
+pub(crate) fn is_uuid(s: &str) -> bool {
+    s.len() == 36
+        && s.bytes().enumerate().all(|(i, b)| match i {
+            8 | 13 | 18 | 23 => b == b'-',
+            _ => b.is_ascii_hexdigit(),
+        })
+}
+
+impl Stats {
+    // Counts what was inferred from a document, as the types generated from it
+    pub fn collect(&mut self, schema: &Schema) {
+        self.types += schema.types.len();
+        for type_def in &schema.types {
+            // Every key on the way is an object, elements of arrays are in the array's
+            let depth = type_def
+                .path
+                .split('/')
+                .filter(|s| !s.is_empty() && *s != "[]");
+            self.max_depth = self.max_depth.max(depth.count() + 1);
+            self.fields += type_def.fields.len();
+            self.optional_fields += type_def
+                .fields
+                .iter()
+                .filter(|f| f.may_be_missing())
+                .count();
+        }
+        for (pointer, _) in schema.untyped_fields() {
+            self.any_fields += 1;
+            self.any_paths.push(pointer);
+        }
+        for type_ref in schema.type_refs() {
+            match type_ref.innermost() {
+                TypeRef::Primitive(LogicalType::Date) => self.dates += 1,
+                TypeRef::Primitive(LogicalType::Uuid) => self.uuids += 1,
+                _ => {}
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "types": self.types,
+            "fields": self.fields,
+            "any_fields": self.any_fields,
+            "any_paths": self.any_paths,
+            "optional_fields": self.optional_fields,
+            "max_depth": self.max_depth,
+            "degraded": self.degraded,
+            "excluded_fields": self.excluded,
+            "warnings": self.warnings,
+            "logical_types": {
+                "date": self.dates,
+                "uuid": self.uuids,
+            },
+        })
+    }
+
+    // Prints the stats to stderr, leaving stdout to the generated code
+    pub fn print(&self, format: StatsFormat) {
+        match format {
+            StatsFormat::Json => eprintln!("{}", self.to_json()),
+            StatsFormat::Text => {
+                eprintln!("types: {}", self.types);
+                eprintln!("fields: {}", self.fields);
+                eprintln!("fields inferred as Any: {}", self.any_fields);
+                for path in &self.any_paths {
+                    eprintln!("  {path}");
+                }
+                eprintln!("optional fields: {}", self.optional_fields);
+                eprintln!("max nesting depth: {}", self.max_depth);
+                eprintln!("degraded subtrees: {}", self.degraded);
+                eprintln!("excluded fields: {}", self.excluded);
+                eprintln!("dates: {}", self.dates);
+                eprintln!("uuids: {}", self.uuids);
+                eprintln!("warnings: {}", self.warnings.len());
+                for warning in &self.warnings {
+                    eprintln!("  {warning}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::io::{self, Read};
+
+use serde::Deserialize;
+
+use crate::error::JsoncError;
+use crate::shape::Shape;
+
+// Keeps track of how much of the input was read, to tell inputs cut off by --max-size
+// and empty ones apart from invalid JSON
+struct Counted<R> {
+    inner: R,
+    count: u64,
+    has_content: bool,
+}
+
+impl<R: Read> Read for Counted<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count += n as u64;
+        self.has_content |= buf[..n].iter().any(|b| !b.is_ascii_whitespace());
+        Ok(n)
+    }
+}
+
+// Reads the shape of plain JSON, or NDJSON whose documents are merged, straight from
+// `reader` without holding the whole input in memory. Every array is folded into a single
+// element as it's read, that's all types are inferred from.
+pub fn parse_streaming(
+    reader: impl Read,
+    ndjson: bool,
+    max_size: Option<u64>,
+    path: Option<&str>,
+) -> Result<Shape, JsoncError> {
+    // One byte more than allowed is enough to tell the input is too large
+    let limit = max_size.map_or(u64::MAX, |max_size| max_size.saturating_add(1));
+    let mut counted = Counted {
+        inner: reader.take(limit),
+        count: 0,
+        has_content: false,
+    };
+
+    let parsed = if ndjson {
+        serde_json::Deserializer::from_reader(&mut counted)
+            .into_iter::<Shape>()
+            .try_fold(Shape::Null, |merged, shape| Ok(merged.merge(shape?)))
+    } else {
+        let mut deserializer = serde_json::Deserializer::from_reader(&mut counted);
+        Shape::deserialize(&mut deserializer).and_then(|shape| deserializer.end().map(|_| shape))
+    };
+
+    match max_size {
+        Some(max_size) if counted.count > max_size => {
+            return Err(JsoncError::TooLarge {
+                path: path.map(String::from),
+                max_size,
+            })
+        }
+        _ if !counted.has_content => {
+            return Err(JsoncError::EmptyInput {
+                path: path.map(String::from),
+            })
+        }
+        _ => {}
+    }
+    parsed.map_err(|e| JsoncError::json(path, e))
+}
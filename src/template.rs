@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::fs;
+
+use handlebars::{no_escape, Handlebars};
+use serde_json::Value;
+
+use crate::error::JsoncError;
+use crate::options::GeneratorOptions;
+use crate::schema::Schema;
+
+static TEMPLATE_NAME: &str = "template";
+
+// An output language defined by a Handlebars template rather than a `LanguageFormatter`.
+// The template is rendered once per document, with the inferred schema as its context.
+// That's the JSON written by `Schema::to_json`, where every field also has its key in
+// camelCase as its `name`.
+pub struct Template {
+    // Where the template was read from, for error messages
+    path: String,
+    handlebars: Handlebars<'static>,
+}
+
+impl Template {
+    pub fn new(path: &str, text: &str) -> Result<Template, JsoncError> {
+        let mut handlebars = Handlebars::new();
+        // Typos in field names are errors rather than empty output, and the output isn't HTML
+        handlebars.set_strict_mode(true);
+        handlebars.register_escape_fn(no_escape);
+        handlebars
+            .register_template_string(TEMPLATE_NAME, text)
+            .map_err(|e| JsoncError::Template {
+                path: path.to_string(),
+                line: e.line_no.unwrap_or(0),
+                column: e.column_no.unwrap_or(0),
+                message: e.reason().to_string(),
+            })?;
+        Ok(Template {
+            path: path.to_string(),
+            handlebars,
+        })
+    }
+
+    pub fn from_file(path: &str) -> Result<Template, JsoncError> {
+        let text = fs::read_to_string(path).map_err(|e| JsoncError::io(Some(path), e))?;
+        Template::new(path, &text)
+    }
+
+    // Renders the code for the types of `schema`, returning it along with the number of
+    // types in it
+    pub fn render(
+        &self,
+        schema: &Schema,
+        opts: &GeneratorOptions,
+    ) -> Result<(String, usize), JsoncError> {
+        let context = template_context(schema, opts);
+        let type_count = context["types"].as_array().map_or(0, Vec::len);
+        let code = self
+            .handlebars
+            .render(TEMPLATE_NAME, &context)
+            .map_err(|e| JsoncError::Template {
+                path: self.path.clone(),
+                line: e.line_no.unwrap_or(0),
+                column: e.column_no.unwrap_or(0),
+                message: e.desc,
+            })?;
+        Ok((code, type_count))
+    }
+}
+
+// The schema, as templates see it
+pub fn template_context(schema: &Schema, opts: &GeneratorOptions) -> Value {
+    let mut context = schema.to_json();
+    // Types are named with the prefix and suffix, and so are the references to them
+    let names: HashMap<String, String> = context["types"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|type_def| {
+            let name = type_def["name"].as_str()?;
+            let key = type_def["key"].as_str()?;
+            Some((name.to_string(), opts.type_name(key)))
+        })
+        .collect();
+    rename_types(&mut context, &names);
+    context["alias"] = Value::from(schema.alias.as_deref().map(|alias| opts.type_name(alias)));
+    if let Some(types) = context["types"].as_array_mut() {
+        for field in types.iter_mut().flat_map(|type_def| {
+            type_def["fields"]
+                .as_array_mut()
+                .into_iter()
+                .flat_map(|fields| fields.iter_mut())
+        }) {
+            let key = field["key"].as_str().unwrap_or_default();
+            let name = opts
+                .cased_field_name(key)
+                .unwrap_or_else(|| opts.naming.field_name(key));
+            field["name"] = Value::from(name);
+        }
+    }
+    context
+}
+
+// Replaces the type names in `value`, the schema as JSON, with the ones in `names`
+fn rename_types(value: &mut Value, names: &HashMap<String, String>) {
+    match value {
+        Value::Object(obj) => {
+            for (key, inner) in obj.iter_mut() {
+                match (key.as_str(), &inner) {
+                    ("name" | "ref", Value::String(name)) if names.contains_key(name) => {
+                        *inner = Value::from(names[name].as_str());
+                    }
+                    _ => rename_types(inner, names),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| rename_types(item, names)),
+        _ => {}
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::error::JsoncError;
+use crate::rename::normalize_pointer;
+use crate::schema::{LogicalType, Schema};
+
+// Types given to fields instead of the ones inferred for them, e.g. id to uuid. Entries are
+// bare keys or JSON pointers, the way they are in a `RenameMap`, and pointers win over bare
+// keys. Fields holding arrays get arrays of the type.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TypeMapping {
+    // By the entry as it's matched, to the type and the entry as it was written
+    entries: BTreeMap<String, (LogicalType, String)>,
+}
+
+impl TypeMapping {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Reads a JSON object from entries to type names, e.g. {"amount": "decimal"}
+    pub fn from_json(text: &str) -> Result<TypeMapping, JsoncError> {
+        let invalid = |message: String| JsoncError::Options { message };
+        let value: Value = serde_json::from_str(text).map_err(|e| invalid(e.to_string()))?;
+        let Value::Object(entries) = value else {
+            return Err(invalid(String::from("expected a JSON object of types")));
+        };
+        let mut mapping = TypeMapping::new();
+        for (entry, name) in entries {
+            let name = name
+                .as_str()
+                .ok_or_else(|| invalid(format!("the type of '{entry}' should be a string")))?;
+            mapping.insert_named(&entry, name)?;
+        }
+        Ok(mapping)
+    }
+
+    pub fn insert(&mut self, entry: &str, logical: LogicalType) {
+        let matched = if entry.starts_with('/') {
+            normalize_pointer(entry)
+        } else {
+            entry.to_string()
+        };
+        self.entries.insert(matched, (logical, entry.to_string()));
+    }
+
+    // Like `insert`, with the type by its name, e.g. timestamp-millis
+    pub fn insert_named(&mut self, entry: &str, name: &str) -> Result<(), JsoncError> {
+        let logical = LogicalType::from_name(name).ok_or_else(|| JsoncError::Options {
+            message: format!(
+                "unknown type '{name}' for '{entry}', expected one of {}",
+                LogicalType::NAMES.join(", ")
+            ),
+        })?;
+        self.insert(entry, logical);
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // The type of the field under `key` at `path`, e.g. /users/[]/id, if it's given one
+    pub fn lookup(&self, key: &str, path: &str) -> Option<LogicalType> {
+        self.entries
+            .get(path)
+            .or_else(|| self.entries.get(key))
+            .map(|(logical, _)| *logical)
+    }
+
+    // Entries that don't match any field of `schema`, they're likely typos
+    pub fn unused(&self, schema: &Schema) -> Vec<String> {
+        let fields: Vec<(&str, String)> = schema
+            .types
+            .iter()
+            .flat_map(|type_def| {
+                type_def.fields.iter().map(|field| {
+                    (
+                        field.key.as_str(),
+                        format!("{}{}", type_def.path, field.pointer()),
+                    )
+                })
+            })
+            .collect();
+        self.entries
+            .iter()
+            .filter(|(entry, _)| {
+                !fields
+                    .iter()
+                    .any(|(key, path)| key == entry || path == *entry)
+            })
+            .map(|(_, (_, written))| written.clone())
+            .collect()
+    }
+}
@@ -0,0 +1,150 @@
+// Problems that don't stop generation but that the user should know about. They are
+// only ever printed to stderr, so that stdout stays valid generated code.
+#[derive(Clone, Default, Debug)]
+pub struct Warnings {
+    warnings: Vec<Warning>,
+}
+
+// What a warning is about, warnings are printed grouped by it, in this order
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum WarningCategory {
+    // Options in the config file that were ignored
+    Config,
+    // Input that was read differently than written, e.g. NaN as a float
+    Input,
+    // Subtrees nested too deep, typed as Any
+    Depth,
+    // Documents left out of the run
+    Skipped,
+    // Fields whose type couldn't be inferred
+    Untyped,
+    // Fields typed as Any because the samples were of different types
+    Conflict,
+    // Renames that match no key
+    Rename,
+    // Types already defined differently in the --append file
+    Append,
+    // Types given to fields that don't exist or that the samples can't be read as
+    TypeMapping,
+}
+
+impl WarningCategory {
+    pub const ALL: [WarningCategory; 9] = [
+        WarningCategory::Config,
+        WarningCategory::Input,
+        WarningCategory::Depth,
+        WarningCategory::Skipped,
+        WarningCategory::Untyped,
+        WarningCategory::Conflict,
+        WarningCategory::Rename,
+        WarningCategory::Append,
+        WarningCategory::TypeMapping,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WarningCategory::Config => "config",
+            WarningCategory::Input => "input",
+            WarningCategory::Depth => "depth",
+            WarningCategory::Skipped => "skipped",
+            WarningCategory::Untyped => "untyped",
+            WarningCategory::Conflict => "conflict",
+            WarningCategory::Rename => "rename",
+            WarningCategory::Append => "append",
+            WarningCategory::TypeMapping => "type-mapping",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<WarningCategory> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.name() == name.to_lowercase())
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Warning {
+    pub category: WarningCategory,
+    // The file or stdin it's about, if it's about an input
+    pub source: Option<String>,
+    // Where in the document it's about, as a JSON pointer with [] for the elements of
+    // arrays, e.g. /users/[]/address
+    pub pointer: Option<String>,
+    pub message: String,
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.source {
+            Some(source) => write!(f, "{source}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Warnings {
+    pub fn push(&mut self, category: WarningCategory, message: impl Into<String>) {
+        self.warnings.push(Warning {
+            category,
+            source: None,
+            pointer: None,
+            message: message.into(),
+        });
+    }
+
+    // A warning about the value at `pointer`
+    pub fn push_at(
+        &mut self,
+        category: WarningCategory,
+        pointer: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        self.warnings.push(Warning {
+            category,
+            source: None,
+            pointer: Some(pointer.into()),
+            message: message.into(),
+        });
+    }
+
+    pub fn extend(&mut self, other: Warnings) {
+        self.warnings.extend(other.warnings);
+    }
+
+    // The warnings, with `source` as the source of the ones that had none
+    pub fn with_source(mut self, source: &str) -> Warnings {
+        for warning in &mut self.warnings {
+            warning.source.get_or_insert_with(|| source.to_string());
+        }
+        self
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Warning> {
+        self.warnings.iter()
+    }
+
+    // How many warnings are in one of `categories`, or in any if there are none
+    pub fn count_in(&self, categories: &[WarningCategory]) -> usize {
+        self.warnings
+            .iter()
+            .filter(|warning| categories.is_empty() || categories.contains(&warning.category))
+            .count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.warnings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    // Prints the warnings grouped by category, in the order they were found within each
+    pub fn print(&self) {
+        let mut warnings: Vec<&Warning> = self.warnings.iter().collect();
+        warnings.sort_by_key(|warning| warning.category);
+        warnings
+            .iter()
+            .for_each(|warning| eprintln!("warning[{}]: {warning}", warning.category.name()));
+    }
+}
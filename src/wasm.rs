@@ -0,0 +1,14 @@
+use wasm_bindgen::prelude::*;
+
+use crate::generate::generate_code;
+use crate::options::options_from_json;
+
+// Entry point for JavaScript, e.g. a web playground. `options_json` is a JSON object of
+// `GeneratorOptions` fields, or an empty string for the defaults. Errors are thrown as
+// their message.
+#[wasm_bindgen]
+pub fn generate(json_text: &str, language: &str, options_json: &str) -> Result<String, JsValue> {
+    options_from_json(options_json)
+        .and_then(|opts| generate_code(json_text, language, &opts))
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
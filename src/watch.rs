@@ -0,0 +1,66 @@
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::cli_error::Error;
+
+// Editors tend to write a file in several steps, changes this close together are
+// handled as a single one
+static DEBOUNCE: Duration = Duration::from_millis(200);
+
+// Calls `regenerate` once and then every time the file at `input` changes, printing
+// the summary it returns. Failed regenerations are reported and watching goes on.
+// Only returns if watching can't be set up, Ctrl-C exits the process.
+pub fn watch(
+    input: &Path,
+    mut regenerate: impl FnMut() -> Result<String, Error>,
+) -> Result<(), Error> {
+    let watch_error = |message: String| Error::Watch {
+        path: input.display().to_string(),
+        message,
+    };
+
+    ctrlc::set_handler(|| std::process::exit(0)).map_err(|e| watch_error(e.to_string()))?;
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(|e| watch_error(e.to_string()))?;
+    // The parent directory is watched rather than the file, since editors often save by
+    // replacing the file, which would end a watch on the file itself
+    let directory = match input.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    watcher
+        .watch(directory, RecursiveMode::NonRecursive)
+        .map_err(|e| watch_error(e.to_string()))?;
+
+    let run = |regenerate: &mut dyn FnMut() -> Result<String, Error>| match regenerate() {
+        Ok(summary) => eprintln!("{summary}"),
+        Err(e) => eprintln!("error: {e}"),
+    };
+
+    run(&mut regenerate);
+    eprintln!(
+        "watching {} for changes, press Ctrl-C to stop",
+        input.display()
+    );
+
+    let is_input_change = |event: &notify::Event| {
+        matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+            && event
+                .paths
+                .iter()
+                .any(|path| path.file_name() == input.file_name())
+    };
+
+    while let Ok(event) = rx.recv() {
+        if !event.as_ref().is_ok_and(is_input_change) {
+            continue;
+        }
+        while rx.recv_timeout(DEBOUNCE).is_ok() {}
+        run(&mut regenerate);
+    }
+    Ok(())
+}
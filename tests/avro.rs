@@ -0,0 +1,38 @@
+use jsonc::{generate_code, GeneratorOptions};
+
+const SAMPLE: &str = r#"[{"a": 1, "b": "x"}, {"a": null}]"#;
+
+fn avro(opts: &GeneratorOptions) -> serde_json::Value {
+    let schema = generate_code(SAMPLE, "avro", opts).unwrap();
+    serde_json::from_str(&schema).unwrap()
+}
+
+fn field<'a>(schema: &'a serde_json::Value, name: &str) -> &'a serde_json::Value {
+    let fields = schema["items"]["fields"].as_array().unwrap();
+    fields.iter().find(|field| field["name"] == name).unwrap()
+}
+
+#[test]
+fn fields_that_may_be_null_or_missing_are_unions_with_null() {
+    let schema = avro(&GeneratorOptions::default());
+    for name in ["a", "b"] {
+        let field = field(&schema, name);
+        assert_eq!(field["type"][0], "null");
+        assert_eq!(field.get("default"), Some(&serde_json::Value::Null));
+    }
+}
+
+#[test]
+fn strict_null_only_defaults_fields_that_may_be_missing() {
+    let opts = GeneratorOptions {
+        strict_null: true,
+        ..GeneratorOptions::default()
+    };
+    let schema = avro(&opts);
+    let a = field(&schema, "a");
+    assert_eq!(a["type"][0], "null");
+    assert!(a.get("default").is_none());
+    let b = field(&schema, "b");
+    assert_eq!(b["type"][0], "null");
+    assert_eq!(b.get("default"), Some(&serde_json::Value::Null));
+}
@@ -0,0 +1,27 @@
+use jsonc::{infer_schema, GeneratorOptions, WarningCategory};
+use serde_json::json;
+
+#[test]
+fn fields_of_different_types_are_untyped_with_a_warning() {
+    let document = json!({"a": [1, "x"], "b": [{"c": true}, {"c": {"d": 1}}], "e": null});
+    let schema = infer_schema(&document, &GeneratorOptions::default()).unwrap();
+    let mut conflicts: Vec<_> = schema
+        .warnings()
+        .iter()
+        .filter(|warning| warning.category == WarningCategory::Conflict)
+        .map(|warning| warning.pointer.clone().unwrap())
+        .collect();
+    conflicts.sort();
+    assert_eq!(conflicts, ["/a", "/b/[]/c"]);
+    let untyped = schema.untyped_fields();
+    assert!(untyped.contains(&(String::from("/a"), "samples of different types")));
+    assert!(untyped.contains(&(String::from("/e"), "all samples null")));
+}
+
+#[test]
+fn integers_and_floats_are_not_a_conflict() {
+    let document = json!([{"a": 1}, {"a": 1.5}, {"a": 2}]);
+    let schema = infer_schema(&document, &GeneratorOptions::default()).unwrap();
+    assert!(schema.conflicts.is_empty());
+    assert!(schema.warnings().is_empty());
+}
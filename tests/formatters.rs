@@ -0,0 +1,60 @@
+use std::io::{ErrorKind, Write};
+use std::process::{Command, Stdio};
+
+const SAMPLE: &str = r#"[
+    {"id": 1, "user_name": "ann", "score": 1.5, "tags": ["a"], "address": {"city": "x", "zip": null}},
+    {"id": 2, "user_name": "bob", "is_admin": true, "tags": [], "address": {"city": "y"}}
+]"#;
+
+// Writes `input` to the stdin of `program` and returns its stdout, or None if it isn't installed
+fn pipe(program: &str, args: &[&str], input: &str) -> Option<String> {
+    let mut child = match Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(err) if err.kind() == ErrorKind::NotFound => return None,
+        Err(err) => panic!("{program}: {err}"),
+    };
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{program}: {stderr}\n{input}");
+    Some(String::from_utf8(output.stdout).unwrap())
+}
+
+fn jsonc(language: &str) -> String {
+    let args = ["--no-config", "-q", "-l", language];
+    pipe(env!("CARGO_BIN_EXE_jsonc"), &args, SAMPLE).unwrap()
+}
+
+// Formatting what's generated leaves it as it is, when the formatter is installed
+fn assert_formatted(language: &str, program: &str, args: &[&str]) {
+    let code = jsonc(language);
+    match pipe(program, args, &code) {
+        Some(formatted) => assert_eq!(code, formatted),
+        None => eprintln!("{program} isn't installed, skipping"),
+    }
+}
+
+#[test]
+fn rustfmt_leaves_rust_as_it_is() {
+    assert_formatted(
+        "rust",
+        "rustfmt",
+        &["--edition", "2021", "--emit", "stdout"],
+    );
+}
+
+#[test]
+fn gofmt_leaves_go_as_it_is() {
+    assert_formatted("go", "gofmt", &[]);
+}
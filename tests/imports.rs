@@ -0,0 +1,112 @@
+use jsonc::naming::Case;
+use jsonc::{generate_code, GeneratorOptions};
+
+const SAMPLE: &str = r#"{"user_name": "ann", "nickname": null, "123": 1}"#;
+
+fn generate(language: &str, opts: &GeneratorOptions) -> String {
+    generate_code(SAMPLE, language, opts).unwrap()
+}
+
+// What the types use is imported, untyped fields are JSON elements kotlinx.serialization
+// has a serializer for
+#[test]
+fn kotlin_imports_what_it_uses() {
+    let kotlin = generate("kotlin", &GeneratorOptions::default());
+    assert!(
+        kotlin.starts_with(
+            "import kotlinx.serialization.SerialName\n\
+             import kotlinx.serialization.Serializable\n\
+             import kotlinx.serialization.json.JsonElement\n\n"
+        ),
+        "{kotlin}"
+    );
+    assert!(kotlin.contains("val nickname: JsonElement?"), "{kotlin}");
+    assert!(
+        kotlin.contains("@SerialName(\"user_name\")\n    val userName: String,"),
+        "{kotlin}"
+    );
+
+    let kotlin = generate("kotlin", &GeneratorOptions::new().annotations(false));
+    assert!(
+        kotlin.starts_with("import kotlinx.serialization.json.JsonElement\n\n"),
+        "{kotlin}"
+    );
+}
+
+// Runs `code` with python3, unless it isn't installed. Whether it ran and succeeded.
+fn run_python(code: &str) -> bool {
+    let output = match std::process::Command::new("python3")
+        .args(["-c", code])
+        .output()
+    {
+        Ok(output) => output,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            eprintln!("python3 isn't installed, skipping");
+            return false;
+        }
+        Err(err) => panic!("python3: {err}"),
+    };
+    output.status.success()
+}
+
+#[test]
+fn python_imports_what_it_uses() {
+    let opts = GeneratorOptions::new().emit_sample(true);
+    let sample = r#"[{"a": [1, null], "b": null}, {"c": 1}]"#;
+    let python = generate_code(sample, "python", &opts).unwrap();
+    assert!(
+        python
+            .starts_with("from dataclasses import dataclass\nfrom typing import Any, Optional\n\n"),
+        "{python}"
+    );
+    assert!(run_python(&python), "{python}");
+
+    let python = generate_code(r#"{"a": 1}"#, "python", &opts).unwrap();
+    assert!(
+        python.starts_with("from dataclasses import dataclass\n\n@dataclass"),
+        "{python}"
+    );
+    assert!(run_python(&python), "{python}");
+}
+
+// Keys starting with a digit or _ make fields that don't, Pydantic would take them for
+// private attributes and never read the key
+#[test]
+fn pydantic_imports_what_it_uses() {
+    let sample = r#"{"123": 1, "_id": "x", "name": null, "user_name": "ann"}"#;
+    let pydantic = generate_code(sample, "pydantic", &GeneratorOptions::default()).unwrap();
+    assert!(
+        pydantic.starts_with(
+            "from pydantic import BaseModel, ConfigDict, Field\nfrom typing import Any\n\n"
+        ),
+        "{pydantic}"
+    );
+    for line in pydantic.lines().filter(|line| line.starts_with("    ")) {
+        assert!(!line.trim_start().starts_with('_'), "{pydantic}");
+    }
+    assert!(
+        pydantic.contains("    field_123: int = Field(alias=\"123\")\n"),
+        "{pydantic}"
+    );
+    assert!(
+        pydantic.contains("    id: str = Field(alias=\"_id\")\n"),
+        "{pydantic}"
+    );
+    let parse = format!("{pydantic}\nassert Model.model_validate_json({sample:?}).field_123 == 1");
+    if run_python("import pydantic") {
+        assert!(run_python(&parse), "{parse}");
+    }
+
+    let opts = GeneratorOptions::new().field_case(Case::Keep);
+    let pydantic = generate_code(sample, "pydantic", &opts).unwrap();
+    assert!(
+        pydantic.contains("    field_id: str = Field(alias=\"_id\")\n"),
+        "{pydantic}"
+    );
+
+    let pydantic = generate_code(r#"{"a": 1}"#, "pydantic", &GeneratorOptions::default()).unwrap();
+    assert!(
+        pydantic.starts_with("from pydantic import BaseModel\n\nclass Model(BaseModel):"),
+        "{pydantic}"
+    );
+}
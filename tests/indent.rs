@@ -0,0 +1,34 @@
+use jsonc::{generate_code, GeneratorOptions};
+
+const SAMPLE: &str = r#"{"id": 1, "address": {"city": "x"}}"#;
+
+// Fields are a level deeper than their class, which closes at its own level
+#[test]
+fn scala_classes_close_at_their_level() {
+    let scala = generate_code(SAMPLE, "scala", &GeneratorOptions::default()).unwrap();
+    assert!(
+        scala.contains("case class Root(\n  address: Address,\n  id: Int,\n)"),
+        "{scala}"
+    );
+
+    let opts = GeneratorOptions::new()
+        .namespace("com.example.Types")
+        .indent("\t");
+    let scala = generate_code(SAMPLE, "scala", &opts).unwrap();
+    assert!(
+        scala.contains("\tcase class Root(\n\t\taddress: Address,\n\t\tid: Int,\n\t)"),
+        "{scala}"
+    );
+}
+
+#[test]
+fn java_and_scala_honour_the_indent() {
+    let java = generate_code(SAMPLE, "java", &GeneratorOptions::default()).unwrap();
+    assert!(java.contains("\n    public Integer id;"), "{java}");
+
+    let opts = GeneratorOptions::new().indent("\t");
+    let java = generate_code(SAMPLE, "java", &opts).unwrap();
+    assert!(java.contains("\n\tpublic Integer id;"), "{java}");
+    let scala = generate_code(SAMPLE, "scala", &opts).unwrap();
+    assert!(scala.contains("\n\tid: Int,\n)"), "{scala}");
+}
@@ -0,0 +1,135 @@
+use jsonc::{generate_code, GeneratorOptions};
+
+const SAMPLE: &str = r#"{"": 1, " ": 2, "unknown": 3, "field_1": 4}"#;
+
+fn generate(language: &str) -> String {
+    generate_code(SAMPLE, language, &GeneratorOptions::default()).unwrap()
+}
+
+// Keys with nothing to make a name of get placeholders that don't collide with real keys,
+// and keep the key they were renamed from
+#[test]
+fn empty_and_blank_keys_get_placeholder_names() {
+    let rust = generate("rust");
+    assert!(
+        rust.contains("#[serde(rename = \"\")]\n    pub field_2: isize,"),
+        "{rust}"
+    );
+    assert!(
+        rust.contains("#[serde(rename = \" \")]\n    pub field_3: isize,"),
+        "{rust}"
+    );
+    assert!(rust.contains("    pub field_1: isize,"), "{rust}");
+    assert!(rust.contains("    pub unknown: isize,"), "{rust}");
+
+    let java = generate("java");
+    assert!(
+        java.contains("@JsonProperty(\"\")\n    public Integer field2;"),
+        "{java}"
+    );
+    assert!(
+        java.contains("@JsonProperty(\" \")\n    public Integer field3;"),
+        "{java}"
+    );
+    assert!(java.contains("public Integer unknown;"), "{java}");
+}
+
+#[test]
+fn placeholder_names_are_unique() {
+    for language in ["rust", "go", "java", "kotlin", "python", "scala"] {
+        let code = generate(language);
+        for name in [
+            "field_1", "field_2", "field_3", "field1", "field2", "field3", "Field1",
+        ] {
+            let declared = code
+                .lines()
+                .filter(|line| {
+                    line.split(|c: char| !c.is_alphanumeric() && c != '_')
+                        .any(|word| word == name)
+                })
+                .count();
+            assert!(
+                declared <= 1,
+                "{language}: {name} declared {declared} times\n{code}"
+            );
+        }
+    }
+}
+
+// Keys that aren't names, or are keywords, are made into names that keep the key they're for
+#[test]
+fn keywords_and_symbols_keep_their_keys() {
+    let sample = r#"{"type": 1, "self": 2, "a\"b": 3, "123": 4, "class": 5}"#;
+    let generate = |language| generate_code(sample, language, &GeneratorOptions::default());
+
+    let rust = generate("rust").unwrap();
+    assert!(
+        rust.starts_with("#[derive(serde::Serialize, serde::Deserialize)]\npub struct"),
+        "{rust}"
+    );
+    for (key, name) in [
+        ("type", "r#type"),
+        ("self", "self_"),
+        ("a\\\"b", "a_b"),
+        ("123", "_123"),
+    ] {
+        let field = format!("#[serde(rename = \"{key}\")]\n    pub {name}: isize,");
+        assert!(rust.contains(&field), "{rust}");
+    }
+    assert!(rust.contains("\n    pub class: isize,"), "{rust}");
+
+    let java = generate("java").unwrap();
+    assert!(
+        java.contains("@JsonProperty(\"class\")\n    public Integer class_;"),
+        "{java}"
+    );
+    assert!(
+        java.contains("@JsonProperty(\"a\\\"b\")\n    public Integer a_b;"),
+        "{java}"
+    );
+
+    let scala = generate("scala").unwrap();
+    assert!(scala.contains("`type`: Int,"), "{scala}");
+    assert!(scala.contains("`a\"b`: Int,"), "{scala}");
+
+    let go = generate("go").unwrap();
+    let line = go.lines().find(|line| line.contains("`json:\"123\"`"));
+    assert!(line.unwrap().trim_start().starts_with("X123 "), "{go}");
+}
+
+// Structs holding a struct that needs no attributes need it to derive serde's traits too
+#[test]
+fn every_rust_struct_derives_serde() {
+    let rust = generate_code(
+        r#"{"a-b": 1, "owner": {"x": 1}}"#,
+        "rust",
+        &GeneratorOptions::default(),
+    )
+    .unwrap();
+    for name in ["Owner", "Root"] {
+        let declaration =
+            format!("#[derive(serde::Serialize, serde::Deserialize)]\npub struct {name} {{");
+        assert!(rust.contains(&declaration), "{rust}");
+    }
+
+    let opts = GeneratorOptions::new().annotations(false);
+    let rust = generate_code(r#"{"owner": {"x": 1}}"#, "rust", &opts).unwrap();
+    assert!(!rust.contains("#[derive"), "{rust}");
+}
+
+// Dart names are ASCII only, the key is still what's read
+#[test]
+fn dart_names_are_ascii() {
+    let dart = generate_code(r#"{"café": 1}"#, "dart", &GeneratorOptions::default()).unwrap();
+    assert!(dart.contains("  final int caf;"), "{dart}");
+    assert!(dart.contains("caf: json['café'] as int,"), "{dart}");
+}
+
+// std.json reads fields named like their keys, so keys without letters are kept in @"..."
+#[test]
+fn zig_keeps_keys_without_letters() {
+    let zig = generate_code(r#"{"☃": 1, " ": 2}"#, "zig", &GeneratorOptions::default()).unwrap();
+    assert!(zig.contains("    @\"☃\": i64,"), "{zig}");
+    assert!(zig.contains("    @\" \": i64,"), "{zig}");
+    assert!(!zig.contains("field_1"), "{zig}");
+}
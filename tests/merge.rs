@@ -0,0 +1,32 @@
+use std::process::Command;
+
+// Runs the jsonc binary on the fixtures in tests/fixtures/merge, returning what it wrote
+fn jsonc(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_jsonc"))
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/merge"))
+        .args(["--no-config", "-q"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn merged_files_type_keys_missing_from_some_of_them_as_optional() {
+    let code = jsonc(&["-l", "rust", "--merge", "first.json", "second.json"]);
+    assert!(code.contains("pub a: isize,"), "{code}");
+    assert!(code.contains("pub b: Option<String>,"), "{code}");
+    assert!(code.contains("pub c: Option<isize>,"), "{code}");
+}
+
+#[test]
+fn merged_files_tell_missing_keys_and_nulls_apart_with_strict_null() {
+    let code = jsonc(&["-l", "rust", "--merge", "--strict-null", "*.json"]);
+    assert!(
+        code.contains("#[serde(default)]\n    pub b: Option<String>,"),
+        "{code}"
+    );
+    assert!(code.contains("    pub c: Option<isize>,"), "{code}");
+    assert!(!code.contains("#[serde(default)]\n    pub c"), "{code}");
+}
@@ -0,0 +1,51 @@
+use jsonc::naming::{camelcase, pascal_case, Case};
+use jsonc::{generate_code, DefaultNaming, GeneratorOptions, NamingStrategy};
+
+#[test]
+fn default_naming_names_types_and_fields_after_snake_case_keys() {
+    let naming = DefaultNaming;
+    assert_eq!(naming.type_name("user_address"), "UserAddress");
+    assert_eq!(naming.field_name("user_id"), "userId");
+    assert_eq!(naming.exported_field_name("user_id"), "UserId");
+    assert_eq!(pascal_case("created_at"), "CreatedAt");
+    assert_eq!(camelcase("created_at"), "createdAt");
+}
+
+#[test]
+fn default_naming_singularizes_plain_plurals() {
+    let naming = DefaultNaming;
+    assert_eq!(naming.singular("users"), "user");
+    assert_eq!(naming.singular("categories"), "category");
+    assert_eq!(naming.singular("data"), "data");
+}
+
+#[test]
+fn cases_split_words_at_case_changes_and_acronyms() {
+    assert_eq!(Case::Snake.apply("userID"), "user_id");
+    assert_eq!(Case::Pascal.apply("userID"), "UserId");
+    assert_eq!(Case::Camel.apply("HTTPServer"), "httpServer");
+    assert_eq!(Case::Snake.apply("created-at2"), "created_at2");
+    assert_eq!(Case::Keep.apply("userID"), "userID");
+}
+
+#[derive(Debug)]
+struct Prefixed;
+
+impl NamingStrategy for Prefixed {
+    fn type_name(&self, key: &str) -> String {
+        format!("Api{}", DefaultNaming.type_name(key))
+    }
+}
+
+#[test]
+fn strategies_name_every_generated_type() {
+    let opts = GeneratorOptions::new().naming(Prefixed);
+    let code = generate_code(r#"{"home_address": {"zip": "1"}}"#, "rust", &opts).unwrap();
+    assert!(code.contains("pub struct ApiHomeAddress {"), "{code}");
+    assert!(code.contains("pub home_address: ApiHomeAddress,"), "{code}");
+    let code = generate_code(r#"{"home_address": {"zip": "1"}}"#, "go", &opts).unwrap();
+    assert!(
+        code.contains("HomeAddress ApiHomeAddress `json:\"home_address\"`"),
+        "{code}"
+    );
+}
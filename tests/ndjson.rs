@@ -0,0 +1,25 @@
+use jsonc::{generate_code, GeneratorOptions};
+
+const SAMPLE: &str = "{\"a\": 1, \"b\": \"x\"}\n{\"a\": 2}\n{\"a\": 3, \"c\": [{\"d\": 1}]}\n";
+
+#[test]
+fn keys_missing_from_some_lines_are_optional() {
+    let code = generate_code(SAMPLE, "rust", &GeneratorOptions::default()).unwrap();
+    assert!(code.contains("pub a: isize,"), "{code}");
+    assert!(code.contains("pub b: Option<String>,"), "{code}");
+    assert!(code.contains("pub c: Option<Vec<C>>,"), "{code}");
+}
+
+#[test]
+fn strict_null_defaults_keys_missing_from_some_lines() {
+    let opts = GeneratorOptions {
+        strict_null: true,
+        ..GeneratorOptions::default()
+    };
+    let code = generate_code(SAMPLE, "rust", &opts).unwrap();
+    assert!(
+        code.contains("#[serde(default)]\n    pub b: Option<String>,"),
+        "{code}"
+    );
+    assert!(!code.contains("#[serde(default)]\n    pub a"), "{code}");
+}
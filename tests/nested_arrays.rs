@@ -0,0 +1,32 @@
+use jsonc::{generate_code, GeneratorOptions};
+
+fn rust(input: &str) -> String {
+    generate_code(input, "rust", &GeneratorOptions::default()).unwrap()
+}
+
+// Elements of the same array are of one type, keys missing from some of them are optional
+#[test]
+fn elements_of_arrays_in_a_root_array_are_one_type() {
+    let code = rust(r#"[[{"a": 1}], [{"b": 2}]]"#);
+    assert_eq!(code.matches("pub struct").count(), 1, "{code}");
+    assert!(code.contains("pub struct RootItem {"), "{code}");
+    assert!(code.contains("pub a: Option<isize>,"), "{code}");
+    assert!(code.contains("pub b: Option<isize>,"), "{code}");
+    assert!(
+        code.contains("pub type Root = Vec<Vec<RootItem>>;"),
+        "{code}"
+    );
+}
+
+// Elements of arrays in arrays elsewhere are named after the path to them
+#[test]
+fn elements_of_arrays_in_different_arrays_get_distinct_names() {
+    let code = rust(r#"{"x": [[{"a": 1}]], "y": [[{"b": 2}]], "z": {"x": [[{"c": 3}]]}}"#);
+    assert!(code.contains("pub x: Vec<Vec<XItem>>,"), "{code}");
+    assert!(code.contains("pub y: Vec<Vec<YItem>>,"), "{code}");
+    assert!(code.contains("pub x: Vec<Vec<ZXItemItem>>,"), "{code}");
+    assert!(
+        code.contains("pub struct XItem {\n    pub a: isize,\n}"),
+        "{code}"
+    );
+}
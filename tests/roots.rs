@@ -0,0 +1,102 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+// Runs the jsonc binary in `dir` with `input` on stdin
+fn jsonc_in(dir: &Path, args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_jsonc"))
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn jsonc(input: &str) -> Output {
+    jsonc_in(Path::new("."), &["--no-config", "-l", "rust"], input)
+}
+
+fn generate(args: &[&str], input: &str) -> String {
+    let output = jsonc_in(Path::new("."), args, input);
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn scalar_roots_fail_with_a_message() {
+    for (input, kind) in [("42", "number"), ("\"x\"", "string"), ("true", "boolean")] {
+        let output = jsonc(input);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(output.status.code(), Some(1), "{input}: {stderr}");
+        assert!(
+            stderr.contains(&format!("cannot generate types for a top-level {kind}")),
+            "{input}: {stderr}"
+        );
+        assert!(output.stdout.is_empty(), "{input}");
+    }
+}
+
+#[test]
+fn empty_root_arrays_are_untyped_with_a_warning() {
+    let output = jsonc("[]");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stderr}");
+    assert!(
+        stdout.contains("pub type Root = Vec<Option<Value>>;"),
+        "{stdout}"
+    );
+    assert!(stderr.contains("warning[untyped]"), "{stderr}");
+}
+
+#[test]
+fn roots_are_named_the_way_each_language_names_them() {
+    for (language, declaration) in [
+        ("rust", "pub struct Root {"),
+        ("go", "type AutoGenerated struct {"),
+        ("python", "class Model:"),
+        ("pydantic", "class Model(BaseModel):"),
+        ("ocaml", "type t = {"),
+        ("scala", "case class Root("),
+    ] {
+        let code = generate(&["--no-config", "-q", "-l", language], r#"{"a": 1}"#);
+        assert!(code.contains(declaration), "{language}: {code}");
+    }
+}
+
+#[test]
+fn root_name_names_the_root() {
+    let code = generate(
+        &["--no-config", "-q", "-l", "go", "--root-name", "Package"],
+        r#"{"a": 1}"#,
+    );
+    assert!(code.contains("type Package struct {"), "{code}");
+
+    let code = generate(
+        &["--no-config", "-q", "-l", "rust", "--root-name=user"],
+        r#"[{"a": 1}]"#,
+    );
+    assert!(code.contains("pub struct User {"), "{code}");
+    assert!(code.contains("pub type UserList = Vec<User>;"), "{code}");
+}
+
+#[test]
+fn root_name_can_be_configured() {
+    let dir = std::env::temp_dir().join(format!("jsonc-root-name-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("jsonc.toml"), "root_name = \"Package\"\n").unwrap();
+    let output = jsonc_in(&dir, &["-q", "-l", "rust"], r#"{"a": 1}"#);
+    fs::remove_dir_all(&dir).unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("pub struct Package {"), "{output:?}");
+}
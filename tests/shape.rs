@@ -0,0 +1,47 @@
+use jsonc::Shape;
+use serde_json::{json, Value};
+
+// Folding an array skips elements that can't change its shape, which must be the same as
+// merging the shape of every element
+fn assert_folds_like_merging(items: Value) {
+    let merged = items
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(Shape::of)
+        .fold(Shape::Null, Shape::merge);
+    let Shape::Array { element, .. } = Shape::of(&items) else {
+        panic!("not an array");
+    };
+    assert_eq!(*element, merged, "{items}");
+}
+
+#[test]
+fn elements_alike_but_for_their_values_fold_into_one() {
+    assert_folds_like_merging(json!([
+        {"a": 1, "b": {"c": "x"}},
+        {"a": 2, "b": {"c": "y"}},
+        {"a": 3, "b": {"c": "z"}},
+    ]));
+}
+
+#[test]
+fn elements_that_differ_anywhere_are_folded_in() {
+    assert_folds_like_merging(json!([
+        {"a": 1, "b": {"c": "x"}},
+        {"a": 1.5, "b": {"c": "x"}},
+        {"a": 1, "b": {"c": null}},
+        {"a": 1, "b": {}},
+        {"a": 1, "b": {"c": "x", "d": [1]}},
+        {"a": 1, "b": {"c": "x", "d": [null, 2.5]}},
+        {"b": {"c": "12"}},
+        {"a": "1", "b": {"c": "x"}},
+        {"a": 1, "b": {"c": "x"}},
+    ]));
+}
+
+#[test]
+fn numeric_strings_stop_being_numeric_on_the_first_that_isnt() {
+    assert_folds_like_merging(json!(["1", "2", "2.5", "x", "3"]));
+    assert_folds_like_merging(json!([[1], ["x"], [true], [1], [{"a": 1}]]));
+}
@@ -0,0 +1,18 @@
+use jsonc::stats::Stats;
+use jsonc::{infer_schema, GeneratorOptions};
+use serde_json::json;
+
+#[test]
+fn fields_that_may_be_null_or_missing_are_optional() {
+    let document = json!([
+        {"a": 1, "b": "x", "c": [1, null], "d": {"e": null}},
+        {"a": 2, "b": null, "c": []},
+    ]);
+    let schema = infer_schema(&document, &GeneratorOptions::default()).unwrap();
+    let mut stats = Stats::default();
+    stats.collect(&schema);
+    // b is null in a sample and d missing from one, c's elements being null isn't c
+    assert_eq!(stats.optional_fields, 2);
+    assert_eq!((stats.types, stats.fields, stats.max_depth), (2, 5, 2));
+    assert_eq!(stats.any_paths, ["/[]/d/e"]);
+}
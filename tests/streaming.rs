@@ -0,0 +1,72 @@
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::process::{Command, Stdio};
+
+// Runs the jsonc binary with --streaming on `input`, returning what it wrote
+fn jsonc(args: &[&str], input: Option<&str>) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_jsonc"))
+        .args(["--no-config", "-q", "--streaming", "-l", "rust"])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(input.unwrap_or("").as_bytes()).unwrap();
+    drop(stdin);
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn keys_missing_from_some_elements_are_optional() {
+    let code = jsonc(
+        &[],
+        Some(r#"[{"a": 1, "b": "x"}, {"a": 2}, {"a": 3, "b": null}]"#),
+    );
+    assert!(code.contains("pub a: isize,"), "{code}");
+    assert!(code.contains("pub b: Option<String>,"), "{code}");
+}
+
+#[test]
+fn keys_missing_from_some_lines_are_optional() {
+    let code = jsonc(
+        &["--ndjson"],
+        Some("{\"a\": 1, \"b\": \"x\"}\n{\"a\": 2}\n"),
+    );
+    assert!(code.contains("pub a: isize,"), "{code}");
+    assert!(code.contains("pub b: Option<String>,"), "{code}");
+}
+
+// Writes a few hundred megabytes of records, the last of which is missing a key
+#[test]
+#[ignore = "writes and reads a 300MB document"]
+fn large_documents_are_read_in_full() {
+    let path = std::env::temp_dir().join(format!("jsonc-streaming-{}.json", std::process::id()));
+    let mut file = BufWriter::new(File::create(&path).unwrap());
+    let records = 2_000_000;
+    write!(file, "[").unwrap();
+    for id in 0..records {
+        let separator = if id == 0 { "" } else { "," };
+        if id == records - 1 {
+            write!(file, r#"{separator}{{"id":{id},"tags":[]}}"#).unwrap();
+        } else {
+            write!(
+                file,
+                r#"{separator}{{"id":{id},"name":"record {id:0>120}","tags":["a","b","c"],"score":{id}.5}}"#
+            )
+            .unwrap();
+        }
+    }
+    write!(file, "]").unwrap();
+    file.into_inner().unwrap().sync_all().unwrap();
+    assert!(fs::metadata(&path).unwrap().len() > 300_000_000);
+
+    let code = jsonc(&[path.to_str().unwrap()], None);
+    fs::remove_file(&path).unwrap();
+    assert!(code.contains("pub id: isize,"), "{code}");
+    assert!(code.contains("pub name: Option<String>,"), "{code}");
+    assert!(code.contains("pub score: Option<f64>,"), "{code}");
+    assert!(code.contains("pub tags: Vec<String>,"), "{code}");
+}
@@ -0,0 +1,150 @@
+use jsonc::language::LANGUAGES;
+use jsonc::{generate_code, GeneratorOptions};
+
+const TORTURE: &str = include_str!("fixtures/torture.json");
+
+fn generate(language: &str) -> String {
+    generate_code(TORTURE, language, &GeneratorOptions::default())
+        .unwrap_or_else(|err| panic!("{language}: {err}"))
+}
+
+fn is_identifier(name: &str) -> bool {
+    let name = name.strip_prefix("r#").unwrap_or(name);
+    name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+// Keys with quotes, backslashes, tabs, line breaks and escaped characters stay escaped in
+// string literals, and don't break the lines they're on
+#[test]
+fn every_language_escapes_keys() {
+    for (language, ..) in LANGUAGES {
+        let code = generate(language);
+        for line in code.lines() {
+            let content = line.trim_start();
+            assert!(!content.starts_with("line"), "{language}:\n{code}");
+            assert!(!content.contains(char::is_control), "{language}: {line:?}");
+            // Names in backticks and single quoted strings can have double quotes in them,
+            // and Parquet names can have anything but what separates them
+            let quoted = ["`a\"b`", "'a\"b'"].iter().any(|q| line.contains(q));
+            assert!(
+                !line.contains("a\"b") || quoted || *language == "parquet",
+                "{language}: {line}"
+            );
+        }
+        if ["avro", "bigquery"].contains(language) {
+            let parsed = serde_json::from_str::<serde_json::Value>(&code);
+            assert!(parsed.is_ok(), "{language}:\n{code}");
+        }
+    }
+}
+
+fn is_ascii_identifier(name: &str) -> bool {
+    is_identifier(name) && name.is_ascii()
+}
+
+// `name` in `quote`s, with none of `forbidden` in it
+fn is_quoted(name: &str, quote: (&str, &str), forbidden: &[char]) -> bool {
+    let inner = name
+        .strip_prefix(quote.0)
+        .and_then(|name| name.strip_suffix(quote.1));
+    inner.is_some_and(|inner| !inner.is_empty() && !inner.contains(forbidden))
+}
+
+// The name of the field declared on `line`, None if it declares none
+fn declared<'a>(language: &str, line: &'a str) -> Option<&'a str> {
+    let content = line.trim_start();
+    let member = line.starts_with("    ");
+    let last = |line: &'a str| line.trim_end_matches([';', ',']).rsplit(' ').next();
+    let before = |line: &'a str, end: &str| line.split_once(end).map(|(name, _)| name);
+    match language {
+        "avro" | "bigquery" => before(content.strip_prefix("\"name\": \"")?, "\""),
+        "c" | "cpp" if member && content.ends_with(';') && !content.contains(['(', '}']) => {
+            last(content).map(|name| name.trim_start_matches('*'))
+        }
+        "capnp" if content.contains(" @") => before(content, " "),
+        "crystal" => before(content.strip_prefix("getter ")?, " :"),
+        "dart" => last(content.strip_prefix("final ")?),
+        "elixir" if content.ends_with(',') => before(content, ": "),
+        "elm" | "haskell" => before(content.strip_prefix(['{', ','])?.trim_start(), " :"),
+        "flatbuffers" | "graphql" if line.starts_with("  ") => before(content, ":"),
+        "fsharp" if line.starts_with("        ") && !content.starts_with("[<") => {
+            before(content, ": ")
+        }
+        "go" if line.starts_with('\t') => content.split_whitespace().next(),
+        "groovy" if member && !content.starts_with('@') => last(content),
+        "io-ts" | "zod" | "scala" if line.starts_with("  ") => before(content, ": "),
+        "java" if line.starts_with("    public ") => last(content),
+        "julia" if member => before(content, "::"),
+        "kotlin" => before(content.strip_prefix("val ")?, ": "),
+        "nim" => before(content, "*: "),
+        "objc" => {
+            last(content.strip_prefix("@property ")?).map(|name| name.trim_start_matches('*'))
+        }
+        "ocaml" => before(content, " : "),
+        "parquet" => {
+            let mut words = content.split(' ');
+            let repetition = words.next()?;
+            let repeated = ["required", "optional", "repeated"].contains(&repetition);
+            repeated.then(|| words.nth(1).map(|name| name.trim_end_matches(';')))?
+        }
+        "php" => last(content.strip_prefix("public readonly ")?)?.strip_prefix('$'),
+        "proto" if line.starts_with("  ") => last(before(content, " = ")?),
+        "pydantic" | "python" if member => before(content, ": "),
+        "ruby" => before(content.strip_prefix(':')?, ","),
+        "rust" => before(line.strip_prefix("    pub ")?, ":"),
+        "thrift" if content.starts_with(|c: char| c.is_ascii_digit()) => last(content),
+        "xsd" => before(content.strip_prefix("<xs:element name=\"")?, "\""),
+        "zig" if member => before(content, ": "),
+        _ => None,
+    }
+}
+
+// Whether the field `name` can be declared in `language`
+fn is_field_name(language: &str, name: &str) -> bool {
+    let lower = name.starts_with(|c: char| c.is_lowercase() || c == '_');
+    match language {
+        "avro" | "bigquery" | "c" | "cpp" | "dart" | "flatbuffers" | "objc" | "proto"
+        | "thrift" => is_ascii_identifier(name),
+        "capnp" => is_ascii_identifier(name) && lower && !name.contains('_'),
+        "elm" | "haskell" => is_identifier(name) && lower,
+        "fsharp" => is_identifier(name) || is_quoted(name, ("``", "``"), &['`', '\n']),
+        "go" => is_identifier(name) && name.starts_with(char::is_uppercase),
+        "graphql" => is_ascii_identifier(name) && !name.starts_with("__"),
+        "io-ts" | "zod" => is_identifier(name) || is_quoted(name, ("\"", "\""), &['\n']),
+        // The JVM doesn't allow some characters in names, even in backticks
+        "kotlin" => {
+            let forbidden = ['`', '\n', '.', ';', '[', ']', '/', '<', '>', ':', '\\'];
+            is_identifier(name) || is_quoted(name, ("`", "`"), &forbidden)
+        }
+        "nim" => {
+            let underscores = name.starts_with('_') || name.ends_with('_') || name.contains("__");
+            is_identifier(name) && !underscores || is_quoted(name, ("`", "`"), &['`', '\n'])
+        }
+        "ocaml" => is_ascii_identifier(name) && lower,
+        // Parquet names can have anything but what separates them
+        "parquet" => !name.is_empty() && !name.contains([' ', ',', ';', '{', '}', '(', ')', '=']),
+        "pydantic" => is_identifier(name) && !name.starts_with('_'),
+        "scala" => is_identifier(name) || is_quoted(name, ("`", "`"), &['`', '\n']),
+        "zig" => is_identifier(name) || is_quoted(name, ("@\"", "\""), &['\n']),
+        _ => is_identifier(name),
+    }
+}
+
+// Fields are named after the keys in every language that has names, which can't have just
+// anything in them
+#[test]
+fn field_names_are_identifiers() {
+    for (language, ..) in LANGUAGES {
+        if *language == "openapi" {
+            // Properties are named by strings
+            continue;
+        }
+        let code = generate(language);
+        let names: Vec<&str> = code.lines().filter_map(|l| declared(language, l)).collect();
+        assert!(names.len() >= 12, "{language}: {names:?}\n{code}");
+        for name in names {
+            assert!(is_field_name(language, name), "{language}: {name}");
+        }
+    }
+}